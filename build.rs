@@ -0,0 +1,5 @@
+fn main() {
+    // link flags for loading the library as a Node.js addon
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+}
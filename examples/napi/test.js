@@ -0,0 +1,17 @@
+const assert = require('node:assert');
+const dlx = require('./dlx.node');
+const { matrix, solutions } = require('./fixture.json');
+
+(async () => {
+    assert.deepStrictEqual(dlx.solveAll(matrix), solutions);
+    assert.deepStrictEqual(dlx.solveOnce(matrix), solutions[0]);
+    assert.strictEqual(dlx.countSolutions(matrix), solutions.length);
+    assert.deepStrictEqual(await dlx.solveAllAsync(matrix), dlx.solveAll(matrix));
+    assert.throws(() => dlx.solveAll([[true], [true, false]]), /row 1/);
+    assert.deepStrictEqual(dlx.matrixFromCourses([[true]]), [[true, true], [false, true]]);
+    assert.throws(() => dlx.sudokuHints([[0]]), /9 by 9/);
+    console.log('ok');
+})().catch(error => {
+    console.error(error);
+    process.exit(1);
+});
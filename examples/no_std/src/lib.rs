@@ -0,0 +1,34 @@
+//! A library without `std` using the solver, to check it builds without the default features. It
+//! brings its own panic handler and allocator, which it couldn't if the solver pulled in `std`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::alloc::{GlobalAlloc, Layout};
+use core::panic::PanicInfo;
+use core::ptr;
+use dlx::Node;
+
+/// An allocator that never has memory: the library is only built, never run.
+struct NoMemory;
+unsafe impl GlobalAlloc for NoMemory {
+    unsafe fn alloc(&self, _layout: Layout) -> *mut u8 { ptr::null_mut() }
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: NoMemory = NoMemory;
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+/// Count the solutions of the `n` by `n` identity matrix.
+#[unsafe(no_mangle)]
+pub extern "C" fn count_identity(n: usize) -> u64 {
+    let input: Vec<Vec<bool>> = (0..n).map(|row| (0..n).map(|col| col == row).collect()).collect();
+    Node::count_solutions(&input)
+}
@@ -0,0 +1,6 @@
+#![no_main]
+
+use dlx::fuzz::FuzzGame2D;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|game: FuzzGame2D| game.check());
@@ -0,0 +1,6 @@
+#![no_main]
+
+use dlx::fuzz::FuzzMatrix;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|matrix: FuzzMatrix| matrix.check());
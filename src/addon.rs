@@ -0,0 +1,104 @@
+//! The solver as a Node.js native addon, with heavy solves running on the libuv thread pool.
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use crate::Node;
+use crate::generation::courses;
+use crate::generation::sudoku::{self, Grid, HintKind};
+
+/// Check that a matrix has rows and columns, all rows being as wide.
+fn check(matrix: &[Vec<bool>]) -> Result<()> {
+    Node::check_input(matrix).map(|_| ()).map_err(|error| Error::new(Status::InvalidArg, error.to_string()))
+}
+
+fn to_js(solution: Vec<usize>) -> Vec<u32> {
+    solution.into_iter().map(|row| row as u32).collect()
+}
+
+/// Find all solutions of a matrix.
+#[napi]
+pub fn solve_all(matrix: Vec<Vec<bool>>) -> Result<Vec<Vec<u32>>> {
+    check(&matrix)?;
+    Ok(Node::solve_all(&matrix).into_iter().map(to_js).collect())
+}
+
+/// Find one solution of a matrix, or `null`.
+#[napi]
+pub fn solve_once(matrix: Vec<Vec<bool>>) -> Result<Option<Vec<u32>>> {
+    check(&matrix)?;
+    Ok(Node::solve_once(&matrix).map(to_js))
+}
+
+/// Count the solutions of a matrix, exactly up to 2^53.
+#[napi]
+pub fn count_solutions(matrix: Vec<Vec<bool>>) -> Result<f64> {
+    check(&matrix)?;
+    Ok(Node::count_solutions(&matrix) as f64)
+}
+
+/// Finding all solutions of a matrix off the event loop.
+pub struct SolveAllTask {
+    matrix: Vec<Vec<bool>>,
+}
+impl Task for SolveAllTask {
+    type Output = Vec<Vec<usize>>;
+    type JsValue = Vec<Vec<u32>>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(Node::solve_all(&self.matrix))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into_iter().map(to_js).collect())
+    }
+}
+
+/// Find all solutions of a matrix on the thread pool, resolving to them.
+#[napi(ts_return_type = "Promise<number[][]>")]
+pub fn solve_all_async(matrix: Vec<Vec<bool>>) -> Result<AsyncTask<SolveAllTask>> {
+    check(&matrix)?;
+    Ok(AsyncTask::new(SolveAllTask { matrix }))
+}
+
+/// Create the matrix of courses, given the time slots each one can be put in.
+#[napi]
+pub fn matrix_from_courses(courses: Vec<Vec<bool>>) -> Result<Vec<Vec<bool>>> {
+    check(&courses)?;
+    courses::matrix_from_courses(&courses).map_err(|error| Error::new(Status::InvalidArg, error.to_string()))
+}
+
+/// A digit a sudoku cell must hold.
+#[napi(object)]
+pub struct SudokuHint {
+    pub row: u32,
+    pub col: u32,
+    pub digit: u32,
+    /// `"nakedSingle"`, `"hiddenSingle"` or `"forced"`.
+    pub kind: String,
+}
+
+/// Find the empty cells of a sudoku whose digit follows from the clues, 0 being an empty cell.
+#[napi]
+pub fn sudoku_hints(grid: Vec<Vec<u32>>, easiest: Option<bool>) -> Result<Vec<SudokuHint>> {
+    let mut cells: Grid = [[0; 9]; 9];
+    if grid.len() != 9 || grid.iter().any(|row| row.len() != 9) {
+        return Err(Error::new(Status::InvalidArg, "the grid is not 9 by 9"));
+    }
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &digit) in line.iter().enumerate() {
+            cells[row][col] = digit.try_into().unwrap_or(u8::MAX);
+        }
+    }
+
+    let hints = sudoku::hints(&cells, easiest.unwrap_or(false)).map_err(|error| Error::new(Status::InvalidArg, error.to_string()))?;
+    Ok(hints.into_iter().map(|hint| SudokuHint {
+        row: hint.row as u32,
+        col: hint.col as u32,
+        digit: hint.digit as u32,
+        kind: match hint.kind {
+            HintKind::NakedSingle => "nakedSingle",
+            HintKind::HiddenSingle => "hiddenSingle",
+            HintKind::Forced => "forced",
+        }.to_string(),
+    }).collect())
+}
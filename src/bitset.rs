@@ -0,0 +1,119 @@
+//! An exact cover search over rows as bitmasks, for matrices of at most 128 columns.
+
+use alloc::{vec, vec::Vec};
+use core::ops::ControlFlow;
+use crate::search::{Backend, ColumnStrategy, SolveOptions, SolveStats, should_stop};
+#[cfg(feature = "log")]
+use crate::search::{log_column, log_depth, log_solution};
+
+/// Check if a matrix is searched with bitsets under some options: when asked to, or when it is narrow
+/// enough and the options need nothing only dancing links do.
+pub(crate) fn applies(input: &[Vec<bool>], options: &SolveOptions) -> bool {
+    match options.backend {
+        Backend::Bitset => true,
+        Backend::DancingLinks => false,
+        Backend::Auto => {
+            input[0].len() <= 128
+                && options.column_strategy == ColumnStrategy::Smallest
+                && options.memo_capacity.is_none()
+                && options.row_seed.is_none()
+                && !options.early_contradiction
+                && !options.merge_duplicate_columns
+                && !options.reports_progress()
+        }
+    }
+}
+
+/// A search over the rows of a matrix as masks of their columns, covering a column being setting its
+/// bit. It goes through the tree dancing links would with the smallest column strategy.
+pub(crate) struct BitsetSearch<'a> {
+    options: &'a SolveOptions,
+    /// The columns of every row.
+    rows: Vec<u128>,
+    /// The rows of every column, top to bottom.
+    columns: Vec<Vec<usize>>,
+    /// The columns to cover exactly once, the others at most once.
+    primary: u128,
+    solution: Vec<usize>,
+    /// Whether the search stopped because it was cancelled, ran out of nodes, or passed its deadline.
+    pub interrupted: bool,
+    pub stats: SolveStats,
+}
+impl<'a> BitsetSearch<'a> {
+    /// Turn the rows of a matrix into masks. Panics if it has more than 128 columns.
+    pub fn new(input: &[Vec<bool>], options: &'a SolveOptions) -> BitsetSearch<'a> {
+        let width = input[0].len();
+        assert!(width <= 128, "a matrix of {width} columns doesn't fit bitsets of 128");
+
+        let mut columns = vec![Vec::new(); width];
+        let rows = input.iter().enumerate().map(|(y, row)| {
+            let forbidden = options.forbid_rows.contains(&y);
+            row.iter().enumerate().filter(|(_, val)| **val).fold(0, |mask, (x, _)| {
+                if !forbidden { columns[x].push(y); }
+                mask | 1 << x
+            })
+        }).collect();
+
+        let primary = options.primary_columns.unwrap_or(width).min(width);
+        let primary = if primary == 128 { u128::MAX } else { (1 << primary) - 1 };
+        BitsetSearch { options, rows, columns, primary, solution: Vec::new(), interrupted: false, stats: SolveStats::default() }
+    }
+
+    /// The columns covered by the rows the options require.
+    pub fn required(&self) -> u128 {
+        self.options.require_rows.iter().fold(0, |covered, &row| covered | self.rows[row])
+    }
+
+    /// Search solutions with the columns of `covered` covered, handing each one to `on_solution` until
+    /// it breaks.
+    pub fn run(&mut self, covered: u128, on_solution: &mut impl FnMut(&[usize]) -> ControlFlow<()>) -> ControlFlow<()> {
+        if should_stop(self.options, self.stats.nodes) {
+            self.interrupted = true;
+            return ControlFlow::Break(());
+        }
+
+        self.stats.nodes += 1;
+        #[cfg(feature = "log")]
+        if self.solution.len() > self.stats.max_depth { log_depth(self.solution.len(), &self.stats); }
+        self.stats.max_depth = self.stats.max_depth.max(self.solution.len());
+        let rows = self.options.require_rows.len() + self.solution.len();
+        if covered & self.primary == self.primary {
+            if self.options.min_rows.is_some_and(|min| rows < min) { return ControlFlow::Continue(()); }
+            self.stats.solutions += 1;
+            #[cfg(feature = "log")]
+            log_solution(&self.stats);
+            return on_solution(&self.solution);
+        }
+        if self.options.max_rows.is_some_and(|max| rows >= max) { return ControlFlow::Continue(()); }
+
+        // the leftmost column with the fewest rows left
+        let mut best: Option<(usize, usize)> = None;
+        let mut left = !covered & self.primary;
+        while left != 0 {
+            let col = left.trailing_zeros() as usize;
+            left &= left - 1;
+            self.stats.headers_scanned += 1;
+
+            let size = self.columns[col].iter().filter(|&&row| self.rows[row] & covered == 0).count();
+            if best.is_none_or(|(min, _)| size < min) { best = Some((size, col)); }
+        }
+        let Some((size, col)) = best else { return ControlFlow::Continue(()) };
+        #[cfg(feature = "log")]
+        log_column(col, size, &self.stats);
+        self.stats.covers += 1;
+        if size == 0 { self.stats.backtracks += 1; }
+
+        for i in 0..self.columns[col].len() {
+            let row = self.columns[col][i];
+            let mask = self.rows[row];
+            if mask & covered != 0 { continue; }
+
+            self.stats.covers += mask.count_ones() as u64 - 1;
+            self.solution.push(row);
+            let flow = self.run(covered | mask, on_solution);
+            self.solution.pop();
+            flow?;
+        }
+        ControlFlow::Continue(())
+    }
+}
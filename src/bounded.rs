@@ -0,0 +1,121 @@
+//! Covering every column between a least and a most number of times, following Knuth's algorithm M.
+
+use alloc::{vec, vec::Vec};
+use crate::{Node, Structure};
+
+/// A search over a structure whose columns carry bounds on how many chosen rows cover them.
+struct Bounded<'a> {
+    structure: &'a mut Structure,
+    /// The least and most rows to cover every column, by header.
+    bounds: Vec<(usize, usize)>,
+    /// The chosen rows covering every column, by header.
+    counts: Vec<usize>,
+    solution: Vec<usize>,
+    results: Vec<Vec<usize>>,
+}
+impl Bounded<'_> {
+    /// Find every set of rows, among the ones left, meeting the bounds of the columns left.
+    fn search(&mut self) {
+        if self.structure.is_solved() {
+            self.results.push(self.solution.clone());
+            return;
+        }
+        let Some(col) = self.choose_column() else { return };
+
+        // choose each row of the column in turn, then leave it out of the later branches
+        let mut hidden = Vec::new();
+        let mut row = self.structure.down(col);
+        while row != col {
+            self.choose(row);
+            self.search();
+            self.unchoose(row);
+
+            let next = self.structure.down(row);
+            self.structure.hide_row(row);
+            hidden.push(row);
+            row = next;
+        }
+
+        // or choose none of the rows left, if the column is covered enough
+        if self.counts[col] >= self.bounds[col].0 {
+            self.structure.cover(col);
+            self.search();
+            self.structure.uncover(col);
+        }
+
+        for &row in hidden.iter().rev() {
+            self.structure.unhide_row(row);
+        }
+    }
+
+    /// Get the column with the fewest ways to go on, or `None` if one can't be covered enough.
+    fn choose_column(&self) -> Option<usize> {
+        let mut best: Option<(usize, usize)> = None;
+        for col in self.structure.walk(Structure::ROOT, Structure::right) {
+            let need = self.bounds[col].0.saturating_sub(self.counts[col]);
+            let size = self.structure.nodes[col].data;
+            if size < need { return None; }
+
+            // the rows that may come first, plus leaving them all out
+            let branches = size - need + 1;
+            if best.is_none_or(|(min, _)| branches < min) {
+                best = Some((branches, col));
+            }
+        }
+        best.map(|(_, col)| col)
+    }
+
+    /// Add a row to the solution, taking it out of its columns and covering the ones it fills up.
+    fn choose(&mut self, row: usize) {
+        self.solution.push(self.structure.nodes[row].data);
+        let mut node = row;
+        loop {
+            self.structure.unlink_ud(node);
+            let col = self.structure.column(node);
+            self.structure.nodes[col].data -= 1;
+            self.counts[col] += 1;
+            if self.counts[col] == self.bounds[col].1 { self.structure.cover(col); }
+
+            node = self.structure.right(node);
+            if node == row { break; }
+        }
+    }
+
+    /// Undo choosing a row, in reverse order.
+    fn unchoose(&mut self, row: usize) {
+        let mut node = row;
+        loop {
+            node = self.structure.left(node);
+            let col = self.structure.column(node);
+            if self.counts[col] == self.bounds[col].1 { self.structure.uncover(col); }
+            self.counts[col] -= 1;
+            self.structure.nodes[col].data += 1;
+            self.structure.link_ud(node);
+
+            if node == row { break; }
+        }
+        self.solution.pop();
+    }
+}
+
+/// Find all sets of rows covering every column between its bounds, `(1, 1)` past the end of `bounds`.
+pub(crate) fn solve_all_bounded(input: &[Vec<bool>], bounds: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut structure = Node::build(input);
+    let width = input[0].len();
+
+    let mut header_bounds = vec![(0, 0); 1 + width];
+    for x in 0..width {
+        let (min, max) = bounds.get(x).copied().unwrap_or((1, 1));
+        assert!(min <= max, "column {x} must be covered at least {min} times but at most {max}");
+        header_bounds[x + 1] = (min, max);
+    }
+
+    // a column covered at most 0 times rules out its rows from the start
+    for (header, _) in header_bounds.iter().enumerate().skip(1).filter(|(_, (_, max))| *max == 0) {
+        structure.cover(header);
+    }
+
+    let mut search = Bounded { structure: &mut structure, bounds: header_bounds, counts: vec![0; 1 + width], solution: Vec::new(), results: Vec::new() };
+    search.search();
+    search.results
+}
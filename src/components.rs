@@ -0,0 +1,62 @@
+//! Splitting a matrix into parts sharing no column, solved on their own.
+
+use alloc::{vec, vec::Vec};
+use crate::Node;
+
+/// Find the representative of a column, halving the path to it on the way.
+fn find(parent: &mut [usize], mut col: usize) -> usize {
+    while parent[col] != col {
+        parent[col] = parent[parent[col]];
+        col = parent[col];
+    }
+    col
+}
+
+/// Group the columns of a matrix into components, two columns being in the same one when a row
+/// covers both, along with the rows of every component. Components come in the order of their first
+/// column, columns and rows ascending. Rows without entries are in none.
+pub(crate) fn split_components(input: &[Vec<bool>]) -> Vec<(Vec<usize>, Vec<usize>)> {
+    let width = input.first().map_or(0, Vec::len);
+    let mut parent: Vec<usize> = (0..width).collect();
+    for row in input {
+        let mut cols = row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| x);
+        let Some(first) = cols.next() else { continue };
+        for col in cols {
+            let (a, b) = (find(&mut parent, first), find(&mut parent, col));
+            parent[a.max(b)] = a.min(b);
+        }
+    }
+
+    // the representative of a component is always its first column
+    let mut component = vec![usize::MAX; width];
+    let mut components: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+    for col in 0..width {
+        let root = find(&mut parent, col);
+        component[col] = if root == col { components.push((Vec::new(), Vec::new())); components.len() - 1 } else { component[root] };
+        components[component[col]].0.push(col);
+    }
+    for (y, row) in input.iter().enumerate() {
+        if let Some(x) = row.iter().position(|&val| val) { components[component[x]].1.push(y); }
+    }
+    components
+}
+
+/// Solve every component on its own and combine their solutions, giving up as soon as one has none.
+pub(crate) fn solve_all_decomposed(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+    let mut combined = vec![Vec::new()];
+    for (cols, rows) in split_components(input) {
+        if rows.is_empty() { return Vec::new(); }
+
+        let matrix: Vec<Vec<bool>> = rows.iter().map(|&y| cols.iter().map(|&x| input[y][x]).collect()).collect();
+        let solutions = Node::solve_all(&matrix);
+        if solutions.is_empty() { return Vec::new(); }
+
+        let rows = &rows;
+        combined = combined.iter()
+            .flat_map(|partial| solutions.iter().map(move |solution| {
+                partial.iter().copied().chain(solution.iter().map(|&row| rows[row])).collect()
+            }))
+            .collect();
+    }
+    combined
+}
@@ -0,0 +1,104 @@
+//! Covering every column at least once, rows being allowed to overlap.
+
+use alloc::{vec, vec::Vec};
+use crate::{Node, Structure};
+
+/// A search for sets of rows covering every column at least once. Covering a column only takes it
+/// out of the columns left: the rows in it stay in their other columns, as they may still be chosen.
+struct SetCover<'a> {
+    structure: &'a mut Structure,
+    /// The chosen rows covering every column, by header.
+    counts: Vec<usize>,
+    /// The first node of every chosen row.
+    chosen: Vec<usize>,
+    /// Keep only the smallest cover, cutting branches that can't beat it.
+    minimize: bool,
+    results: Vec<Vec<usize>>,
+}
+impl SetCover<'_> {
+    /// Find every minimal cover of the columns left with the rows left, or a smaller cover than the
+    /// one found so far when minimizing.
+    fn search(&mut self) {
+        if self.structure.is_solved() {
+            let solution = self.chosen.iter().map(|&row| self.structure.nodes[row].data).collect();
+            if self.minimize { self.results.clear(); }
+            self.results.push(solution);
+            return;
+        }
+        // another row at least is needed
+        if self.minimize && self.results.first().is_some_and(|best| self.chosen.len() + 1 >= best.len()) { return; }
+
+        let Some(col) = self.choose_column() else { return };
+
+        // choose each row of the column in turn, then leave it out of the later branches, so no set of
+        // rows is found twice
+        let mut hidden = Vec::new();
+        let mut row = self.structure.down(col);
+        while row != col {
+            self.choose(row);
+            if !self.redundant() { self.search(); }
+            self.unchoose(row);
+
+            let next = self.structure.down(row);
+            self.structure.hide_row(row);
+            hidden.push(row);
+            row = next;
+        }
+
+        for &row in hidden.iter().rev() {
+            self.structure.unhide_row(row);
+        }
+    }
+
+    /// Get the column left with the fewest rows, the leftmost on ties, or `None` if one has none.
+    fn choose_column(&self) -> Option<usize> {
+        let col = self.structure.walk(Structure::ROOT, Structure::right).min_by_key(|&col| self.structure.nodes[col].data)?;
+        (self.structure.nodes[col].data > 0).then_some(col)
+    }
+
+    /// Check if a chosen row covers no column on its own. Chosen rows only ever cover more, so no
+    /// minimal cover has the rows chosen so far.
+    fn redundant(&self) -> bool {
+        self.chosen.iter().any(|&row| {
+            core::iter::once(row).chain(self.structure.walk(row, Structure::right))
+                .all(|node| self.counts[self.structure.column(node)] > 1)
+        })
+    }
+
+    /// Add a row to the cover, covering the columns it is the first to cover.
+    fn choose(&mut self, row: usize) {
+        self.chosen.push(row);
+        let mut node = row;
+        loop {
+            let col = self.structure.column(node);
+            self.counts[col] += 1;
+            if self.counts[col] == 1 { self.structure.unlink_lr(col); }
+
+            node = self.structure.right(node);
+            if node == row { break; }
+        }
+    }
+
+    /// Undo adding a row to the cover, in reverse order.
+    fn unchoose(&mut self, row: usize) {
+        let mut node = row;
+        loop {
+            node = self.structure.left(node);
+            let col = self.structure.column(node);
+            if self.counts[col] == 1 { self.structure.link_lr(col); }
+            self.counts[col] -= 1;
+
+            if node == row { break; }
+        }
+        self.chosen.pop();
+    }
+}
+
+/// Find all minimal sets of rows covering every column at least once, or a smallest one.
+pub(crate) fn solve_cover(input: &[Vec<bool>], minimize_rows: bool) -> Vec<Vec<usize>> {
+    let mut structure = Node::build(input);
+    let width = input[0].len();
+    let mut search = SetCover { structure: &mut structure, counts: vec![0; 1 + width], chosen: Vec::new(), minimize: minimize_rows, results: Vec::new() };
+    search.search();
+    search.results
+}
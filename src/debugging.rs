@@ -0,0 +1,62 @@
+//! Tools for turning a large failing instance into a small reproducer.
+
+use alloc::vec::Vec;
+
+/// Most times `shrink_instance` checks the predicate.
+pub const MAX_CHECKS: usize = 10_000;
+
+/// Shrink a matrix for which `predicate` holds into a locally minimal one for which it still holds,
+/// by delta debugging: chunks of rows, then of columns, are removed while the predicate keeps holding,
+/// halving the chunks down to single rows and columns, until a whole pass removes nothing.
+///
+/// The predicate is checked at most `MAX_CHECKS` times, and the result is the same for the same
+/// input. If the predicate doesn't hold for the input, it is returned unchanged. To shrink an instance
+/// making the solver panic, check with `std::panic::catch_unwind` in the predicate.
+pub fn shrink_instance(input: &[Vec<bool>], predicate: impl Fn(&Vec<Vec<bool>>) -> bool) -> Vec<Vec<bool>> {
+    let mut current = input.to_vec();
+    let mut checks = 1;
+    if !predicate(&current) { return current; }
+
+    let mut check = |candidate: &Vec<Vec<bool>>| {
+        if checks >= MAX_CHECKS { return false; }
+        checks += 1;
+        predicate(candidate)
+    };
+
+    loop {
+        let size = (current.len(), current.first().map_or(0, Vec::len));
+
+        current = shrink_along(current, |matrix| matrix.len(), |matrix, start, end| {
+            matrix.drain(start..end);
+        }, &mut check);
+        current = shrink_along(current, |matrix| matrix.first().map_or(0, Vec::len), |matrix, start, end| {
+            for row in matrix { row.drain(start..end); }
+        }, &mut check);
+
+        if (current.len(), current.first().map_or(0, Vec::len)) == size { return current; }
+    }
+}
+
+/// Remove chunks of rows or columns from a matrix as long as the check holds, halving the chunks
+/// down to one.
+fn shrink_along(
+    mut current: Vec<Vec<bool>>,
+    len: fn(&Vec<Vec<bool>>) -> usize,
+    remove: fn(&mut Vec<Vec<bool>>, usize, usize),
+    check: &mut impl FnMut(&Vec<Vec<bool>>) -> bool,
+) -> Vec<Vec<bool>> {
+    let mut chunk = len(&current).div_ceil(2);
+    while chunk > 0 {
+        let mut start = 0;
+        while start < len(&current) {
+            let end = (start + chunk).min(len(&current));
+            let mut candidate = current.clone();
+            remove(&mut candidate, start, end);
+
+            // keep looking at the same position, now holding what came after the chunk
+            if check(&candidate) { current = candidate; } else { start = end; }
+        }
+        chunk /= 2;
+    }
+    current
+}
@@ -0,0 +1,23 @@
+//! Solutions along with the row covering every column.
+
+use alloc::{vec, vec::Vec};
+
+/// A solution found by `Node::solve_all_detailed`, with the row chosen for every column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolutionDetail {
+    /// The rows of the solution, in the order they were chosen.
+    pub rows: Vec<usize>,
+    /// The row covering every column, or `None` for a secondary column no row covers.
+    pub covered_by: Vec<Option<usize>>,
+}
+
+/// Find the row covering every column in a solution.
+pub(crate) fn detail(input: &[Vec<bool>], rows: &[usize]) -> SolutionDetail {
+    let mut covered_by = vec![None; input[0].len()];
+    for &row in rows {
+        for (x, _) in input[row].iter().enumerate().filter(|(_, val)| **val) {
+            covered_by[x] = Some(row);
+        }
+    }
+    SolutionDetail { rows: rows.to_vec(), covered_by }
+}
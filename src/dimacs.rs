@@ -0,0 +1,60 @@
+//! Exact cover problems written as boolean formulas in conjunctive normal form, for SAT solvers.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+/// Columns with at most this many rows forbid every pair of them; wider ones use a sequential counter,
+/// whose clauses grow linearly instead of quadratically.
+const PAIRWISE_MAX: usize = 6;
+
+/// Write a problem in the DIMACS CNF format. Variable `y + 1` is true when row `y` is chosen, and the
+/// variables after the rows are the counters of wide columns.
+pub(crate) fn to_dimacs_cnf(input: &[Vec<bool>]) -> String {
+    let width = input.first().map_or(0, Vec::len);
+    let mut clauses: Vec<Vec<i64>> = Vec::new();
+    let mut variables = input.len() as i64;
+
+    for x in 0..width {
+        let rows: Vec<i64> = (0..input.len()).filter(|&y| input[y][x]).map(|y| y as i64 + 1).collect();
+        // at least one, an empty clause for a column without rows
+        clauses.push(rows.clone());
+
+        // at most one
+        if rows.len() <= PAIRWISE_MAX {
+            for (i, &a) in rows.iter().enumerate() {
+                for &b in &rows[i + 1..] { clauses.push(vec![-a, -b]); }
+            }
+        } else {
+            // counter i is true once one of the first i + 1 rows is chosen
+            let counters: Vec<i64> = (variables + 1..variables + rows.len() as i64).collect();
+            variables += counters.len() as i64;
+            clauses.push(vec![-rows[0], counters[0]]);
+            for i in 1..rows.len() - 1 {
+                clauses.push(vec![-rows[i], counters[i]]);
+                clauses.push(vec![-counters[i - 1], counters[i]]);
+                clauses.push(vec![-rows[i], -counters[i - 1]]);
+            }
+            clauses.push(vec![-rows[rows.len() - 1], -counters[rows.len() - 2]]);
+        }
+    }
+
+    // a row without entries is in no solution
+    for (y, _) in input.iter().enumerate().filter(|(_, row)| !row.contains(&true)) {
+        clauses.push(vec![-(y as i64 + 1)]);
+    }
+
+    let mut cnf = String::new();
+    // writing to a string can't fail
+    let _ = writeln!(cnf, "c exact cover of {} rows and {width} columns", input.len());
+    let _ = writeln!(cnf, "p cnf {variables} {}", clauses.len());
+    for clause in &clauses {
+        for literal in clause { let _ = write!(cnf, "{literal} "); }
+        cnf.push_str("0\n");
+    }
+    cnf
+}
+
+/// Get the rows chosen by an assignment of the variables of `to_dimacs_cnf`, from the first on.
+pub(crate) fn solution_from_assignment(rows: usize, assignment: &[bool]) -> Vec<usize> {
+    assignment.iter().take(rows).enumerate().filter(|(_, val)| **val).map(|(y, _)| y).collect()
+}
@@ -0,0 +1,63 @@
+//! Drawing the links of a structure with Graphviz.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use crate::Structure;
+
+impl Structure {
+    /// Write the nodes still linked as a graph in the DOT format: the root and the headers on one rank,
+    /// labeled with their column and its size, and the nodes of every row boxed together, labeled with
+    /// their row and column. Only the right and down links are drawn, those closing a ring dashed.
+    /// Covered columns are out of the ring of the root, so they and their nodes aren't drawn, along with
+    /// the nodes of rows taken out of their columns; secondary columns are drawn as long as they have
+    /// nodes.
+    pub fn to_dot(&self) -> String {
+        let is_secondary = |header: usize| self.left(header) == header;
+        let headers: Vec<usize> = self.walk(Structure::ROOT, Structure::right)
+            .chain((1..self.nodes.len()).filter(|&node| self.column(node) == node && is_secondary(node) && self.down(node) != node))
+            .collect();
+        let mut rows: Vec<(usize, usize)> = headers.iter()
+            .flat_map(|&header| self.walk(header, Structure::down))
+            .map(|node| (self.nodes[node].data, node))
+            .collect();
+        rows.sort_unstable();
+        let shown = |node: usize| rows.binary_search(&(self.nodes[node].data, node)).is_ok();
+
+        let mut dot = String::from("digraph dlx {\n    node [shape=box];\n");
+        // writing to a string can't fail
+        let _ = write!(dot, "    {{ rank=same; n0 [label=\"root\"];");
+        for &header in &headers {
+            let _ = write!(dot, " n{header} [label=\"column {}\\nsize {}\"];", header - 1, self.nodes[header].data);
+        }
+        dot.push_str(" }\n");
+
+        for row in rows.chunk_by(|a, b| a.0 == b.0) {
+            let _ = write!(dot, "    subgraph cluster_row{} {{ label=\"row {}\";", row[0].0, row[0].0);
+            for &(y, node) in row {
+                let _ = write!(dot, " n{node} [label=\"row {y}\\ncolumn {}\"];", self.column(node) - 1);
+            }
+            dot.push_str(" }\n");
+        }
+
+        let mut edge = |from: usize, to: usize, label: &str, wraps: bool| {
+            let style = if wraps { ", style=dashed, constraint=false" } else { "" };
+            let _ = writeln!(dot, "    n{from} -> n{to} [label=\"{label}\"{style}];");
+        };
+        for node in core::iter::once(Structure::ROOT).chain(headers.iter().copied().filter(|&header| !is_secondary(header))) {
+            edge(node, self.right(node), "R", self.right(node) == Structure::ROOT);
+        }
+        for &header in &headers {
+            for node in core::iter::once(header).chain(self.walk(header, Structure::down)) {
+                edge(node, self.down(node), "D", self.down(node) == header);
+            }
+        }
+        for &(_, node) in &rows {
+            // the next node of the row still drawn, skipping those out of their columns
+            let Some(next) = self.walk(node, Structure::right).find(|&next| shown(next)) else { continue };
+            edge(node, next, "R", next <= node);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
@@ -0,0 +1,42 @@
+//! Rows and columns of a matrix that are copies of each other.
+
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Keep the first copy of every row, in order, along with the original indices of the copies of
+/// every kept row, ascending.
+pub(crate) fn dedup_rows(input: &[Vec<bool>]) -> (Vec<Vec<bool>>, Vec<Vec<usize>>) {
+    let mut kept: HashMap<&[bool], usize> = HashMap::new();
+    let mut matrix = Vec::new();
+    let mut originals: Vec<Vec<usize>> = Vec::new();
+
+    for (y, row) in input.iter().enumerate() {
+        let index = *kept.entry(row).or_insert_with(|| {
+            matrix.push(row.clone());
+            originals.push(Vec::new());
+            matrix.len() - 1
+        });
+        originals[index].push(y);
+    }
+
+    (matrix, originals)
+}
+
+/// Map a solution of the matrix without copies to the first copy of every row in the original one.
+pub(crate) fn first_copies(solution: &[usize], originals: &[Vec<usize>]) -> Vec<usize> {
+    solution.iter().map(|&row| originals[row][0]).collect()
+}
+
+/// Find the columns that are copies of an earlier column of the same kind, primary or secondary,
+/// covered by the same rows, along with the first copy of each of them, ascending.
+pub(crate) fn duplicate_columns(input: &[Vec<bool>], primary: usize) -> Vec<(usize, usize)> {
+    let width = input.first().map_or(0, Vec::len);
+    let mut kept: HashMap<(bool, Vec<bool>), usize> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for x in 0..width {
+        let column = input.iter().map(|row| row[x]).collect();
+        let first = *kept.entry((x < primary, column)).or_insert(x);
+        if first != x { duplicates.push((x, first)); }
+    }
+    duplicates
+}
@@ -0,0 +1,54 @@
+//! Errors of the solver.
+
+use core::fmt;
+
+/// Why a problem couldn't be prepared or loaded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DlxError {
+    /// The matrix has no rows.
+    EmptyMatrix,
+    /// The matrix has rows but no columns.
+    NoColumns,
+    /// A row isn't as wide as the first one.
+    RaggedRow { row: usize, expected: usize, got: usize },
+    /// A row of the options isn't in the matrix.
+    RowOutOfRange { row: usize },
+    /// A row is both required and forbidden by the options.
+    RequiredAndForbidden { row: usize },
+    /// Two rows required by the options both cover a column.
+    RequiredConflict { column: usize, rows: (usize, usize) },
+    /// The bytes don't start like a saved structure.
+    NotDlx,
+    /// The bytes were saved in a format version this doesn't read.
+    UnsupportedVersion { version: u32 },
+    /// The bytes end before the structure does, or go on after it.
+    Truncated,
+    /// The bytes don't match their checksum.
+    ChecksumMismatch,
+    /// A link of a node points outside the structure or doesn't close its ring.
+    BrokenLink { node: usize },
+    /// A symmetry of the options isn't a permutation of the columns.
+    NotAPermutation { symmetry: usize },
+    /// A symmetry of the options moves the columns of a row to columns no row has.
+    SymmetryBreaksRow { symmetry: usize, row: usize },
+}
+impl fmt::Display for DlxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DlxError::EmptyMatrix => write!(f, "the matrix has no rows"),
+            DlxError::NoColumns => write!(f, "the matrix has no columns"),
+            DlxError::RaggedRow { row, expected, got } => write!(f, "row {row} has {got} columns instead of {expected}"),
+            DlxError::RowOutOfRange { row } => write!(f, "row {row} isn't in the matrix"),
+            DlxError::RequiredAndForbidden { row } => write!(f, "row {row} is both required and forbidden"),
+            DlxError::RequiredConflict { column, rows } => write!(f, "required rows {} and {} both cover column {column}", rows.0, rows.1),
+            DlxError::NotDlx => write!(f, "not a saved structure"),
+            DlxError::UnsupportedVersion { version } => write!(f, "unsupported format version {version}"),
+            DlxError::Truncated => write!(f, "the structure doesn't have the expected length"),
+            DlxError::ChecksumMismatch => write!(f, "the checksum doesn't match"),
+            DlxError::BrokenLink { node } => write!(f, "node {node} has a broken link"),
+            DlxError::NotAPermutation { symmetry } => write!(f, "symmetry {symmetry} isn't a permutation of the columns"),
+            DlxError::SymmetryBreaksRow { symmetry, row } => write!(f, "symmetry {symmetry} moves row {row} to columns no row has"),
+        }
+    }
+}
+impl core::error::Error for DlxError {}
@@ -0,0 +1,65 @@
+//! Solving off the calling thread behind a future.
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use crate::{Node, SolveOptions, SolveOutcome};
+
+#[derive(Default)]
+struct Shared {
+    /// The outcome of the search, or what it panicked with.
+    outcome: Option<thread::Result<SolveOutcome>>,
+    waker: Option<Waker>,
+}
+
+/// A search running on its own thread, cancelled when dropped before it is done.
+pub struct SolveFuture {
+    shared: Arc<Mutex<Shared>>,
+    cancel: Arc<AtomicBool>,
+    done: bool,
+}
+impl SolveFuture {
+    /// Start solving on a new thread.
+    pub(crate) fn spawn(input: Vec<Vec<bool>>, mut options: SolveOptions) -> SolveFuture {
+        let cancel = options.cancel.get_or_insert_with(Default::default).clone();
+        let shared = Arc::new(Mutex::new(Shared::default()));
+
+        let worker = shared.clone();
+        thread::spawn(move || {
+            // a panic is handed to the future, which would otherwise wait forever
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| Node::solve_all_outcome(&input, &options).0));
+            let mut shared = worker.lock().unwrap();
+            shared.outcome = Some(outcome);
+            if let Some(waker) = shared.waker.take() { waker.wake(); }
+        });
+
+        SolveFuture { shared, cancel, done: false }
+    }
+}
+impl Future for SolveFuture {
+    type Output = SolveOutcome;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<SolveOutcome> {
+        let mut shared = self.shared.lock().unwrap();
+        let Some(outcome) = shared.outcome.take() else {
+            shared.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        };
+
+        drop(shared);
+        self.done = true;
+        match outcome {
+            Ok(outcome) => Poll::Ready(outcome),
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+impl Drop for SolveFuture {
+    fn drop(&mut self) {
+        if !self.done { self.cancel.store(true, Ordering::Relaxed); }
+    }
+}
@@ -0,0 +1,159 @@
+//! Bounded generators for fuzzing the solver and the block pipeline, and the properties their
+//! results must satisfy. Every generated value is small enough to solve in an instant, and fewer
+//! input bytes always give a smaller value, so fuzzers shrink failing cases well.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use crate::Node;
+use crate::generation::blocks::{Block2D, Game2D, Solution2D};
+
+/// Most rows of a `FuzzMatrix`.
+pub const MAX_ROWS: usize = 12;
+/// Most columns of a `FuzzMatrix`.
+pub const MAX_COLUMNS: usize = 10;
+/// Most percentage of filled entries in a `FuzzMatrix`.
+pub const MAX_DENSITY: u8 = 60;
+/// Most width and height of a fuzzed block.
+pub const MAX_BLOCK_SIDE: usize = 3;
+/// Most width and height of the board of a `FuzzGame2D`.
+pub const MAX_BOARD_SIDE: usize = 4;
+/// Most blocks of a `FuzzGame2D`.
+pub const MAX_BLOCKS: usize = 4;
+
+/// A small matrix with at least one row, one column, and a filled entry in every column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuzzMatrix {
+    pub rows: Vec<Vec<bool>>,
+}
+impl<'a> Arbitrary<'a> for FuzzMatrix {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let height = u.int_in_range(1..=MAX_ROWS)?;
+        let width = u.int_in_range(1..=MAX_COLUMNS)?;
+        let density = u.int_in_range(1..=MAX_DENSITY)?;
+
+        let mut rows = vec![vec![false; width]; height];
+        for entry in rows.iter_mut().flatten() {
+            *entry = u.ratio(density, 100)?;
+        }
+
+        // a column without rows leaves nothing to solve, so keep it out of the inputs
+        for column in 0..width {
+            if rows.iter().all(|row| !row[column]) {
+                let row = u.choose_index(height)?;
+                rows[row][column] = true;
+            }
+        }
+
+        Ok(FuzzMatrix { rows })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (3, Some(3 + MAX_ROWS * MAX_COLUMNS * 2 + MAX_COLUMNS * 4))
+    }
+}
+impl FuzzMatrix {
+    /// Check the solvers agree on the matrix, panicking otherwise: every solution covers every column
+    /// exactly once, no solution is found twice, and the counting and single solvers match.
+    pub fn check(&self) {
+        let solutions = Node::solve_all(&self.rows);
+        for solution in &solutions {
+            if let Err(error) = Node::verify_solution(&self.rows, solution) {
+                panic!("{solution:?} isn't an exact cover: {error}");
+            }
+        }
+
+        let mut sorted: Vec<Vec<usize>> = solutions.iter().map(|solution| {
+            let mut solution = solution.clone();
+            solution.sort_unstable();
+            solution
+        }).collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), solutions.len(), "a solution was found twice");
+
+        match Node::solve_once(&self.rows) {
+            Some(solution) => assert!(solutions.contains(&solution), "{solution:?} isn't found by solve_all"),
+            None => assert!(solutions.is_empty(), "solve_once found nothing"),
+        }
+
+        let (count, _) = Node::count_solutions_with_options(&self.rows, &Default::default());
+        assert_eq!(count, solutions.len() as u64, "counted solutions differ");
+    }
+}
+
+impl<'a> Arbitrary<'a> for Block2D {
+    /// A block of at most `MAX_BLOCK_SIDE` squares each way, with at least one filled.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let w = u.int_in_range(1..=MAX_BLOCK_SIDE)?;
+        let h = u.int_in_range(1..=MAX_BLOCK_SIDE)?;
+
+        let mut data = vec![vec![false; w]; h];
+        for square in data.iter_mut().flatten() {
+            *square = u.arbitrary()?;
+        }
+        if !data.iter().flatten().any(|filled| *filled) {
+            let square = u.choose_index(w * h)?;
+            data[square / w][square % w] = true;
+        }
+
+        Ok(Block2D { w, h, data, name: None })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(2 + MAX_BLOCK_SIDE * MAX_BLOCK_SIDE + 4))
+    }
+}
+
+/// A small board with a few blocks, turned into a game with `game`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuzzGame2D {
+    pub w: usize,
+    pub h: usize,
+    pub blocks: Vec<Block2D>,
+}
+impl<'a> Arbitrary<'a> for FuzzGame2D {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let w = u.int_in_range(1..=MAX_BOARD_SIDE)?;
+        let h = u.int_in_range(1..=MAX_BOARD_SIDE)?;
+        let amount = u.int_in_range(1..=MAX_BLOCKS)?;
+        let blocks = (0..amount).map(|_| u.arbitrary()).collect::<Result<_>>()?;
+
+        Ok(FuzzGame2D { w, h, blocks })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let (_, block) = Block2D::size_hint(depth);
+        (3, block.map(|block| 3 + MAX_BLOCKS * block))
+    }
+}
+impl FuzzGame2D {
+    /// Create the game.
+    pub fn game(&self) -> Game2D {
+        Game2D::new(self.w, self.h, self.blocks.clone())
+    }
+
+    /// Check the game's solvers agree, panicking otherwise: every solution of its matrix is a valid
+    /// layout, and the single and sampling solvers find one exactly when there is one.
+    pub fn check(&self) {
+        let mut game = self.game();
+        let matrix = game.get_matrix();
+        let solutions = if matrix.is_empty() { Vec::new() } else { Node::solve_all(&matrix) };
+        let placements = game.get_placements();
+        for rows in &solutions {
+            let solution = Solution2D {
+                placements: rows.iter().map(|&row| placements[row].clone()).collect(),
+                grid: game.solution_grid(rows),
+            };
+            assert!(game.validate_layout(&solution), "{rows:?} isn't a valid layout");
+        }
+
+        match game.solve_once() {
+            Some(rows) => assert!(solutions.contains(&rows), "{rows:?} isn't found by solve_all"),
+            None => assert!(solutions.is_empty(), "solve_once found nothing"),
+        }
+
+        match game.sample_solution(0) {
+            Some(solution) => assert!(game.validate_layout(&solution), "the sampled layout isn't valid"),
+            None => assert!(solutions.is_empty(), "sample_solution found nothing"),
+        }
+    }
+}
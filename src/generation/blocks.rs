@@ -1,17 +1,77 @@
 //! Block generation.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Write;
+use std::ops::ControlFlow;
+use crate::{ExactCoverProblem, Node, SolutionSet, SolveOptions};
+use crate::search::Search;
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+use crate::solutions::farthest_points;
+use crate::unsat::relaxations;
+
+/// How a block is turned before being placed: flipped upside down first if `flip`, then rotated by
+/// `rotations` clockwise quarter turns.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TransformOp {
+    pub flip: bool,
+    pub rotations: u8,
+}
+
+/// Why a string doesn't make a block. Lines are counted from 1, leading blank lines aside.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BlockError {
+    /// The string has no lines.
+    Empty,
+    /// The string has no filled squares.
+    NoCells,
+    /// A line doesn't have as many squares as the first one.
+    Ragged { line: usize },
+}
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockError::Empty => write!(f, "the block is empty"),
+            BlockError::NoCells => write!(f, "the block has no filled squares"),
+            BlockError::Ragged { line } => write!(f, "line {line} of the block isn't as wide as the first"),
+        }
+    }
+}
+impl std::error::Error for BlockError {}
+
+/// A block of a game that couldn't be made, along with its position in the input.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InvalidBlock {
+    pub block: usize,
+    pub error: BlockError,
+}
+impl fmt::Display for InvalidBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "block {}: {}", self.block, self.error)
+    }
+}
+impl std::error::Error for InvalidBlock {}
 
 /// Blocks in 2D.
-#[derive(Clone, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Block2D {
     pub w: usize,
     pub h: usize,
     pub data: Vec<Vec<bool>>,
+    pub name: Option<String>,
 }
 impl Block2D {
     /// Create a block from a string. Empty squares are '.', filled are anything else.
-    fn from_string(s: &str) -> Block2D {
+    /// Panics if the string has no lines, lines of different widths, or no filled squares.
+    pub fn from_string(s: &str) -> Block2D {
+        Self::try_from_string(s).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Create a block from a string. Empty squares are '.', filled are anything else.
+    /// Errors if the string has no lines, lines of different widths, or no filled squares.
+    pub fn try_from_string(s: &str) -> Result<Block2D, BlockError> {
         let mut data: Vec<Vec<bool>> = Vec::new();
 
         for line in s.trim().lines() {
@@ -22,11 +82,32 @@ impl Block2D {
             data.push(row);
         }
 
-        Block2D {
+        if data.is_empty() { return Err(BlockError::Empty); }
+        if let Some(line) = data.iter().position(|row| row.len() != data[0].len()) {
+            return Err(BlockError::Ragged { line: line + 1 });
+        }
+        if !data.iter().flatten().any(|filled| *filled) { return Err(BlockError::NoCells); }
+
+        Ok(Block2D {
             w: data[0].len(),
             h: data.len(),
             data,
-        }
+            name: None,
+        })
+    }
+
+    /// Give the block a name.
+    pub fn named(mut self, name: &str) -> Block2D {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Get the block turned by a transformation.
+    pub fn transformed(&self, op: TransformOp) -> Block2D {
+        let mut block = self.clone();
+        if op.flip { block.flip(); }
+        for _ in 0..op.rotations % 4 { block.rotate(); }
+        block
     }
 
     /// Get the flipped form of a block.
@@ -42,67 +123,747 @@ impl Block2D {
         std::mem::swap(&mut self.w, &mut self.h);
     }
 
-    /// Get all the unique transformations of a block within a grid.
-    fn get_transformations(&mut self) -> Vec<Block2D> {
+    /// Get the shape of a block regardless of how it is turned, equal for blocks that are
+    /// transformations of each other.
+    pub fn canonical(&self) -> Vec<Vec<bool>> {
+        self.get_transformations().into_iter().map(|(_, block)| block.data).min().unwrap()
+    }
+
+    /// Get all the unique transformations of a block within a grid, in the order they are first reached.
+    fn get_transformations(&self) -> Vec<(TransformOp, Block2D)> {
         let mut hs: HashSet<Block2D> = HashSet::new();
-        for _ in 0..2 {
-            for _ in 0..4 {
-                hs.insert(self.clone());
-                self.rotate();
+        let mut transformations = Vec::new();
+        let mut block = self.clone();
+        for flip in [false, true] {
+            for rotations in 0..4 {
+                if hs.insert(block.clone()) { transformations.push((TransformOp { flip, rotations }, block.clone())); }
+                block.rotate();
+            }
+            block.flip();
+        }
+        transformations
+    }
+}
+
+/// A transformation of a block shifted to a position on the board, as one row of the matrix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Placement {
+    /// Index of the block in the game.
+    pub block: usize,
+    pub x: usize,
+    pub y: usize,
+    /// The first transformation found giving the shape.
+    pub op: TransformOp,
+    /// The transformed block.
+    pub shape: Block2D,
+}
+impl Placement {
+    /// Get the board cells covered, as `(x, y)` pairs.
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for (dy, row) in self.shape.data.iter().enumerate() {
+            for (dx, filled) in row.iter().enumerate() {
+                if *filled { cells.push((self.x + dx, self.y + dy)); }
             }
-            self.flip();
         }
-        hs.into_iter().collect()
+        cells
     }
 }
 
+/// Placed blocks that leave no room for any other block, although cells may stay empty.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Packing {
+    pub placements: Vec<Placement>,
+    /// The cells covered, as `(x, y)` pairs in row-major order.
+    pub covered: Vec<(usize, usize)>,
+    /// The cells left empty, as `(x, y)` pairs in row-major order.
+    pub uncovered: Vec<(usize, usize)>,
+}
+
+/// A tiling of the board.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Solution2D {
+    pub placements: Vec<Placement>,
+    /// The block covering every cell, indexed as `grid[y][x]`.
+    pub grid: Vec<Vec<Option<usize>>>,
+}
+
+impl Solution2D {
+    /// Check if a straight line across the whole board, between two rows or columns of cells, cuts
+    /// no block.
+    pub fn has_fault_line(&self) -> bool {
+        let (h, w) = (self.grid.len(), self.grid.first().map_or(0, Vec::len));
+        let spans = |a: Option<usize>, b: Option<usize>| a.is_some() && a == b;
+        let vertical = (1..w).any(|x| (0..h).all(|y| !spans(self.grid[y][x - 1], self.grid[y][x])));
+        let horizontal = (1..h).any(|y| (0..w).all(|x| !spans(self.grid[y - 1][x], self.grid[y][x])));
+        vertical || horizontal
+    }
+}
+
+/// A tiling of the board, along with the shapes of the blocks it uses.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShapeTiling {
+    /// Rows of `get_matrix`.
+    pub rows: Vec<usize>,
+    /// The first block of every shape used, in order.
+    pub shapes: Vec<usize>,
+}
+
+/// A problem making a game impossible to solve, found before solving.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// A block has no filled squares.
+    NoCells { block: usize },
+    /// A block fits nowhere on the board.
+    NoPlacement { block: usize },
+    /// The blocks don't add up to the area of the board.
+    AreaMismatch { board: usize, blocks: usize },
+    /// Coloring the board like a checkerboard, no placements of the blocks cover as many cells of
+    /// each color as the board has.
+    Parity,
+    /// Two fixed blocks overlap on a cell.
+    FixedOverlap { blocks: (usize, usize), cell: (usize, usize) },
+    /// No placement covers a cell.
+    UncoverableCell { cell: (usize, usize) },
+}
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::NoCells { block } => write!(f, "block {block} has no filled squares"),
+            ValidationError::NoPlacement { block } => write!(f, "block {block} fits nowhere on the board"),
+            ValidationError::AreaMismatch { board, blocks } => write!(f, "the blocks cover {blocks} cells but the board has {board}"),
+            ValidationError::Parity => write!(f, "the blocks can't match the colors of the board"),
+            ValidationError::FixedOverlap { blocks: (a, b), cell: (x, y) } => write!(f, "fixed blocks {a} and {b} overlap at ({x}, {y})"),
+            ValidationError::UncoverableCell { cell: (x, y) } => write!(f, "no block can cover ({x}, {y})"),
+        }
+    }
+}
+
+/// Something odd about a game that doesn't keep it from being solved.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationWarning {
+    /// The filled squares of a block are not all connected through their sides.
+    Disconnected { block: usize },
+}
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationWarning::Disconnected { block } => write!(f, "block {block} is in several pieces"),
+        }
+    }
+}
+
+/// Everything found wrong with a game by the checks run before solving.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+impl ValidationReport {
+    /// Check if nothing keeps the game from being solved.
+    pub fn is_ok(&self) -> bool { self.errors.is_empty() }
+}
+
+/// What makes two tilings the same when counting them.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CountMode {
+    /// Every solution counts.
+    #[default]
+    Raw,
+    /// Tilings turned into each other by rotating or mirroring the board count once.
+    UpToBoardSymmetry,
+    /// Tilings turned into each other by rotating or mirroring the board, or by swapping blocks of the
+    /// same shape, count once.
+    UpToBoardSymmetryAndIdenticalPieces,
+}
+
 /// A container for blocks, bounded with a width and height.
 pub struct Game2D {
     pub w: usize,
     pub h: usize,
     pub blocks: Vec<Block2D>,
+    /// Placements blocks are fixed to, at most one per block.
+    pub fixed: Vec<Placement>,
+    /// The cost of placing a block turned some way, by default 1 when it is flipped and 0 otherwise.
+    pub transform_cost: fn(&TransformOp) -> u64,
+    /// Run `validate` before solving or counting tilings, giving up on any error as if there were no
+    /// tiling. Maximal packings and empty cell suggestions, meant for boards that can't be tiled,
+    /// don't validate.
+    pub validate_first: bool,
 }
 impl Game2D {
     /// Create a game from a width, height, and vector of strings.
-    pub fn from_strings(w: usize, h: usize, s: Vec<&str>) -> Game2D {
+    /// Errors with the position of the first string not making a block.
+    pub fn from_strings(w: usize, h: usize, s: Vec<&str>) -> Result<Game2D, InvalidBlock> {
         let mut blocks: Vec<Block2D> = Vec::new();
-        for block in s {
-            blocks.push(Block2D::from_string(block));
+        for (i, block) in s.into_iter().enumerate() {
+            blocks.push(Block2D::try_from_string(block).map_err(|error| InvalidBlock { block: i, error })?);
+        }
+
+        Ok(Game2D::new(w, h, blocks))
+    }
+
+    /// Create a game from a width, height, and blocks.
+    pub fn new(w: usize, h: usize, blocks: Vec<Block2D>) -> Game2D {
+        Game2D { w, h, blocks, fixed: Vec::new(), transform_cost: |op| op.flip as u64, validate_first: false }
+    }
+
+    /// Find one solution as rows of `get_matrix`, or `None` when there is none, such as when a block or
+    /// cell can't be covered by any placement.
+    pub fn solve_once(&mut self) -> Option<Vec<usize>> {
+        if self.fails_validation() { return None; }
+        let matrix = self.get_matrix();
+        if !self.coverable(&matrix) { return None; }
+        if matrix.is_empty() { return Some(Vec::new()); }
+
+        Node::solve_once(&matrix)
+    }
+
+    /// Check if `validate_first` is set and `validate` finds errors, so solving gives up.
+    fn fails_validation(&self) -> bool {
+        self.validate_first && !self.validate().is_ok()
+    }
+
+    /// Check that every block and cell has a row of `get_matrix` covering it.
+    fn coverable(&self, matrix: &[Vec<bool>]) -> bool {
+        let width = self.blocks.len() + self.w * self.h;
+        (0..width).all(|column| matrix.iter().any(|entry| entry[column]))
+    }
+
+    /// Pick a tiling uniformly at random out of all of them, reproducibly for a given seed. It is
+    /// drawn from the diagram of all solutions, so no tiling is favored by the order of the rows.
+    pub fn sample_solution(&mut self, seed: u64) -> Option<Solution2D> {
+        if self.fails_validation() { return None; }
+        let matrix = self.get_matrix();
+        if !self.coverable(&matrix) { return None; }
+        let rows = if matrix.is_empty() { Vec::new() } else { Node::solve_to_zdd(&matrix).sample(seed)? };
+
+        let placements = self.get_placements();
+        Some(Solution2D {
+            grid: self.solution_grid(&rows),
+            placements: rows.iter().map(|&row| placements[row].clone()).collect(),
+        })
+    }
+
+    /// Check that a tiling places every block once where it may go, covering every cell exactly once.
+    pub fn validate_layout(&self, solution: &Solution2D) -> bool {
+        let placements = self.get_placements();
+        let mut covered = vec![vec![None; self.w]; self.h];
+        let mut used = vec![false; self.blocks.len()];
+        for placement in &solution.placements {
+            if !placements.contains(placement) || std::mem::replace(&mut used[placement.block], true) { return false; }
+            for (x, y) in placement.cells() {
+                if covered[y][x].replace(placement.block).is_some() { return false; }
+            }
+        }
+
+        used.iter().all(|used| *used) && covered == solution.grid && covered.iter().flatten().all(Option::is_some)
+    }
+
+    /// Run every cheap check that can tell a game has no solution without solving it.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let placements = self.get_placements();
+        let color = |(x, y): (usize, usize)| if (x + y) % 2 == 0 { 1 } else { -1 };
+
+        for (i, block) in self.blocks.iter().enumerate() {
+            let cells: Vec<(usize, usize)> = (0..block.h)
+                .flat_map(|y| (0..block.w).map(move |x| (x, y)))
+                .filter(|&(x, y)| block.data[y][x])
+                .collect();
+            if cells.is_empty() {
+                report.errors.push(ValidationError::NoCells { block: i });
+                continue;
+            }
+            if placements.iter().all(|placement| placement.block != i) {
+                report.errors.push(ValidationError::NoPlacement { block: i });
+            }
+
+            // flood the block from its first square
+            let mut reached = vec![cells[0]];
+            let mut stack = vec![cells[0]];
+            while let Some((x, y)) = stack.pop() {
+                for next in [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)] {
+                    if cells.contains(&next) && !reached.contains(&next) {
+                        reached.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            if reached.len() < cells.len() { report.warnings.push(ValidationWarning::Disconnected { block: i }); }
         }
 
-        Game2D { w, h, blocks }
+        let area: usize = self.blocks.iter().map(|block| block.data.iter().flatten().filter(|filled| **filled).count()).sum();
+        if area != self.w * self.h {
+            report.errors.push(ValidationError::AreaMismatch { board: self.w * self.h, blocks: area });
+        }
+
+        // the color balances reachable by placing every block once
+        let mut reachable: HashSet<i64> = HashSet::from([0]);
+        for i in 0..self.blocks.len() {
+            let balances: HashSet<i64> = placements.iter()
+                .filter(|placement| placement.block == i)
+                .map(|placement| placement.cells().into_iter().map(color).sum())
+                .collect();
+            reachable = reachable.iter().flat_map(|a| balances.iter().map(move |b| a + b)).collect();
+        }
+        let board: i64 = (0..self.h).flat_map(|y| (0..self.w).map(move |x| color((x, y)))).sum();
+        if !reachable.is_empty() && !reachable.contains(&board) { report.errors.push(ValidationError::Parity); }
+
+        for (i, a) in self.fixed.iter().enumerate() {
+            for b in &self.fixed[i + 1..] {
+                if let Some(&cell) = a.cells().iter().find(|cell| b.cells().contains(cell)) {
+                    report.errors.push(ValidationError::FixedOverlap { blocks: (a.block, b.block), cell });
+                }
+            }
+        }
+
+        let mut coverable = vec![false; self.w * self.h];
+        for (x, y) in placements.iter().flat_map(Placement::cells) { coverable[y * self.w + x] = true; }
+        for y in 0..self.h {
+            for x in 0..self.w {
+                if !coverable[y * self.w + x] { report.errors.push(ValidationError::UncoverableCell { cell: (x, y) }); }
+            }
+        }
+
+        report
+    }
+
+    /// Set the cost of placing a block turned some way, used by `solve_min_flips`.
+    pub fn set_transform_cost(&mut self, cost: fn(&TransformOp) -> u64) {
+        self.transform_cost = cost;
+    }
+
+    /// Find the solution with the lowest total transformation cost, by default the fewest flipped
+    /// blocks, along with its cost. The solution is given as rows of `get_matrix`.
+    pub fn solve_min_flips(&mut self) -> Option<(u64, Vec<usize>)> {
+        if self.fails_validation() { return None; }
+        let costs: Vec<u64> = self.get_placements().iter().map(|placement| (self.transform_cost)(&placement.op)).collect();
+        let matrix = self.get_matrix();
+        if matrix.is_empty() { return None; }
+
+        Node::solve_ordered_by_cost(&matrix, &costs).next()
+    }
+
+    /// Fix a block at a position, turned by `op`, so it is never placed anywhere else.
+    /// Panics if it doesn't fit on the board there.
+    pub fn fix(&mut self, block: usize, op: TransformOp, x: usize, y: usize) {
+        let shape = self.blocks[block].transformed(op);
+        assert!(x + shape.w <= self.w && y + shape.h <= self.h, "block {block} doesn't fit at ({x}, {y})");
+
+        self.fixed.retain(|placement| placement.block != block);
+        self.fixed.push(Placement { block, x, y, op, shape });
+    }
+
+    /// Get every placement of every block on the board, in the order of the rows of `get_matrix`.
+    pub fn get_placements(&self) -> Vec<Placement> {
+        let mut placements = Vec::new();
+        for (i, block) in self.blocks.iter().enumerate() {
+            if let Some(fixed) = self.fixed.iter().find(|placement| placement.block == i) {
+                placements.push(fixed.clone());
+                continue;
+            }
+
+            for (op, transformation) in block.get_transformations() {
+                // a transformation larger than the board has nowhere to go
+                let (Some(max_x), Some(max_y)) = (self.w.checked_sub(transformation.w), self.h.checked_sub(transformation.h)) else { continue };
+                for shift_y in 0..=max_y {
+                    for shift_x in 0..=max_x {
+                        placements.push(Placement { block: i, x: shift_x, y: shift_y, op, shape: transformation.clone() });
+                    }
+                }
+            }
+        }
+
+        placements
+    }
+
+    /// Get the amount of placements of every block, 0 for a block that can't go anywhere.
+    pub fn placement_counts(&mut self) -> Vec<usize> {
+        let mut counts = vec![0; self.blocks.len()];
+        for placement in self.get_placements() { counts[placement.block] += 1; }
+        counts
     }
 
     /// Create a matrix from the blocks in the game to use within DLX and create the structure.
+    /// The first columns are the blocks, followed by the board cells row by row. Solving the game as an
+    /// `ExactCoverProblem` gives the same rows without the matrix.
     pub fn get_matrix(&mut self) -> Vec<Vec<bool>> {
-        let amt_blocks = self.blocks.len();
-        let width = self.w * self.h + amt_blocks;
+        let width = self.num_columns();
         let mut matrix = Vec::new();
-        for (i, block) in self.blocks.iter_mut().enumerate() {
-            for transformation in block.get_transformations() {
-                for shift_y in 0..=(self.h - transformation.h) {
-                    for shift_x in 0..=(self.w - transformation.w) {
-                        let mut current_vec = vec![false; width];
-                        let mut j = amt_blocks;
-                        for py in 0..self.h {
-                            for px in 0..self.w {
-                                current_vec[j] =
-                                    shift_x <= px &&
-                                    px < (shift_x + transformation.w) &&
-                                    shift_y <= py &&
-                                    py < (shift_y + transformation.h) &&
-                                    transformation.data[py - shift_y][px - shift_x];
-
-                                j += 1;
-                            }
-                        }
-                        current_vec[i] = true;
-                        matrix.push(current_vec);
-                    }
-                }
+        for placement in self.get_placements() {
+            let mut current_vec = vec![false; width];
+            for column in self.placement_columns(&placement) {
+                current_vec[column] = true;
             }
+            matrix.push(current_vec);
         }
 
         matrix
     }
+
+    /// Get the columns of `get_matrix` a placement covers: its block, then its cells.
+    fn placement_columns(&self, placement: &Placement) -> Vec<usize> {
+        let amt_blocks = self.blocks.len();
+        core::iter::once(placement.block)
+            .chain(placement.cells().into_iter().map(|(px, py)| amt_blocks + py * self.w + px))
+            .collect()
+    }
+
+    /// Find up to `limit` maximal packings: blocks placed so that none of the unused ones fits anywhere
+    /// in the cells left, fixed blocks always being placed. Packings only differing by swapping blocks
+    /// of the same shape are found once.
+    pub fn maximal_packings(&mut self, limit: usize) -> Vec<Packing> {
+        if limit == 0 { return Vec::new(); }
+
+        let placements = self.get_placements();
+        let mut matrix = self.get_matrix();
+        let amt_blocks = self.blocks.len();
+        let width = amt_blocks + self.w * self.h;
+        // a row of its own lets a block go unused or a cell stay empty
+        for column in 0..width {
+            if column < amt_blocks && self.fixed.iter().any(|placement| placement.block == column) { continue; }
+            let mut filler = vec![false; width];
+            filler[column] = true;
+            matrix.push(filler);
+        }
+
+        let mut packings = Vec::new();
+        let mut seen = HashSet::new();
+        let mut structure = Node::build(&matrix);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, 0);
+        let _ = search.run(&mut |rows| {
+            let used: Vec<&Placement> = rows.iter().filter_map(|&row| placements.get(row)).collect();
+            let mut covered = vec![false; self.w * self.h];
+            for (x, y) in used.iter().flat_map(|placement| placement.cells()) { covered[y * self.w + x] = true; }
+
+            let fits = |placement: &Placement| placement.cells().iter().all(|&(x, y)| !covered[y * self.w + x]);
+            let maximal = placements.iter()
+                .filter(|placement| used.iter().all(|other| other.block != placement.block))
+                .all(|placement| !fits(placement));
+
+            // blocks of the same shape are told apart by their index only
+            let mut key: Vec<_> = used.iter()
+                .map(|placement| (&self.blocks[placement.block].data, placement.cells()))
+                .collect();
+            key.sort();
+            if maximal && seen.insert(key) {
+                let cells = (0..self.h).flat_map(|y| (0..self.w).map(move |x| (x, y)));
+                let (covered, uncovered) = cells.partition(|&(x, y)| covered[y * self.w + x]);
+                packings.push(Packing { placements: used.into_iter().cloned().collect(), covered, uncovered });
+            }
+
+            if packings.len() == limit { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+
+        packings
+    }
+
+    /// Get the shape of every block as the first block of that shape.
+    fn shapes(&self) -> Vec<usize> {
+        let canonical: Vec<Vec<Vec<bool>>> = self.blocks.iter().map(Block2D::canonical).collect();
+        canonical.iter().map(|c| canonical.iter().position(|other| other == c).unwrap()).collect()
+    }
+
+    /// Get the symmetries of the board, as maps of cell indices: every combination of mirroring each
+    /// axis, and of swapping the axes for a square.
+    fn board_symmetries(&self) -> Vec<Vec<usize>> {
+        let (w, h) = (self.w, self.h);
+        let amount = if w == h { 8 } else { 4 };
+        (0..amount).map(|symmetry| {
+            (0..w * h).map(|cell| {
+                let (mut x, mut y) = (cell % w, cell / w);
+                if symmetry & 1 != 0 { x = w - 1 - x; }
+                if symmetry & 2 != 0 { y = h - 1 - y; }
+                if symmetry & 4 != 0 { std::mem::swap(&mut x, &mut y); }
+                y * w + x
+            }).collect()
+        }).collect()
+    }
+
+    /// Get a function mapping a solution, as rows of `get_matrix`, to the same key for every tiling the
+    /// symmetries of the board turn it into: the block on every cell, the smallest over all symmetries.
+    /// Passing it to `Node::solve_all_dedup` keeps one tiling out of each set of symmetric ones.
+    pub fn symmetry_canonicalizer(&self) -> impl Fn(&[usize]) -> Vec<usize> + use<> {
+        let placements = self.get_placements();
+        let symmetries = self.board_symmetries();
+        let w = self.w;
+        move |rows| {
+            symmetries.iter().map(|symmetry| {
+                let mut cells = vec![usize::MAX; symmetry.len()];
+                for &row in rows {
+                    for (x, y) in placements[row].cells() { cells[symmetry[y * w + x]] = placements[row].block; }
+                }
+                cells
+            }).min().unwrap_or_default()
+        }
+    }
+
+    /// Find up to `limit` tilings without a fault line, checking solutions as the search finds them.
+    pub fn solve_fault_free(&mut self, limit: usize) -> Vec<Solution2D> {
+        if limit == 0 || self.fails_validation() { return Vec::new(); }
+        let matrix = self.get_matrix();
+        if !self.coverable(&matrix) || matrix.is_empty() { return Vec::new(); }
+
+        let placements = self.get_placements();
+        let mut tilings = Vec::new();
+        let mut structure = Node::build(&matrix);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, self.blocks.len());
+        let _ = search.run(&mut |rows| {
+            let tiling = Solution2D {
+                grid: self.solution_grid(rows),
+                placements: rows.iter().map(|&row| placements[row].clone()).collect(),
+            };
+            if !tiling.has_fault_line() { tilings.push(tiling); }
+            if tilings.len() == limit { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+
+        tilings
+    }
+
+    /// Count the tilings of the board, saturating at `u64::MAX`. Raw counts come from the solver's
+    /// counting mode, while the others go through every solution, keeping the canonical form of each.
+    pub fn count_solutions(&mut self, mode: CountMode) -> u64 {
+        if self.fails_validation() { return 0; }
+        let matrix = self.get_matrix();
+        if !self.coverable(&matrix) { return 0; }
+        if matrix.is_empty() { return 1; }
+        if mode == CountMode::Raw { return Node::count_solutions_with_options(&matrix, &SolveOptions::default()).0; }
+
+        let placements = self.get_placements();
+        let symmetries = self.board_symmetries();
+        let labels = match mode {
+            CountMode::UpToBoardSymmetryAndIdenticalPieces => self.shapes(),
+            _ => (0..self.blocks.len()).collect(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut structure = Node::build(&matrix);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, self.blocks.len());
+        let _ = search.run(&mut |rows| {
+            // the tiling as the labelled cells of every block, the smallest over all symmetries
+            let canonical = symmetries.iter().map(|symmetry| {
+                let mut pieces: Vec<(usize, Vec<usize>)> = rows.iter().map(|&row| {
+                    let mut cells: Vec<usize> = placements[row].cells().iter().map(|&(x, y)| symmetry[y * self.w + x]).collect();
+                    cells.sort_unstable();
+                    (labels[placements[row].block], cells)
+                }).collect();
+                pieces.sort_unstable();
+                pieces
+            }).min();
+            seen.insert(canonical);
+            ControlFlow::Continue(())
+        });
+
+        seen.len() as u64
+    }
+
+    /// Find every tiling of the board using blocks of at most `k` different shapes, each block being
+    /// used at most once and fixed blocks always. Blocks of the same shape, turned or not, are
+    /// repeats of each other. Every choice of `k` shapes is solved in turn, keeping each tiling once.
+    pub fn max_distinct_pieces(&mut self, k: usize) -> Vec<ShapeTiling> {
+        if self.fails_validation() { return Vec::new(); }
+        let placements = self.get_placements();
+        let mut matrix = self.get_matrix();
+        let amt_blocks = self.blocks.len();
+        let width = amt_blocks + self.w * self.h;
+        for block in 0..amt_blocks {
+            if self.fixed.iter().any(|placement| placement.block == block) { continue; }
+            let mut filler = vec![false; width];
+            filler[block] = true;
+            matrix.push(filler);
+        }
+
+        let shape_of = self.shapes();
+        let mut shapes = shape_of.clone();
+        shapes.sort_unstable();
+        shapes.dedup();
+
+        let mut tilings = Vec::new();
+        let mut seen = HashSet::new();
+        let size = k.min(shapes.len());
+        let mut picks: Vec<usize> = (0..size).collect();
+        loop {
+            let allowed: Vec<usize> = picks.iter().map(|&i| shapes[i]).collect();
+            let rows: Vec<usize> = (0..matrix.len())
+                .filter(|&row| placements.get(row).is_none_or(|placement| allowed.contains(&shape_of[placement.block])))
+                .collect();
+            let sub: Vec<Vec<bool>> = rows.iter().map(|&row| matrix[row].clone()).collect();
+
+            // every column has to keep a row, or the shapes can't tile the board
+            if !sub.is_empty() && (0..width).all(|column| sub.iter().any(|entry| entry[column])) {
+                for solution in Node::solve_all(&sub) {
+                    let mut solution: Vec<usize> = solution.iter().map(|&row| rows[row]).filter(|&row| row < placements.len()).collect();
+                    solution.sort_unstable();
+                    if !seen.insert(solution.clone()) { continue; }
+
+                    let mut used: Vec<usize> = solution.iter().map(|&row| shape_of[placements[row].block]).collect();
+                    used.sort_unstable();
+                    used.dedup();
+                    tilings.push(ShapeTiling { rows: solution, shapes: used });
+                }
+            }
+
+            // move on to the next choice of shapes
+            let Some(i) = (0..size).rev().find(|&i| picks[i] < shapes.len() - size + i) else { break };
+            picks[i] += 1;
+            for j in i + 1..size { picks[j] = picks[j - 1] + 1; }
+        }
+
+        tilings
+    }
+
+    /// When the blocks can't cover the whole board, find the fewest cells, at most `max_drop` of them,
+    /// that leaving empty would make it work, as `(x, y)` pairs. An empty list means the board can
+    /// already be covered, and `None` that no such cells were found within the node budget of each check.
+    pub fn suggest_empty_cells(&mut self, max_drop: usize, node_budget: Option<u64>) -> Option<Vec<(usize, usize)>> {
+        let amt_blocks = self.blocks.len();
+        let cells: Vec<usize> = (amt_blocks..amt_blocks + self.w * self.h).collect();
+        let columns = relaxations(&self.get_matrix(), &cells, max_drop, node_budget)?;
+        Some(columns.iter().map(|column| ((column - amt_blocks) % self.w, (column - amt_blocks) / self.w)).collect())
+    }
+
+    /// Get the block covering every cell of the board for a solution given as rows of `get_matrix`,
+    /// indexed as `grid[y][x]`.
+    pub fn solution_grid(&self, rows: &[usize]) -> Vec<Vec<Option<usize>>> {
+        let placements = self.get_placements();
+        let mut grid = vec![vec![None; self.w]; self.h];
+        for &row in rows {
+            for (x, y) in placements[row].cells() {
+                grid[y][x] = Some(placements[row].block);
+            }
+        }
+        grid
+    }
+
+    /// Get the character drawing a block: the start of its name, or a letter from its index.
+    pub fn block_char(&self, block: usize) -> char {
+        block_char(self.blocks[block].name.as_deref(), block)
+    }
+
+    /// Render a solution given as rows of `get_matrix`, drawing each block with `block_char` and
+    /// empty cells as '.'.
+    pub fn render_solution(&mut self, rows: &[usize]) -> String {
+        let mut out = String::new();
+        for line in self.solution_grid(rows) {
+            let line: String = line.iter().map(|cell| cell.map_or('.', |block| self.block_char(block))).collect();
+            writeln!(out, "{line}").unwrap();
+        }
+        out
+    }
+
+    /// Pick up to `m` visually different tilings out of solutions of `get_matrix`, returning their indices
+    /// in the set. Two tilings are as far apart as the amount of cells covered by different blocks.
+    pub fn select_diverse(&mut self, solutions: &SolutionSet, m: usize) -> Vec<usize> {
+        let grids: Vec<Vec<Vec<Option<usize>>>> = (0..solutions.len())
+            .map(|i| self.solution_grid(&solutions.indices(i)))
+            .collect();
+        farthest_points(grids.len(), m, |a, b| {
+            grids[a].iter().flatten().zip(grids[b].iter().flatten()).filter(|(x, y)| x != y).count()
+        })
+    }
+
+    /// Get every placement along with the number of solutions using it, 0 for every one when a block or
+    /// cell can't be covered by any placement, or `validate_first` gives up.
+    pub fn placement_heat(&mut self) -> Vec<(Placement, u64)> {
+        let placements = self.get_placements();
+        let matrix = self.get_matrix();
+        if self.fails_validation() || !self.coverable(&matrix) || matrix.is_empty() {
+            return placements.into_iter().map(|placement| (placement, 0)).collect();
+        }
+
+        placements.into_iter().zip(Node::row_participation(&matrix)).collect()
+    }
+
+    /// For every cell, indexed as `heatmap[y][x]`, count the solutions in which each block covers it.
+    /// A cell covered by the same block in every solution is forced. Summed from the solutions using
+    /// every placement, so the solutions are never listed.
+    pub fn coverage_heatmap(&mut self) -> Vec<Vec<HashMap<usize, u64>>> {
+        let mut heatmap = vec![vec![HashMap::new(); self.w]; self.h];
+        let matrix = self.get_matrix();
+        if self.fails_validation() || !self.coverable(&matrix) || matrix.is_empty() { return heatmap; }
+
+        for (placement, heat) in self.get_placements().into_iter().zip(Node::row_participation(&matrix)) {
+            if heat == 0 { continue; }
+            for (x, y) in placement.cells() {
+                *heatmap[y][x].entry(placement.block).or_insert(0) += heat;
+            }
+        }
+        heatmap
+    }
+
+    /// Render the coverage heat map, drawing every cell by its most frequent block: with `block_char`
+    /// when that block covers it in every solution, and otherwise with a digit from 0 to 9 for the tenths
+    /// of the solutions it covers it in. Boards without solutions are all '.'.
+    pub fn render_heatmap(&mut self) -> String {
+        let heatmap = self.coverage_heatmap();
+        let mut out = String::new();
+        for line in &heatmap {
+            let line: String = line.iter().map(|counts| {
+                let total: u64 = counts.values().sum();
+                // the most frequent block, the first one on ties
+                match counts.iter().max_by_key(|&(block, count)| (*count, std::cmp::Reverse(*block))) {
+                    None => '.',
+                    Some((&block, &count)) if count == total => self.block_char(block),
+                    Some((_, &count)) => char::from_digit((count * 10 / total) as u32, 10).unwrap(),
+                }
+            }).collect();
+            writeln!(out, "{line}").unwrap();
+        }
+        out
+    }
+
+    /// Render every placement on the board along with the number of solutions using it.
+    pub fn render_placement_heat(&mut self) -> String {
+        let mut out = String::new();
+        for (placement, heat) in self.placement_heat() {
+            let cells = placement.cells();
+            writeln!(out, "block {} at ({}, {}): {heat}", placement.block, placement.x, placement.y).unwrap();
+            for y in 0..self.h {
+                let line: String = (0..self.w).map(|x| if cells.contains(&(x, y)) { '#' } else { '.' }).collect();
+                writeln!(out, "{line}").unwrap();
+            }
+        }
+        out
+    }
+}
+impl ExactCoverProblem for Game2D {
+    fn num_columns(&self) -> usize {
+        self.blocks.len() + self.w * self.h
+    }
+
+    /// The placements of `get_placements`, as the columns of `get_matrix` they cover.
+    fn rows(&self) -> Box<dyn Iterator<Item = Vec<usize>> + '_> {
+        Box::new(self.get_placements().into_iter().map(|placement| self.placement_columns(&placement)))
+    }
+}
+
+/// Get the character drawing a block: the start of its name, or a letter from its index.
+pub(crate) fn block_char(name: Option<&str>, block: usize) -> char {
+    name.and_then(|name| name.chars().next()).unwrap_or(match block {
+        0..26 => (b'A' + block as u8) as char,
+        26..52 => (b'a' + (block - 26) as u8) as char,
+        _ => '#',
+    })
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Blocks drawn as lines of characters, '.' being empty and anything else filled. */
+export type Blocks = string[];
+"#;
+
+/// Get the matrix of the placements of blocks on a board `w` cells wide and `h` high.
+///
+/// @throws {DlxError} if the blocks aren't strings, or a block is empty, has no filled squares, or has
+/// lines of different widths.
+#[wasm_bindgen(unchecked_return_type = "Matrix")]
+pub fn js_matrix_from_blocks(w: usize, h: usize, #[wasm_bindgen(unchecked_param_type = "Blocks")] blocks: JsValue) -> Result<JsValue, JsError> {
+    let blocks: Vec<String> = serde_wasm_bindgen::from_value(blocks)?;
+    let mut game = Game2D::from_strings(w, h, blocks.iter().map(String::as_str).collect())?;
+
+    Ok(game.get_matrix().serialize(&Serializer::json_compatible())?)
 }
@@ -0,0 +1,431 @@
+//! Block generation in 3D.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
+use crate::Node;
+use super::blocks::{BlockError, InvalidBlock, ValidationError, ValidationReport, block_char};
+
+/// A rotation of space turning the axes onto each other, possibly mirroring them, as a matrix of
+/// -1, 0 and 1 entries applied to `(x, y, z)` column vectors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Rotation3 {
+    pub matrix: [[i8; 3]; 3],
+}
+impl Rotation3 {
+    /// The rotation leaving everything in place.
+    pub const IDENTITY: Rotation3 = Rotation3 { matrix: [[1, 0, 0], [0, 1, 0], [0, 0, 1]] };
+
+    /// Get the 24 rotations of a cube, the identity first.
+    pub fn all() -> Vec<Rotation3> {
+        Self::all_with_mirrors().into_iter().filter(|rotation| !rotation.is_mirror()).collect()
+    }
+
+    /// Get the 4 rotations of a cube around the z axis, keeping layers in place, the identity first.
+    pub fn flat() -> Vec<Rotation3> {
+        Self::all().into_iter().filter(|rotation| rotation.matrix[2] == [0, 0, 1]).collect()
+    }
+
+    /// Get the 24 rotations of a cube followed by their 24 mirror images, the identity first.
+    pub fn all_with_mirrors() -> Vec<Rotation3> {
+        let mut rotations = Vec::new();
+        let mut mirrors = Vec::new();
+        for (axes, odd) in [([0, 1, 2], false), ([0, 2, 1], true), ([1, 0, 2], true), ([1, 2, 0], false), ([2, 0, 1], false), ([2, 1, 0], true)] {
+            for signs in 0..8u8 {
+                let mut matrix = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    matrix[row][axis] = if signs >> row & 1 == 0 { 1 } else { -1 };
+                }
+                // a rotation keeps the handedness of the axes
+                if (signs.count_ones() % 2 == 1) == odd { rotations.push(Rotation3 { matrix }); } else { mirrors.push(Rotation3 { matrix }); }
+            }
+        }
+        rotations.append(&mut mirrors);
+        rotations
+    }
+
+    /// Check if this turns space into its mirror image.
+    pub fn is_mirror(&self) -> bool {
+        let m = self.matrix.map(|row| row.map(i64::from));
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        det < 0
+    }
+
+    /// Turn a point around the origin.
+    pub fn apply(&self, point: [i64; 3]) -> [i64; 3] {
+        self.matrix.map(|row| (0..3).map(|i| row[i] as i64 * point[i]).sum())
+    }
+}
+
+/// The ways a block may be turned when placed.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum OrientationSet {
+    /// Any of the 24 rotations.
+    #[default]
+    All24,
+    /// Any rotation or mirror image, for pieces that can be made either way.
+    All48WithMirror,
+    /// Only rotations around the z axis, the block lying flat on the layers.
+    FlatXYOnly,
+    /// Only these transformations, mirror images included only if listed.
+    Custom(Vec<Rotation3>),
+}
+impl OrientationSet {
+    /// Get the transformations of the set.
+    pub fn rotations(&self) -> Vec<Rotation3> {
+        match self {
+            OrientationSet::All24 => Rotation3::all(),
+            OrientationSet::All48WithMirror => Rotation3::all_with_mirrors(),
+            OrientationSet::FlatXYOnly => Rotation3::flat(),
+            OrientationSet::Custom(rotations) => rotations.clone(),
+        }
+    }
+}
+
+/// Blocks in 3D.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Block3D {
+    pub w: usize,
+    pub h: usize,
+    pub d: usize,
+    /// The filled squares, indexed as `data[z][y][x]`.
+    pub data: Vec<Vec<Vec<bool>>>,
+    pub name: Option<String>,
+    /// The ways the block may be turned when placed.
+    pub orientations: OrientationSet,
+}
+impl Block3D {
+    /// Create a block from one string per layer, bottom first. Empty squares are '.', filled are
+    /// anything else, and layers are padded with empty squares to the widest and tallest one.
+    /// Errors if there are no layers or no filled squares.
+    pub fn from_layers(layers: &[&str]) -> Result<Block3D, BlockError> {
+        let layers: Vec<Vec<Vec<bool>>> = layers.iter().map(|layer| {
+            layer.trim().lines().map(|line| line.trim().chars().map(|ch| ch != '.').collect()).collect()
+        }).collect();
+
+        if layers.is_empty() { return Err(BlockError::Empty); }
+        if !layers.iter().flatten().flatten().any(|filled| *filled) { return Err(BlockError::NoCells); }
+
+        let w = layers.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        let h = layers.iter().map(Vec::len).max().unwrap_or(0);
+        let data = layers.into_iter().map(|mut layer| {
+            layer.resize(h, Vec::new());
+            for row in &mut layer { row.resize(w, false); }
+            layer
+        }).collect::<Vec<_>>();
+
+        Ok(Block3D { w, h, d: data.len(), data, name: None, orientations: OrientationSet::All24 })
+    }
+
+    /// Create the smallest block holding cells given as `(x, y, z)`, shifted to touch the origin.
+    /// Errors if there are no cells.
+    pub fn from_cells(cells: &[[i64; 3]]) -> Result<Block3D, BlockError> {
+        let Some(&first) = cells.first() else { return Err(BlockError::NoCells) };
+        let (min, max) = cells.iter().fold((first, first), |(min, max), cell| {
+            (std::array::from_fn(|i| min[i].min(cell[i])), std::array::from_fn(|i| max[i].max(cell[i])))
+        });
+        let [w, h, d] = std::array::from_fn(|i| (max[i] - min[i] + 1) as usize);
+
+        let mut data = vec![vec![vec![false; w]; h]; d];
+        for cell in cells {
+            data[(cell[2] - min[2]) as usize][(cell[1] - min[1]) as usize][(cell[0] - min[0]) as usize] = true;
+        }
+        Ok(Block3D { w, h, d, data, name: None, orientations: OrientationSet::All24 })
+    }
+
+    /// Give the block a name.
+    pub fn named(mut self, name: &str) -> Block3D {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Restrict the ways the block may be turned when placed.
+    pub fn orientations(mut self, orientations: OrientationSet) -> Block3D {
+        self.orientations = orientations;
+        self
+    }
+
+    /// Get the filled squares as `(x, y, z)`.
+    pub fn cells(&self) -> Vec<[i64; 3]> {
+        let mut cells = Vec::new();
+        for (z, layer) in self.data.iter().enumerate() {
+            for (y, row) in layer.iter().enumerate() {
+                for (x, filled) in row.iter().enumerate() {
+                    if *filled { cells.push([x as i64, y as i64, z as i64]); }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Get the block turned by a rotation, shrunk to the squares it fills.
+    pub fn transformed(&self, rotation: Rotation3) -> Block3D {
+        let cells: Vec<[i64; 3]> = self.cells().into_iter().map(|cell| rotation.apply(cell)).collect();
+        let block = Block3D::from_cells(&cells).expect("a block has filled squares");
+        Block3D { name: self.name.clone(), orientations: self.orientations.clone(), ..block }
+    }
+
+    /// Get every polycube of `n` cells, counting ones that are rotations of each other once, but mirror
+    /// images separately.
+    pub fn free_polycubes(n: usize) -> Vec<Block3D> {
+        Self::polycubes(n, OrientationSet::All24)
+    }
+
+    /// Get every polycube of `n` cells, counting ones that are transformations of each other by
+    /// `orientations` once: `All48WithMirror` also merges mirror images, and `Custom` with only the
+    /// identity gives every fixed polycube. The blocks are placed with the same orientations.
+    pub fn polycubes(n: usize, orientations: OrientationSet) -> Vec<Block3D> {
+        if n == 0 { return Vec::new(); }
+
+        // grow every polycube of one size by a cell to get the next size
+        let mut level: BTreeSet<Vec<[i64; 3]>> = BTreeSet::from([vec![[0, 0, 0]]]);
+        for _ in 1..n {
+            let mut next = BTreeSet::new();
+            for cells in &level {
+                for cell in cells {
+                    for axis in 0..3 {
+                        for step in [-1, 1] {
+                            let mut neighbor = *cell;
+                            neighbor[axis] += step;
+                            if cells.contains(&neighbor) { continue; }
+
+                            let mut grown = cells.clone();
+                            grown.push(neighbor);
+                            let block = Block3D::from_cells(&grown).expect("a polycube has cells").orientations(orientations.clone());
+                            next.insert(block.canonical_cells());
+                        }
+                    }
+                }
+            }
+            level = next;
+        }
+
+        level.into_iter()
+            .map(|cells| Block3D::from_cells(&cells).expect("a polycube has cells").orientations(orientations.clone()))
+            .collect()
+    }
+
+    /// Get the cells of the block regardless of how it is turned by its orientations, equal for blocks
+    /// that are transformations of each other.
+    pub fn canonical_cells(&self) -> Vec<[i64; 3]> {
+        self.get_transformations().into_iter().map(|(_, block)| block.cells()).min().unwrap()
+    }
+
+    /// Get all the unique transformations of a block allowed by its orientations, in the order they
+    /// are first reached.
+    pub fn get_transformations(&self) -> Vec<(Rotation3, Block3D)> {
+        let mut hs: HashSet<Block3D> = HashSet::new();
+        let mut transformations = Vec::new();
+        for rotation in self.orientations.rotations() {
+            let block = self.transformed(rotation);
+            if hs.insert(block.clone()) { transformations.push((rotation, block)); }
+        }
+        transformations
+    }
+}
+
+/// A rotation of a block shifted to a position in the box, as one row of the matrix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Placement3D {
+    /// Index of the block in the game.
+    pub block: usize,
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+    /// The first transformation found giving the shape.
+    pub rotation: Rotation3,
+    /// The rotated block.
+    pub shape: Block3D,
+}
+impl Placement3D {
+    /// Get the box cells covered, as `(x, y, z)`.
+    pub fn cells(&self) -> Vec<(usize, usize, usize)> {
+        self.shape.cells().into_iter()
+            .map(|[x, y, z]| (self.x + x as usize, self.y + y as usize, self.z + z as usize))
+            .collect()
+    }
+}
+
+/// A container for blocks, bounded with a width, height and depth.
+pub struct Game3D {
+    pub w: usize,
+    pub h: usize,
+    pub d: usize,
+    pub blocks: Vec<Block3D>,
+    /// Which cells of the box exist and have to be covered, indexed as `mask[z][y][x]`.
+    pub mask: Vec<Vec<Vec<bool>>>,
+}
+impl Game3D {
+    /// Create a game from a width, height, depth, and blocks given as layer strings.
+    /// Errors with the position of the first block not made by its layers.
+    pub fn from_layers(w: usize, h: usize, d: usize, blocks: Vec<Vec<&str>>) -> Result<Game3D, InvalidBlock> {
+        let mut parsed = Vec::new();
+        for (i, layers) in blocks.iter().enumerate() {
+            parsed.push(Block3D::from_layers(layers).map_err(|error| InvalidBlock { block: i, error })?);
+        }
+
+        Ok(Game3D::new(w, h, d, parsed))
+    }
+
+    /// Create a game from a width, height, depth, and blocks.
+    pub fn new(w: usize, h: usize, d: usize, blocks: Vec<Block3D>) -> Game3D {
+        Game3D { w, h, d, blocks, mask: vec![vec![vec![true; w]; h]; d] }
+    }
+
+    /// Create a game whose cells are given by one string per layer, bottom first. Cells that exist
+    /// are '#', and anything else is a void or lies outside the shape. The box is as large as the
+    /// widest line, the most lines, and the amount of layers.
+    pub fn from_layer_strings(layers: Vec<&str>, blocks: Vec<Block3D>) -> Game3D {
+        let layers: Vec<Vec<Vec<bool>>> = layers.iter().map(|layer| {
+            layer.trim().lines().map(|line| line.trim().chars().map(|ch| ch == '#').collect()).collect()
+        }).collect();
+
+        let w = layers.iter().flatten().map(Vec::len).max().unwrap_or(0);
+        let h = layers.iter().map(Vec::len).max().unwrap_or(0);
+        let mask: Vec<Vec<Vec<bool>>> = layers.into_iter().map(|mut layer| {
+            layer.resize(h, Vec::new());
+            for row in &mut layer { row.resize(w, false); }
+            layer
+        }).collect();
+
+        Game3D { w, h, d: mask.len(), blocks, mask }
+    }
+
+    /// Get the column of every cell of the box after the block columns, `None` for cells that don't
+    /// exist, indexed as `columns[z][y][x]`.
+    fn cell_columns(&self) -> Vec<Vec<Vec<Option<usize>>>> {
+        let mut next = self.blocks.len();
+        self.mask.iter().map(|layer| layer.iter().map(|row| row.iter().map(|&exists| {
+            exists.then(|| { next += 1; next - 1 })
+        }).collect()).collect()).collect()
+    }
+
+    /// Get the amount of cells of the box that exist.
+    pub fn area(&self) -> usize {
+        self.mask.iter().flatten().flatten().filter(|exists| **exists).count()
+    }
+
+    /// Get every placement of every block in the existing cells of the box, in the order of the rows of `get_matrix`.
+    pub fn get_placements(&self) -> Vec<Placement3D> {
+        let mut placements = Vec::new();
+        for (i, block) in self.blocks.iter().enumerate() {
+            for (rotation, shape) in block.get_transformations() {
+                // a rotation larger than the box has nowhere to go
+                let (Some(max_x), Some(max_y), Some(max_z)) = (self.w.checked_sub(shape.w), self.h.checked_sub(shape.h), self.d.checked_sub(shape.d)) else { continue };
+                for z in 0..=max_z {
+                    for y in 0..=max_y {
+                        for x in 0..=max_x {
+                            let placement = Placement3D { block: i, x, y, z, rotation, shape: shape.clone() };
+                            if placement.cells().into_iter().all(|(x, y, z)| self.mask[z][y][x]) { placements.push(placement); }
+                        }
+                    }
+                }
+            }
+        }
+
+        placements
+    }
+
+    /// Create a matrix from the blocks in the game to use within DLX and create the structure.
+    /// The first columns are the blocks, followed by the existing cells layer by layer, row by row.
+    pub fn get_matrix(&self) -> Vec<Vec<bool>> {
+        let columns = self.cell_columns();
+        let width = self.blocks.len() + self.area();
+        self.get_placements().into_iter().map(|placement| {
+            let mut row = vec![false; width];
+            row[placement.block] = true;
+            for (x, y, z) in placement.cells() {
+                row[columns[z][y][x].expect("placements only cover existing cells")] = true;
+            }
+            row
+        }).collect()
+    }
+
+    /// Run every cheap check that can tell a game has no solution without solving it.
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let placements = self.get_placements();
+        let color = |(x, y, z): (usize, usize, usize)| if (x + y + z) % 2 == 0 { 1 } else { -1 };
+
+        for i in 0..self.blocks.len() {
+            if placements.iter().all(|placement| placement.block != i) {
+                report.errors.push(ValidationError::NoPlacement { block: i });
+            }
+        }
+
+        let area: usize = self.blocks.iter().map(|block| block.cells().len()).sum();
+        if area != self.area() {
+            report.errors.push(ValidationError::AreaMismatch { board: self.area(), blocks: area });
+        }
+
+        // the color balances reachable by placing every block once
+        let mut reachable: HashSet<i64> = HashSet::from([0]);
+        for i in 0..self.blocks.len() {
+            let balances: HashSet<i64> = placements.iter()
+                .filter(|placement| placement.block == i)
+                .map(|placement| placement.cells().into_iter().map(color).sum())
+                .collect();
+            reachable = reachable.iter().flat_map(|a| balances.iter().map(move |b| a + b)).collect();
+        }
+        let mut board = 0;
+        for (z, layer) in self.mask.iter().enumerate() {
+            for (y, row) in layer.iter().enumerate() {
+                for (x, exists) in row.iter().enumerate() {
+                    if *exists { board += color((x, y, z)); }
+                }
+            }
+        }
+        if !reachable.is_empty() && !reachable.contains(&board) { report.errors.push(ValidationError::Parity); }
+
+        report
+    }
+
+    /// Find one solution as rows of `get_matrix`, or `None` when there is none, such as when a block or
+    /// cell can't be covered by any placement.
+    pub fn solve_once(&self) -> Option<Vec<usize>> {
+        let matrix = self.get_matrix();
+        let width = self.blocks.len() + self.area();
+        if !(0..width).all(|column| matrix.iter().any(|row| row[column])) { return None; }
+        if matrix.is_empty() { return Some(Vec::new()); }
+
+        Node::solve_once(&matrix)
+    }
+
+    /// Get the block covering every cell of the box for a solution given as rows of `get_matrix`,
+    /// indexed as `grid[z][y][x]`, cells that don't exist being `None`.
+    pub fn solution_grid_3d(&self, rows: &[usize]) -> Vec<Vec<Vec<Option<usize>>>> {
+        let placements = self.get_placements();
+        let mut grid = vec![vec![vec![None; self.w]; self.h]; self.d];
+        for &row in rows {
+            for (x, y, z) in placements[row].cells() {
+                grid[z][y][x] = Some(placements[row].block);
+            }
+        }
+        grid
+    }
+
+    /// Get the character drawing a block: the start of its name, or a letter from its index.
+    pub fn block_char(&self, block: usize) -> char {
+        block_char(self.blocks[block].name.as_deref(), block)
+    }
+
+    /// Render a solution given as rows of `get_matrix`, one "layer k" heading and grid per layer from
+    /// the bottom, drawing each block with `block_char`, empty cells as '.' and cells that don't exist
+    /// as ' '.
+    pub fn render_solution(&self, rows: &[usize]) -> String {
+        let mut out = String::new();
+        for (z, layer) in self.solution_grid_3d(rows).iter().enumerate() {
+            writeln!(out, "layer {z}").unwrap();
+            for (y, line) in layer.iter().enumerate() {
+                let line: String = line.iter().enumerate().map(|(x, cell)| match cell {
+                    Some(block) => self.block_char(*block),
+                    None if self.mask[z][y][x] => '.',
+                    None => ' ',
+                }).collect();
+                writeln!(out, "{line}").unwrap();
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,159 @@
+//! Assembling matrices out of blocks of columns.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Rows of an exact cover problem, each one marking the columns it covers.
+pub type Matrix = Vec<Vec<bool>>;
+
+/// Why matrices can't be put together.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ComposeError {
+    /// A row of a matrix is not as wide as its first one.
+    RaggedRow { row: usize, expected: usize, got: usize },
+    /// A part of a stack doesn't have as many rows as the first one.
+    RowCount { part: usize, expected: usize, got: usize },
+    /// A permutation doesn't take every column exactly once.
+    NotPermutation { width: usize },
+    /// A row is past the end of the matrix.
+    RowOutOfRange { row: usize, rows: usize },
+    /// A row is forbidden from being chosen together with itself.
+    SelfExclusion { row: usize },
+}
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ComposeError::RaggedRow { row, expected, got } => write!(f, "row {row} has {got} columns instead of {expected}"),
+            ComposeError::RowCount { part, expected, got } => write!(f, "part {part} has {got} rows instead of {expected}"),
+            ComposeError::NotPermutation { width } => write!(f, "not a permutation of {width} columns"),
+            ComposeError::RowOutOfRange { row, rows } => write!(f, "row {row} is out of range for {rows} rows"),
+            ComposeError::SelfExclusion { row } => write!(f, "row {row} is forbidden together with itself"),
+        }
+    }
+}
+impl std::error::Error for ComposeError {}
+
+/// Get the width of a matrix, checking every row has it. A matrix without rows has no columns.
+pub(crate) fn width(matrix: &[Vec<bool>]) -> Result<usize, ComposeError> {
+    let expected = matrix.first().map_or(0, Vec::len);
+    match matrix.iter().position(|row| row.len() != expected) {
+        Some(row) => Err(ComposeError::RaggedRow { row, expected, got: matrix[row].len() }),
+        None => Ok(expected),
+    }
+}
+
+/// Put matrices side by side, row by row. They all need as many rows.
+pub fn hstack(parts: &[Matrix]) -> Result<Matrix, ComposeError> {
+    let expected = parts.first().map_or(0, Vec::len);
+    for (part, matrix) in parts.iter().enumerate() {
+        width(matrix)?;
+        if matrix.len() != expected {
+            return Err(ComposeError::RowCount { part, expected, got: matrix.len() });
+        }
+    }
+
+    Ok((0..expected).map(|y| parts.iter().flat_map(|matrix| matrix[y].iter().copied()).collect()).collect())
+}
+
+/// Create the `n` by `n` identity matrix, row `i` covering only column `i`.
+pub fn identity_block(n: usize) -> Matrix {
+    (0..n).map(|i| (0..n).map(|j| i == j).collect()).collect()
+}
+
+/// Reorder the columns of a matrix, column `i` of the result being column `perm[i]` of the input.
+pub fn column_permutation(matrix: &[Vec<bool>], perm: &[usize]) -> Result<Matrix, ComposeError> {
+    let width = width(matrix)?;
+    let mut taken = vec![false; width];
+    for &x in perm {
+        if x >= width || std::mem::replace(&mut taken[x], true) {
+            return Err(ComposeError::NotPermutation { width });
+        }
+    }
+    if perm.len() != width { return Err(ComposeError::NotPermutation { width }); }
+
+    Ok(matrix.iter().map(|row| perm.iter().map(|&x| row[x]).collect()).collect())
+}
+
+/// Add empty columns to a matrix, `before` on the left and `after` on the right.
+pub fn pad_columns(matrix: &[Vec<bool>], before: usize, after: usize) -> Result<Matrix, ComposeError> {
+    width(matrix)?;
+    Ok(matrix.iter().map(|row| {
+        let mut padded = vec![false; before];
+        padded.extend(row);
+        padded.resize(padded.len() + after, false);
+        padded
+    }).collect())
+}
+
+/// A matrix with extra columns keeping pairs of rows from being chosen together, made by `forbid_pairs`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Exclusions {
+    /// The original rows along with the extra columns, followed by a filler row covering only that
+    /// column for each extra column, so that it can be left out by the other rows.
+    pub matrix: Matrix,
+    /// The extra columns, each one covered by at most one original row in a solution.
+    pub secondary: Vec<usize>,
+    /// The amount of rows of the original matrix.
+    pub rows: usize,
+}
+impl Exclusions {
+    /// Get a solution of `matrix` as rows of the original matrix, dropping the filler rows.
+    pub fn decode(&self, solution: &[usize]) -> Vec<usize> {
+        solution.iter().copied().filter(|&row| row < self.rows).collect()
+    }
+}
+
+/// Compile pairs of rows that must not both be chosen into extra columns of a matrix, set by the rows
+/// of the pairs, the solutions of the result being those of the input without a forbidden pair.
+/// Rows forbidden with each other pairwise share one column, so a group of `k` rows of which at most
+/// one may be chosen takes a single column instead of one per pair, and there are never more extra
+/// columns than distinct pairs.
+pub fn forbid_pairs(matrix: &[Vec<bool>], pairs: &[(usize, usize)]) -> Result<Exclusions, ComposeError> {
+    let width = width(matrix)?;
+    let rows = matrix.len();
+    let mut forbidden: HashSet<(usize, usize)> = HashSet::new();
+    let mut neighbors: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &(a, b) in pairs {
+        if let Some(row) = [a, b].into_iter().find(|&row| row >= rows) { return Err(ComposeError::RowOutOfRange { row, rows }); }
+        if a == b { return Err(ComposeError::SelfExclusion { row: a }); }
+        // an empty row is never chosen, and would become a filler of the column
+        if [a, b].iter().any(|&row| !matrix[row].contains(&true)) { continue; }
+        if forbidden.insert((a.min(b), a.max(b))) {
+            neighbors.entry(a).or_default().push(b);
+            neighbors.entry(b).or_default().push(a);
+        }
+    }
+    let is_forbidden = |a: usize, b: usize| forbidden.contains(&(a.min(b), a.max(b)));
+
+    // grow a group of pairwise forbidden rows from every pair not grouped yet, in order
+    let mut seeds: Vec<(usize, usize)> = forbidden.iter().copied().collect();
+    seeds.sort_unstable();
+    for list in neighbors.values_mut() { list.sort_unstable(); }
+    let mut grouped: HashSet<(usize, usize)> = HashSet::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (a, b) in seeds {
+        if grouped.contains(&(a, b)) { continue; }
+
+        let mut group = vec![a, b];
+        for &c in &neighbors[&a] {
+            if !group.contains(&c) && group.iter().all(|&row| is_forbidden(row, c)) { group.push(c); }
+        }
+        for (i, &x) in group.iter().enumerate() {
+            for &y in &group[i + 1..] { grouped.insert((x.min(y), x.max(y))); }
+        }
+        groups.push(group);
+    }
+
+    let mut extended: Matrix = matrix.iter().map(|row| {
+        let mut row = row.clone();
+        row.resize(width + groups.len(), false);
+        row
+    }).collect();
+    for (i, group) in groups.iter().enumerate() {
+        for &row in group { extended[row][width + i] = true; }
+    }
+    let fillers = pad_columns(&identity_block(groups.len()), width, 0)?;
+    extended.extend(fillers);
+
+    Ok(Exclusions { matrix: extended, secondary: (width..width + groups.len()).collect(), rows })
+}
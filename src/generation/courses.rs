@@ -1,25 +1,415 @@
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::ops::{ControlFlow, Range};
 use wasm_bindgen::JsValue;
 use wasm_bindgen::prelude::*;
 use serde_wasm_bindgen::Serializer;
-use serde::Serialize;
-
-#[wasm_bindgen]
-pub fn js_matrix_from_courses(input: JsValue) -> JsValue {
-    let input: Vec<Vec<bool>> = serde_wasm_bindgen::from_value(input).unwrap();
-    let sig_width = input.len();
-    let width = sig_width + input[0].len();
-    let mut matrix: Vec<Vec<bool>> = Vec::new();
-    
-    for (i, course) in input.iter().enumerate() {
-        for (j, slot) in course.iter().enumerate() {
-            if *slot {
-                let mut entry = vec![false; width];
-                entry[i] = true;
-                entry[j + sig_width] = true;
-                matrix.push(entry);
+use serde::{Deserialize, Serialize};
+use crate::Node;
+use super::compose::{ComposeError, Matrix, hstack, identity_block, width};
+
+/// A way to schedule a course: the time slots it takes, and how much it is disliked.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pattern {
+    pub slots: Vec<usize>,
+    pub cost: u64,
+}
+
+/// A course scheduled with one of its patterns.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct Assignment {
+    pub course: usize,
+    pub pattern: usize,
+}
+
+/// Optional courses out of which exactly `count` are scheduled.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize)]
+pub struct CourseGroup {
+    pub courses: Vec<usize>,
+    pub count: usize,
+}
+
+/// A course group that can't be added to a schedule.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GroupError {
+    /// The group has fewer courses than it must schedule.
+    TooFewCourses { courses: usize, count: usize },
+    /// A course doesn't exist, or is already in a group.
+    Course(usize),
+}
+impl fmt::Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GroupError::TooFewCourses { courses, count } => write!(f, "can't schedule {count} out of {courses} courses"),
+            GroupError::Course(course) => write!(f, "course {course} doesn't exist or is already in a group"),
+        }
+    }
+}
+impl std::error::Error for GroupError {}
+
+/// The columns entering and leaving a state of a course group, if they exist.
+type StateColumns = (Option<usize>, Option<usize>);
+
+/// A row of the matrix of a schedule.
+struct Row {
+    columns: Vec<usize>,
+    cost: u64,
+    /// What the row schedules, if anything.
+    assignment: Option<Assignment>,
+}
+
+/// Courses to fit into time slots, every course taking exactly one of its patterns and every slot
+/// holding at most one course. Courses in a group are optional, only as many as it requires being
+/// scheduled.
+#[derive(Clone, Debug, Default)]
+pub struct Schedule {
+    pub slots: usize,
+    /// For every course, the patterns it can be scheduled with.
+    pub courses: Vec<Vec<Pattern>>,
+    /// Disjoint groups of courses along with how many of each to schedule.
+    pub groups: Vec<CourseGroup>,
+    /// Slots no course may take, any pattern touching one being left out.
+    pub blocked: Vec<usize>,
+}
+impl Schedule {
+    /// Create a schedule from the time slots every course can be put in, each taking a single slot.
+    /// `costs` runs parallel to `availability`, only its entries for available slots being used;
+    /// without it every pattern is free.
+    pub fn from_availability(availability: &[Vec<bool>], costs: Option<&[Vec<u64>]>) -> Schedule {
+        let slots = availability.first().map_or(0, Vec::len);
+        let courses = availability.iter().enumerate().map(|(i, course)| {
+            course.iter().enumerate().filter(|(_, available)| **available).map(|(j, _)| Pattern {
+                slots: vec![j],
+                cost: costs.map_or(0, |costs| costs[i][j]),
+            }).collect()
+        }).collect();
+
+        Schedule { slots, courses, groups: Vec::new(), blocked: Vec::new() }
+    }
+
+    /// Schedule exactly `count` courses out of a group instead of all of them.
+    /// Errors if the group is too small, or has a course that doesn't exist or is already in a group.
+    pub fn require_count(&mut self, group: &[usize], count: usize) -> Result<(), GroupError> {
+        if group.len() < count {
+            return Err(GroupError::TooFewCourses { courses: group.len(), count });
+        }
+        for (i, &course) in group.iter().enumerate() {
+            let grouped = self.groups.iter().any(|group| group.courses.contains(&course));
+            if course >= self.courses.len() || grouped || group[..i].contains(&course) {
+                return Err(GroupError::Course(course));
+            }
+        }
+
+        self.groups.push(CourseGroup { courses: group.to_vec(), count });
+        Ok(())
+    }
+
+    /// Forbid some slots, such as breaks, leaving out every pattern that touches one.
+    /// Returns the courses this leaves without any pattern, which can then never be scheduled.
+    pub fn block_slots(&mut self, slots: &[usize]) -> Vec<usize> {
+        let before: Vec<bool> = (0..self.courses.len()).map(|course| self.has_patterns(course)).collect();
+        self.blocked.extend(slots);
+        (0..self.courses.len()).filter(|&course| before[course] && !self.has_patterns(course)).collect()
+    }
+
+    /// Check if a pattern touches a blocked slot.
+    fn is_blocked(&self, pattern: &Pattern) -> bool {
+        pattern.slots.iter().any(|slot| self.blocked.contains(slot))
+    }
+
+    /// Check if a course has a pattern left.
+    fn has_patterns(&self, course: usize) -> bool {
+        self.courses[course].iter().any(|pattern| !self.is_blocked(pattern))
+    }
+
+    /// Lay out the rows of the matrix, returning its width along with them.
+    ///
+    /// The courses of a group are decided one by one along a path through the states "`k` courses
+    /// scheduled out of the first `g`", from no courses to all of them with `count` scheduled. Every
+    /// course takes a step, scheduling itself or not, and every state off the path is filled by a
+    /// row of its own. So each choice of courses is a single solution.
+    fn rows(&self) -> (usize, Vec<Row>) {
+        let mut width = self.courses.len() + self.slots;
+
+        // the columns of every state of every group, by courses decided and courses scheduled
+        let mut states: Vec<Vec<Vec<StateColumns>>> = Vec::new();
+        // the group of every course along with its position in it
+        let mut positions = vec![None; self.courses.len()];
+        for (i, group) in self.groups.iter().enumerate() {
+            let (m, n) = (group.courses.len(), group.count);
+            assert!(n <= m, "can't schedule {n} out of {m} courses");
+            states.push((0..=m).map(|g| {
+                (0..=n).map(|k| {
+                    // states from which the count can't be reached are left out
+                    if k > g || n - k > m - g { return (None, None); }
+                    let into = (g > 0).then(|| { width += 1; width - 1 });
+                    let out = (g < m).then(|| { width += 1; width - 1 });
+                    (into, out)
+                }).collect()
+            }).collect());
+            for (g, &course) in group.courses.iter().enumerate() {
+                positions[course] = Some((i, g));
             }
         }
+
+        let mut rows = Vec::new();
+        for (course, patterns) in self.courses.iter().enumerate() {
+            // the steps the course may take as (group columns, whether it is scheduled)
+            let steps: Vec<(Vec<usize>, bool)> = match positions[course] {
+                None => vec![(Vec::new(), true)],
+                Some((i, g)) => {
+                    let mut steps = Vec::new();
+                    for k in 0..states[i][g].len() {
+                        let Some(out) = states[i][g][k].1 else { continue };
+                        if let Some(into) = states[i][g + 1].get(k + 1).and_then(|state| state.0) {
+                            steps.push((vec![out, into], true));
+                        }
+                        if let Some(into) = states[i][g + 1][k].0 {
+                            steps.push((vec![out, into], false));
+                        }
+                    }
+                    steps
+                }
+            };
+
+            for (group_columns, scheduled) in steps {
+                if !scheduled {
+                    rows.push(Row { columns: [vec![course], group_columns].concat(), cost: 0, assignment: None });
+                    continue;
+                }
+                for (pattern, Pattern { slots, cost }) in patterns.iter().enumerate().filter(|(_, p)| !self.is_blocked(p)) {
+                    let mut columns = vec![course];
+                    columns.extend(slots.iter().map(|slot| self.courses.len() + slot));
+                    columns.extend(&group_columns);
+                    rows.push(Row { columns, cost: *cost, assignment: Some(Assignment { course, pattern }) });
+                }
+            }
+        }
+
+        for slot in 0..self.slots {
+            rows.push(Row { columns: vec![self.courses.len() + slot], cost: 0, assignment: None });
+        }
+        for state in states.iter().flatten().flatten() {
+            if let (Some(into), Some(out)) = state {
+                rows.push(Row { columns: vec![*into, *out], cost: 0, assignment: None });
+            }
+        }
+
+        (width, rows)
+    }
+
+    /// Create the matrix of the schedule along with the cost of every row. The first columns are the
+    /// courses, followed by the slots, followed by the states of the groups. The first rows are the
+    /// unblocked patterns and skips course by course, followed by one free row per slot standing for it staying
+    /// empty, followed by one row per group state standing for it not being passed through.
+    pub fn get_matrix(&self) -> (Vec<Vec<bool>>, Vec<u64>) {
+        let (width, rows) = self.rows();
+        let (courses, slots) = (self.courses.len(), self.slots);
+        let parts = [
+            block(&rows, 0..courses),
+            block(&rows, courses..courses + slots),
+            block(&rows, courses + slots..width),
+        ];
+        let matrix = hstack(&parts).expect("the blocks have a row per row of the schedule");
+
+        (matrix, rows.iter().map(|row| row.cost).collect())
+    }
+
+    /// Get the assignments of a solution given as rows of `get_matrix`, by course.
+    pub fn assignments(&self, rows: &[usize]) -> Vec<Assignment> {
+        assignments_of(&self.rows().1, rows)
     }
-    
-    matrix.serialize(&Serializer::json_compatible()).unwrap()
+
+    /// Find the schedule with the lowest total cost, along with its cost.
+    pub fn solve_best(&self) -> Option<(u64, Vec<Assignment>)> {
+        self.solve_best_k(1).pop()
+    }
+
+    /// Find the `k` schedules with the lowest total cost by nondecreasing cost, along with their cost.
+    pub fn solve_best_k(&self, k: usize) -> Vec<(u64, Vec<Assignment>)> {
+        if let Some(schedules) = self.trivial() {
+            return schedules.into_iter().take(k).map(|assignments| (0, assignments)).collect();
+        }
+
+        let (matrix, costs) = self.get_matrix();
+        Node::solve_ordered_by_cost(&matrix, &costs)
+            .take(k)
+            .map(|(cost, rows)| (cost, self.assignments(&rows)))
+            .collect()
+    }
+
+    /// Go through every schedule, keeping the `limit` ones with the lowest score sorted by it.
+    /// Schedules of equal score are kept in the order they are found.
+    pub fn enumerate_ranked(&self, score: impl Fn(&[Assignment]) -> i64, limit: usize) -> Vec<ScoredSchedule> {
+        if limit == 0 { return Vec::new(); }
+
+        // the worst schedule kept so far on top
+        let mut heap: BinaryHeap<(i64, usize, Vec<Assignment>)> = BinaryHeap::new();
+        let mut offer = |seq: usize, assignments: Vec<Assignment>| {
+            let score = score(&assignments);
+            if heap.len() == limit && heap.peek().is_some_and(|&(worst, _, _)| worst <= score) { return; }
+            heap.push((score, seq, assignments));
+            if heap.len() > limit { heap.pop(); }
+        };
+
+        match self.trivial() {
+            Some(schedules) => schedules.into_iter().enumerate().for_each(|(seq, assignments)| offer(seq, assignments)),
+            None => {
+                // scored as they are found, so only the schedules kept are held
+                let (_, all_rows) = self.rows();
+                let mut seq = 0;
+                let _ = Node::solve_with(&self.get_matrix().0, |rows| {
+                    offer(seq, assignments_of(&all_rows, rows));
+                    seq += 1;
+                    ControlFlow::Continue(())
+                });
+            }
+        }
+
+        heap.into_sorted_vec().into_iter()
+            .map(|(score, _, assignments)| ScoredSchedule { score, assignments })
+            .collect()
+    }
+
+    /// Get the total cost of the patterns of a schedule.
+    pub fn cost(&self, assignments: &[Assignment]) -> u64 {
+        assignments.iter().map(|a| self.courses[a.course][a.pattern].cost).sum()
+    }
+
+    /// Settle the schedules the solver can't be handed: none if a course or group state can't be
+    /// covered by any row, or only the empty one if there are no rows at all.
+    fn trivial(&self) -> Option<Vec<Vec<Assignment>>> {
+        let (width, rows) = self.rows();
+        if (0..width).any(|column| rows.iter().all(|row| !row.columns.contains(&column))) { return Some(Vec::new()); }
+        rows.is_empty().then(|| vec![Vec::new()])
+    }
+}
+
+/// Get the matrix of the courses and the time slots they can be scheduled in, the one of a schedule of
+/// them without costs, groups or blocked slots. It is what the wasm and Node.js bindings hand out.
+/// Errors if the courses don't all have as many slots.
+pub fn matrix_from_courses(availability: &[Vec<bool>]) -> Result<Matrix, ComposeError> {
+    width(availability)?;
+    Ok(Schedule::from_availability(availability, None).get_matrix().0)
+}
+
+/// Get the block of the columns in a range of the rows of a schedule, every row being the sum of the
+/// rows of the identity block of its columns there.
+fn block(rows: &[Row], columns: Range<usize>) -> Matrix {
+    let identity = identity_block(columns.len());
+    rows.iter().map(|row| {
+        let mut entry = vec![false; columns.len()];
+        for column in row.columns.iter().filter(|column| columns.contains(column)) {
+            entry.iter_mut().zip(&identity[column - columns.start]).for_each(|(entry, &one)| *entry |= one);
+        }
+        entry
+    }).collect()
+}
+
+/// Get the assignments of the rows chosen out of some rows of a schedule, by course.
+fn assignments_of(all_rows: &[Row], rows: &[usize]) -> Vec<Assignment> {
+    let mut assignments: Vec<Assignment> = rows.iter().filter_map(|&row| all_rows[row].assignment).collect();
+    assignments.sort_by_key(|assignment| assignment.course);
+    assignments
+}
+
+/// A schedule along with its score, lower being better.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoredSchedule {
+    pub score: i64,
+    pub assignments: Vec<Assignment>,
+}
+
+/// Scores schedules by how spread out they are: the amount of free slots between the first and the
+/// last slot taken.
+pub struct Compactness<'a>(pub &'a Schedule);
+impl Compactness<'_> {
+    /// Get the amount of gaps in a schedule.
+    pub fn score(&self, assignments: &[Assignment]) -> i64 {
+        let mut taken: Vec<usize> = assignments.iter()
+            .flat_map(|a| self.0.courses[a.course][a.pattern].slots.iter().copied())
+            .collect();
+        taken.sort_unstable();
+        taken.dedup();
+        match (taken.first(), taken.last()) {
+            (Some(first), Some(last)) => (last - first + 1 - taken.len()) as i64,
+            _ => 0,
+        }
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** For every course, the time slots it can be scheduled in. */
+export type Courses = boolean[][];
+/** For every course, the cost of scheduling it in each time slot, parallel to `Courses`. */
+export type CourseCosts = number[][];
+/** A course scheduled with one of its patterns, a pattern being an available slot by order. */
+export type Assignment = { course: number, pattern: number };
+/** Optional courses out of which exactly `count` are scheduled. */
+export type CourseGroup = { courses: number[], count: number };
+/** Constraints on a schedule besides availability. */
+export type ScheduleConstraints = { groups?: CourseGroup[], blockedSlots?: number[] };
+/** The cheapest schedule, with its total cost. */
+export type BestSchedule = { cost: number, assignments: Assignment[] };
+/** The cheapest schedule if there is one, and the courses the blocked slots leave without any slot. */
+export type ScheduleResult = { best: BestSchedule | null, unschedulable: number[] };
+"#;
+
+/// Get the matrix of the courses and the time slots they can be scheduled in, laid out like
+/// `Schedule::get_matrix`: a row per course and slot it can take, then a free row per slot.
+///
+/// @throws {DlxError} if the courses aren't rows of booleans, or don't all have as many slots.
+#[wasm_bindgen(unchecked_return_type = "Matrix")]
+pub fn js_matrix_from_courses(#[wasm_bindgen(unchecked_param_type = "Courses")] input: JsValue) -> Result<JsValue, JsError> {
+    let input: Vec<Vec<bool>> = serde_wasm_bindgen::from_value(input)?;
+    Ok(matrix_from_courses(&input)?.serialize(&Serializer::json_compatible())?)
+}
+
+/// Find the cheapest schedule of the courses, `null` if they can't be scheduled, along with the courses
+/// that lose every slot to the blocked ones, as `Schedule::block_slots` returns them.
+///
+/// @throws {DlxError} if an argument doesn't have the shape of its type, the courses don't all have as
+/// many slots, the costs aren't parallel to the courses, or a group is too small or shares a course.
+#[wasm_bindgen(unchecked_return_type = "ScheduleResult")]
+pub fn js_best_schedule(
+    #[wasm_bindgen(unchecked_param_type = "Courses")] input: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "CourseCosts")] costs: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "ScheduleConstraints | undefined")] constraints: JsValue,
+) -> Result<JsValue, JsError> {
+    #[derive(Default, Deserialize)]
+    #[serde(default, rename_all = "camelCase")]
+    struct ScheduleConstraints {
+        groups: Vec<CourseGroup>,
+        blocked_slots: Vec<usize>,
+    }
+
+    #[derive(Serialize)]
+    struct BestSchedule {
+        cost: u64,
+        assignments: Vec<Assignment>,
+    }
+
+    #[derive(Serialize)]
+    struct ScheduleResult {
+        best: Option<BestSchedule>,
+        unschedulable: Vec<usize>,
+    }
+
+    let input: Vec<Vec<bool>> = serde_wasm_bindgen::from_value(input)?;
+    let costs: Vec<Vec<u64>> = serde_wasm_bindgen::from_value(costs)?;
+    let constraints: ScheduleConstraints = serde_wasm_bindgen::from_value::<Option<_>>(constraints)?.unwrap_or_default();
+    let slots = width(&input)?;
+    if costs.len() != input.len() || costs.iter().any(|course| course.len() != slots) {
+        return Err(JsError::new(&format!("the costs aren't {} courses of {slots} slots", input.len())));
+    }
+
+    let mut schedule = Schedule::from_availability(&input, Some(&costs));
+    for group in constraints.groups {
+        schedule.require_count(&group.courses, group.count)?;
+    }
+    let unschedulable = schedule.block_slots(&constraints.blocked_slots);
+
+    let best = schedule.solve_best().map(|(cost, assignments)| BestSchedule { cost, assignments });
+    Ok(ScheduleResult { best, unschedulable }.serialize(&Serializer::json_compatible())?)
 }
@@ -1,2 +1,6 @@
 pub mod courses;
 pub mod blocks;
+pub mod blocks3d;
+pub mod presets;
+pub mod sudoku;
+pub mod compose;
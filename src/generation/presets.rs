@@ -0,0 +1,133 @@
+//! Ready-made games of popular puzzles.
+
+use std::fmt;
+use crate::Node;
+use super::blocks::{Block2D, Game2D, TransformOp};
+
+/// The twelve Kanoodle pieces, named by letter.
+const KANOODLE_PIECES: [(&str, &str); 12] = [
+    // orange
+    ("A", "###\n#.."),
+    // red
+    ("B", "###\n##."),
+    // dark blue
+    ("C", "####\n#..."),
+    // pink
+    ("D", "####\n.#.."),
+    // green
+    ("E", "###.\n..##"),
+    // white
+    ("F", "##\n#."),
+    // light blue
+    ("G", "###\n#..\n#.."),
+    // purple
+    ("H", "##.\n.##\n..#"),
+    // yellow
+    ("I", "###\n#.#"),
+    // lavender
+    ("J", "####"),
+    // light green
+    ("K", "##\n##"),
+    // gray
+    ("L", ".#.\n###\n.#."),
+];
+
+/// The Kanoodle puzzle: twelve pieces filling an 11x5 board.
+pub fn kanoodle() -> Game2D {
+    let blocks = KANOODLE_PIECES.iter()
+        .map(|(name, shape)| Block2D::from_string(shape).named(name))
+        .collect();
+    Game2D::new(11, 5, blocks)
+}
+
+/// A Kanoodle challenge: the board with some pieces already placed, given by name, transformation and
+/// position. Panics on an unknown name or a piece not fitting on the board.
+pub fn kanoodle_challenge(preplaced: &[(&str, TransformOp, usize, usize)]) -> Game2D {
+    let mut game = kanoodle();
+    for &(name, op, x, y) in preplaced {
+        let block = game.blocks.iter()
+            .position(|block| block.name.as_deref() == Some(name))
+            .unwrap_or_else(|| panic!("no Kanoodle piece named {name}"));
+        game.fix(block, op, x, y);
+    }
+    game
+}
+
+/// Queens given for an N-Queens completion that can't all stand on the board.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QueensError {
+    /// A queen is outside the board.
+    OffBoard { queen: (usize, usize) },
+    /// Two queens attack each other.
+    Attack { queens: ((usize, usize), (usize, usize)) },
+}
+impl fmt::Display for QueensError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueensError::OffBoard { queen: (row, col) } => write!(f, "queen at ({row}, {col}) is off the board"),
+            QueensError::Attack { queens: ((r1, c1), (r2, c2)) } => write!(f, "queens at ({r1}, {c1}) and ({r2}, {c2}) attack each other"),
+        }
+    }
+}
+impl std::error::Error for QueensError {}
+
+/// The N-Queens puzzle: `n` queens on an `n` by `n` board, none attacking another.
+///
+/// Row `row * n + col` puts a queen on that square. The first `n` columns are the ranks, followed by
+/// `n` for the files, `2n - 1` for the diagonals and `2n - 1` for the anti-diagonals. Diagonals may stay
+/// empty, so each one also has a row of its own after the squares.
+pub fn n_queens(n: usize) -> Vec<Vec<bool>> {
+    let width = 6 * n - 2;
+    let mut matrix = Vec::new();
+    for row in 0..n {
+        for col in 0..n {
+            let mut entry = vec![false; width];
+            entry[row] = true;
+            entry[n + col] = true;
+            entry[2 * n + row + col] = true;
+            entry[4 * n - 1 + row + n - 1 - col] = true;
+            matrix.push(entry);
+        }
+    }
+    for diagonal in 2 * n..width {
+        let mut filler = vec![false; width];
+        filler[diagonal] = true;
+        matrix.push(filler);
+    }
+    matrix
+}
+
+/// Complete some queens given as `(row, col)` into a solution of the N-Queens puzzle, returning every
+/// queen of it sorted, or `None` if they can't be completed. Errors if the given queens attack each
+/// other, before any search.
+pub fn n_queens_complete(n: usize, placed: &[(usize, usize)]) -> Result<Option<Vec<(usize, usize)>>, QueensError> {
+    for (i, &(r1, c1)) in placed.iter().enumerate() {
+        if r1 >= n || c1 >= n { return Err(QueensError::OffBoard { queen: (r1, c1) }); }
+        for &(r2, c2) in &placed[..i] {
+            if r1 == r2 || c1 == c2 || r1 + c2 == r2 + c1 || r1 + c1 == r2 + c2 {
+                return Err(QueensError::Attack { queens: ((r2, c2), (r1, c1)) });
+            }
+        }
+    }
+    if n == 0 { return Ok(Some(Vec::new())); }
+
+    let matrix = n_queens(n);
+    let chosen: Vec<usize> = placed.iter().map(|&(row, col)| row * n + col).collect();
+    let reduced = Node::reduce(&matrix, &chosen).expect("queens not attacking each other share no column");
+
+    let rows = if reduced.columns.is_empty() {
+        Vec::new()
+    } else {
+        // a rank or file no square is left for can't be filled
+        if (0..reduced.columns.len()).any(|x| reduced.matrix.iter().all(|entry| !entry[x])) { return Ok(None); }
+        let Some(solution) = Node::solve_once(&reduced.matrix) else { return Ok(None) };
+        reduced.original_rows(&solution)
+    };
+
+    let mut queens: Vec<(usize, usize)> = chosen.into_iter().chain(rows)
+        .filter(|&row| row < n * n)
+        .map(|row| (row / n, row % n))
+        .collect();
+    queens.sort_unstable();
+    Ok(Some(queens))
+}
@@ -0,0 +1,124 @@
+//! Sudoku as an exact cover problem, and hints derived from it.
+
+use std::fmt;
+use crate::Node;
+use super::compose::{Matrix, hstack, identity_block};
+
+/// A 9x9 grid indexed as `grid[row][col]`, 0 being an empty cell.
+pub type Grid = [[u8; 9]; 9];
+
+/// Why a grid can't be worked on.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SudokuError {
+    /// A cell holds something other than 0 to 9.
+    InvalidDigit { row: usize, col: usize, digit: u8 },
+    /// The clues clash, or leave no way to fill the grid.
+    Contradiction,
+}
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SudokuError::InvalidDigit { row, col, digit } => write!(f, "cell ({row}, {col}) holds {digit}, not a digit"),
+            SudokuError::Contradiction => write!(f, "the grid has no solution"),
+        }
+    }
+}
+impl std::error::Error for SudokuError {}
+
+/// How a hint was found.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum HintKind {
+    /// The cell has a single candidate left.
+    NakedSingle,
+    /// The digit has a single place left in a row, column or box.
+    HiddenSingle,
+    /// Every other candidate of the cell leads to a contradiction, which takes a search to find.
+    Forced,
+}
+
+/// A digit the cell at `row` and `col` must hold.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Hint {
+    pub row: usize,
+    pub col: usize,
+    pub digit: u8,
+    pub kind: HintKind,
+}
+
+/// Get the row of the matrix putting a digit in a cell.
+pub fn matrix_row(row: usize, col: usize, digit: u8) -> usize {
+    row * 81 + col * 9 + digit as usize - 1
+}
+
+/// Create the matrix of an empty grid, with a row per digit in every cell. The first 81 columns are
+/// the cells, followed by 81 for every row and digit, 81 for every column and digit and 81 for every
+/// box and digit.
+pub fn get_matrix() -> Vec<Vec<bool>> {
+    let identity = identity_block(81);
+    let mut families: [Matrix; 4] = Default::default();
+    for row in 0..9 {
+        for col in 0..9 {
+            for digit in 0..9 {
+                families[0].push(identity[row * 9 + col].clone());
+                families[1].push(identity[row * 9 + digit].clone());
+                families[2].push(identity[col * 9 + digit].clone());
+                families[3].push(identity[(row / 3 * 3 + col / 3) * 9 + digit].clone());
+            }
+        }
+    }
+    hstack(&families).unwrap()
+}
+
+/// Find the empty cells whose digit follows from the clues, each as a hint. Errors if the grid has
+/// no solution. With `easiest`, only the hints found without any search are kept.
+pub fn hints(grid: &Grid, easiest: bool) -> Result<Vec<Hint>, SudokuError> {
+    let mut clues = Vec::new();
+    for (row, line) in grid.iter().enumerate() {
+        for (col, &digit) in line.iter().enumerate() {
+            match digit {
+                0 => {}
+                1..=9 => clues.push(matrix_row(row, col, digit)),
+                _ => return Err(SudokuError::InvalidDigit { row, col, digit }),
+            }
+        }
+    }
+
+    let reduced = Node::reduce(&get_matrix(), &clues).map_err(|_| SudokuError::Contradiction)?;
+    if reduced.columns.is_empty() { return Ok(Vec::new()); }
+    // a constraint without any candidate left can never be met
+    let width = reduced.columns.len();
+    if (0..width).any(|column| reduced.matrix.iter().all(|entry| !entry[column])) {
+        return Err(SudokuError::Contradiction);
+    }
+
+    // the candidates that are the only ones left for a constraint
+    let mut found: Vec<Option<HintKind>> = vec![None; reduced.rows.len()];
+    for column in 0..width {
+        let mut candidates = (0..reduced.matrix.len()).filter(|&row| reduced.matrix[row][column]);
+        if let (Some(row), None) = (candidates.next(), candidates.next()) {
+            let kind = if reduced.columns[column] < 81 { HintKind::NakedSingle } else { HintKind::HiddenSingle };
+            if found[row] != Some(HintKind::NakedSingle) { found[row] = Some(kind); }
+        }
+    }
+
+    // probe the candidates of one solution: one is forced if the grid can't be filled without it
+    let solution = Node::solve_once(&reduced.matrix).ok_or(SudokuError::Contradiction)?;
+    if !easiest {
+        for &row in &solution {
+            if found[row].is_some() { continue; }
+            let without: Vec<Vec<bool>> = reduced.matrix.iter().enumerate()
+                .filter(|&(other, _)| other != row)
+                .map(|(_, entry)| entry.clone())
+                .collect();
+            let coverable = (0..width).all(|column| without.iter().any(|entry| entry[column]));
+            if !coverable || Node::solve_once(&without).is_none() { found[row] = Some(HintKind::Forced); }
+        }
+    }
+
+    let mut hints: Vec<Hint> = found.iter().enumerate().filter_map(|(row, kind)| {
+        let original = reduced.rows[row];
+        kind.map(|kind| Hint { row: original / 81, col: original / 9 % 9, digit: (original % 9 + 1) as u8, kind })
+    }).collect();
+    hints.sort_by_key(|hint| (hint.row, hint.col));
+    Ok(hints)
+}
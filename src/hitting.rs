@@ -0,0 +1,30 @@
+//! Exact hitting sets, the problem dual to exact cover.
+
+use alloc::{vec, vec::Vec};
+use crate::Node;
+
+/// Swap the rows and columns of a matrix.
+pub(crate) fn transpose(input: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let width = input.first().map_or(0, Vec::len);
+    (0..width).map(|x| input.iter().map(|row| row[x]).collect()).collect()
+}
+
+/// Find every set of elements out of `universe` hitting each set exactly once, as exact covers of the
+/// sets by the elements.
+pub(crate) fn solve_hitting_set(sets: &[Vec<usize>], universe: usize) -> Vec<Vec<usize>> {
+    // nothing to hit, or an empty set to hit
+    if sets.is_empty() { return vec![Vec::new()]; }
+    if sets.iter().any(Vec::is_empty) { return Vec::new(); }
+
+    let mut matrix = vec![vec![false; sets.len()]; universe];
+    for (x, set) in sets.iter().enumerate() {
+        for &element in set {
+            assert!(element < universe, "element {element} of set {x} isn't in the universe of {universe}");
+            matrix[element][x] = true;
+        }
+    }
+
+    let mut solutions = Node::solve_all(&matrix);
+    for solution in &mut solutions { solution.sort_unstable(); }
+    solutions
+}
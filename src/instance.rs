@@ -0,0 +1,90 @@
+//! A plain text format for exact cover problems.
+//!
+//! A header line `p dlx <rows> <cols>` comes first, then a line for every row listing the columns it
+//! covers, counted from 1, separated by spaces, or `-` for a row covering none. Lines starting with
+//! `#` and blank lines are skipped anywhere.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Write};
+
+/// Why a text isn't a problem. Lines are counted from 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The text has no header.
+    MissingHeader,
+    /// The first line isn't a header `p dlx <rows> <cols>`.
+    BadHeader { line: usize },
+    /// Something on a row's line isn't a column.
+    BadColumn { line: usize },
+    /// A column of a row is 0 or past the last one.
+    ColumnOutOfRange { line: usize, column: usize },
+    /// The text doesn't have as many rows as its header says.
+    RowCount { expected: usize, got: usize },
+    /// A row has more columns than there is memory to hold.
+    TooWide { line: usize },
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingHeader => write!(f, "the text has no header"),
+            ParseError::BadHeader { line } => write!(f, "line {line}: expected a header `p dlx <rows> <cols>`"),
+            ParseError::BadColumn { line } => write!(f, "line {line}: expected column numbers"),
+            ParseError::ColumnOutOfRange { line, column } => write!(f, "line {line}: column {column} isn't in the matrix"),
+            ParseError::RowCount { expected, got } => write!(f, "the header says {expected} rows, but there are {got}"),
+            ParseError::TooWide { line } => write!(f, "line {line}: the row has too many columns to hold"),
+        }
+    }
+}
+impl core::error::Error for ParseError {}
+
+/// Read a problem written in the text format.
+pub(crate) fn parse_instance(text: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let mut lines = text.lines().enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (line, header) = lines.next().ok_or(ParseError::MissingHeader)?;
+    let fields: Vec<&str> = header.split_whitespace().collect();
+    let (rows, width) = match fields[..] {
+        ["p", "dlx", rows, cols] => rows.parse().ok().zip(cols.parse().ok()).ok_or(ParseError::BadHeader { line })?,
+        _ => return Err(ParseError::BadHeader { line }),
+    };
+
+    // the header isn't trusted with the memory taken, rows are only held once read
+    let mut matrix: Vec<Vec<bool>> = Vec::new();
+    while let Some((line, text)) = lines.next() {
+        if matrix.len() == rows { return Err(ParseError::RowCount { expected: rows, got: rows + 1 + lines.count() }); }
+
+        let mut row = Vec::new();
+        row.try_reserve_exact(width).map_err(|_| ParseError::TooWide { line })?;
+        row.resize(width, false);
+        if text != "-" {
+            for field in text.split_whitespace() {
+                let column: usize = field.parse().map_err(|_| ParseError::BadColumn { line })?;
+                if column == 0 || column > width { return Err(ParseError::ColumnOutOfRange { line, column }); }
+                row[column - 1] = true;
+            }
+        }
+        matrix.push(row);
+    }
+
+    if matrix.len() != rows { return Err(ParseError::RowCount { expected: rows, got: matrix.len() }); }
+    Ok(matrix)
+}
+
+/// Write a problem in the text format.
+pub(crate) fn write_instance(input: &[Vec<bool>]) -> String {
+    let width = input.first().map_or(0, Vec::len);
+    let mut text = String::new();
+    // writing to a string can't fail
+    let _ = writeln!(text, "p dlx {} {width}", input.len());
+    for row in input {
+        let mut columns = row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| x + 1).peekable();
+        if columns.peek().is_none() { text.push('-'); }
+        for (i, column) in columns.enumerate() {
+            let _ = write!(text, "{}{column}", if i == 0 { "" } else { " " });
+        }
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,128 @@
+//! Checking that the links of a structure are as the search leaves them, to catch changes breaking
+//! them where they happen instead of by wrong solutions later.
+
+use core::fmt;
+use crate::Structure;
+
+/// How the links of a structure are broken. Nodes are counted by their index in the structure, the
+/// root first, then the header of every column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvariantError {
+    /// A link of a node points outside the structure, or to a node not linking back to it.
+    BrokenLink { node: usize },
+    /// The column of a node isn't a header, or a node linked to the root isn't one.
+    NotAHeader { node: usize },
+    /// A node is in the ring of a column it doesn't belong to.
+    WrongColumn { node: usize },
+    /// The size of a column isn't the number of nodes in it.
+    WrongSize { header: usize, size: usize, counted: usize },
+    /// A node isn't of the same row as the node left of it.
+    RowMismatch { node: usize },
+}
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvariantError::BrokenLink { node } => write!(f, "node {node} has a broken link"),
+            InvariantError::NotAHeader { node } => write!(f, "node {node} is used as a header but isn't one"),
+            InvariantError::WrongColumn { node } => write!(f, "node {node} is in the ring of another column"),
+            InvariantError::WrongSize { header, size, counted } => write!(f, "column {header} has size {size} but {counted} nodes"),
+            InvariantError::RowMismatch { node } => write!(f, "node {node} isn't of the same row as the node left of it"),
+        }
+    }
+}
+impl core::error::Error for InvariantError {}
+
+impl Structure {
+    /// Check the rings still linked: those of the root and of every column, and the row links of every
+    /// node in a column, which holds for a structure being searched as well as a new one. Nodes taken
+    /// out of their column by covering are only checked through the nodes next to them.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        let len = self.nodes.len();
+        let is_header = |node: usize| node != Structure::ROOT && node < len && self.nodes[node].c as usize == node;
+
+        self.check_ring(Structure::ROOT, |node| self.nodes[node].r, |node| self.nodes[node].l, |node| {
+            if is_header(node) { Ok(()) } else { Err(InvariantError::NotAHeader { node }) }
+        })?;
+
+        for header in (1..len).filter(|&node| is_header(node)) {
+            let mut counted = 0;
+            self.check_ring(header, |node| self.nodes[node].d, |node| self.nodes[node].u, |node| {
+                if self.nodes[node].c as usize != header { return Err(InvariantError::WrongColumn { node }); }
+                counted += 1;
+
+                // the rest of the row is checked from its other columns, or is out of them
+                let (left, right) = (self.nodes[node].l as usize, self.nodes[node].r as usize);
+                if left >= len || right >= len || self.nodes[left].r as usize != node || self.nodes[right].l as usize != node {
+                    return Err(InvariantError::BrokenLink { node });
+                }
+                if !is_header(self.nodes[right].c as usize) { return Err(InvariantError::NotAHeader { node: right }); }
+                if self.nodes[right].data != self.nodes[node].data { return Err(InvariantError::RowMismatch { node: right }); }
+                Ok(())
+            })?;
+
+            let size = self.nodes[header].data;
+            if size != counted { return Err(InvariantError::WrongSize { header, size, counted }); }
+        }
+        Ok(())
+    }
+
+    /// Walk a ring from `start`, checking that every link stays in the structure and is mirrored by the
+    /// link the other way, and checking every other node of it, without going around more than once.
+    fn check_ring(
+        &self,
+        start: usize,
+        next: impl Fn(usize) -> u32,
+        back: impl Fn(usize) -> u32,
+        mut check: impl FnMut(usize) -> Result<(), InvariantError>,
+    ) -> Result<(), InvariantError> {
+        let mut node = start;
+        for _ in 0..self.nodes.len() {
+            let following = next(node) as usize;
+            if following >= self.nodes.len() || back(following) as usize != node { return Err(InvariantError::BrokenLink { node }); }
+            if following == start { return Ok(()); }
+            check(following)?;
+            node = following;
+        }
+        // longer than the structure, so it never comes back to the start
+        Err(InvariantError::BrokenLink { node: start })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec};
+    use super::InvariantError;
+    use crate::{Node, Structure};
+
+    /// The structure of an odd cycle: the root, the headers of columns 0 to 2, then rows 0 to 2 with
+    /// two nodes each, on columns `01`, `12` and `02`.
+    fn cycle() -> Structure {
+        Node::build(&[vec![true, true, false], vec![false, true, true], vec![true, false, true]])
+    }
+
+    #[test]
+    fn corrupted_links_are_found() {
+        assert_eq!(cycle().check_invariants(), Ok(()));
+
+        let mut structure = cycle();
+        structure.nodes[4].d = 99;
+        assert_eq!(structure.check_invariants(), Err(InvariantError::BrokenLink { node: 4 }));
+
+        let mut structure = cycle();
+        structure.nodes[4].c = 2;
+        assert_eq!(structure.check_invariants(), Err(InvariantError::WrongColumn { node: 4 }));
+
+        let mut structure = cycle();
+        structure.nodes[5].c = 4;
+        assert_eq!(structure.check_invariants(), Err(InvariantError::NotAHeader { node: 5 }));
+
+        let mut structure = cycle();
+        structure.nodes[1].data = 5;
+        assert_eq!(structure.check_invariants(), Err(InvariantError::WrongSize { header: 1, size: 5, counted: 2 }));
+        assert_eq!(structure.check_invariants().unwrap_err().to_string(), "column 1 has size 5 but 2 nodes");
+
+        let mut structure = cycle();
+        structure.nodes[5].data = 2;
+        assert_eq!(structure.check_invariants(), Err(InvariantError::RowMismatch { node: 5 }));
+    }
+}
@@ -0,0 +1,116 @@
+//! Solutions found one at a time, by a search that can be saved and picked up again later.
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use crate::{Node, Structure};
+
+/// Where a `SolutionIter` is in its search, free of any memory address so it can be saved.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Deserialize, Serialize))]
+pub struct Checkpoint {
+    /// The column branched on at every level from the root, and how far down it the row tried is among
+    /// the rows left in it, leading to the last solution found.
+    pub path: Vec<(usize, usize)>,
+    /// Whether a solution was asked for yet. Once the search is over, the path is empty.
+    pub started: bool,
+}
+
+/// An iterator over the solutions of a problem, in the order `Node::solve_all` finds them. It owns its
+/// structure, searching a bit further on every call, and `checkpoint` tells where it is so that
+/// `resume` can go on from there, for instance after a restart.
+pub struct SolutionIter {
+    structure: Structure,
+    /// The header of the column branched on at every level, and the row of it tried.
+    path: Vec<(usize, usize)>,
+    started: bool,
+}
+impl SolutionIter {
+    /// Start on the solutions of a matrix. Panics if the matrix has no rows or columns, or rows of
+    /// different widths.
+    pub fn new(input: &[Vec<bool>]) -> SolutionIter {
+        SolutionIter { structure: Node::build(input), path: Vec::new(), started: false }
+    }
+
+    /// Tell where the search is.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let path = self.path.iter().map(|&(col, row)| {
+            let ordinal = self.structure.walk(col, Structure::down).position(|node| node == row).unwrap();
+            (col - 1, ordinal)
+        }).collect();
+        Checkpoint { path, started: self.started }
+    }
+
+    /// Draw the structure as the search left it, in the DOT format like `Structure::to_dot`, the columns
+    /// covered by the rows on the path being gone.
+    pub fn to_dot(&self) -> alloc::string::String {
+        self.structure.to_dot()
+    }
+
+    /// Go on from a checkpoint of a search over the same matrix, building its structure again and
+    /// choosing the rows on the path. Panics if the matrix is invalid, or the path isn't one its search
+    /// takes.
+    pub fn resume(input: &[Vec<bool>], checkpoint: &Checkpoint) -> SolutionIter {
+        let mut iter = SolutionIter::new(input);
+        iter.started = checkpoint.started;
+        for &(col, ordinal) in &checkpoint.path {
+            let header = col + 1;
+            assert_eq!(iter.choose_column(), Some(header), "the checkpoint isn't from a search of this matrix");
+            let row = iter.structure.walk(header, Structure::down).nth(ordinal)
+                .expect("the checkpoint isn't from a search of this matrix");
+            iter.structure.cover(header);
+            iter.structure.cover_row(row);
+            iter.path.push((header, row));
+        }
+        iter
+    }
+
+    /// Get the column left with the fewest rows, the leftmost on ties, or `None` if one has none.
+    fn choose_column(&self) -> Option<usize> {
+        let col = self.structure.walk(Structure::ROOT, Structure::right).min_by_key(|&col| self.structure.nodes[col].data)?;
+        (self.structure.nodes[col].data > 0).then_some(col)
+    }
+
+    /// Choose the first row of the column to branch on until every column is covered, telling if it
+    /// is, or a column has no rows left.
+    fn descend(&mut self) -> bool {
+        while !self.structure.is_solved() {
+            let Some(col) = self.choose_column() else { return false };
+            let row = self.structure.down(col);
+            self.structure.cover(col);
+            self.structure.cover_row(row);
+            self.path.push((col, row));
+        }
+        true
+    }
+
+    /// Get the rows on the path.
+    fn solution(&self) -> Vec<usize> {
+        self.path.iter().map(|&(_, row)| self.structure.nodes[row].data).collect()
+    }
+}
+impl Iterator for SolutionIter {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if !self.started {
+            self.started = true;
+            if self.descend() { return Some(self.solution()); }
+        }
+
+        // move on from the last solution or dead end to the next row, going up when a column runs out
+        while let Some((col, row)) = self.path.pop() {
+            self.structure.uncover_row(row);
+            let next = self.structure.down(row);
+            if next == col {
+                self.structure.uncover(col);
+                continue;
+            }
+
+            self.structure.cover_row(next);
+            self.path.push((col, next));
+            if self.descend() { return Some(self.solution()); }
+        }
+        None
+    }
+}
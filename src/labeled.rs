@@ -0,0 +1,49 @@
+//! Matrices whose rows carry labels, solutions coming back as the labels of their rows.
+
+use alloc::vec::Vec;
+use crate::Node;
+
+/// A matrix built row by row, every row with a label of the caller's, so solutions are given as the
+/// labels of their rows rather than as indices.
+#[derive(Clone, Debug)]
+pub struct LabeledMatrix<T> {
+    labels: Vec<T>,
+    rows: Vec<Vec<bool>>,
+}
+impl<T> LabeledMatrix<T> {
+    /// Create a matrix without rows.
+    pub fn new() -> LabeledMatrix<T> {
+        LabeledMatrix { labels: Vec::new(), rows: Vec::new() }
+    }
+
+    /// Add a row with its label.
+    pub fn add_row(&mut self, label: T, cols: Vec<bool>) {
+        self.labels.push(label);
+        self.rows.push(cols);
+    }
+
+    /// The labels of the rows, in the order they were added.
+    pub fn labels(&self) -> &[T] { &self.labels }
+
+    /// The rows, in the order they were added.
+    pub fn rows(&self) -> &[Vec<bool>] { &self.rows }
+
+    /// Find all solutions, as the labels of their rows in the order `Node::solve_all` gives them.
+    /// Panics if there are no rows or columns, or rows of different widths.
+    pub fn solve_all(&self) -> Vec<Vec<&T>> {
+        Node::solve_all(&self.rows).iter().map(|solution| self.label(solution)).collect()
+    }
+
+    /// Find one solution, as the labels of its rows. Panics like `solve_all`.
+    pub fn solve_once(&self) -> Option<Vec<&T>> {
+        Node::solve_once(&self.rows).map(|solution| self.label(&solution))
+    }
+
+    /// Get the labels of some rows.
+    fn label(&self, rows: &[usize]) -> Vec<&T> {
+        rows.iter().map(|&row| &self.labels[row]).collect()
+    }
+}
+impl<T> Default for LabeledMatrix<T> {
+    fn default() -> LabeledMatrix<T> { LabeledMatrix::new() }
+}
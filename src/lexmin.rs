@@ -0,0 +1,100 @@
+//! The solution whose rows, ascending, come first in lexicographic order.
+
+use alloc::vec::Vec;
+use crate::{Node, Structure};
+
+/// A step of the search, undone when backtracking.
+#[derive(Clone, Copy)]
+enum Decision {
+    /// The only row of a column was chosen.
+    Forced(usize),
+    /// The smallest row left was chosen.
+    Chosen(usize),
+    /// The smallest row left was left out, after no solution had it.
+    Hidden(usize),
+}
+
+/// A search deciding the rows left in ascending order, choosing each before leaving it out, so the
+/// first solution found is the lexicographically smallest.
+struct LexMin<'a> {
+    structure: &'a mut Structure,
+    /// The first node of every chosen row.
+    chosen: Vec<usize>,
+}
+impl LexMin<'_> {
+    /// Find the smallest solution with the rows chosen, telling if there is one, in which case the
+    /// structure is left with its rows chosen. Otherwise, it is as it was. The decisions taken on the
+    /// way are kept in a stack rather than the call stack, so any depth is fine.
+    fn search(&mut self) -> bool {
+        let mut decisions = Vec::new();
+        loop {
+            if self.structure.is_solved() { return true; }
+            if let Some(decision) = self.decide() {
+                let (Decision::Forced(row) | Decision::Chosen(row) | Decision::Hidden(row)) = decision;
+                self.choose(row);
+                decisions.push(decision);
+                continue;
+            }
+
+            // back up to the last row chosen that can be left out instead
+            loop {
+                match decisions.pop() {
+                    None => return false,
+                    Some(Decision::Forced(row)) => self.unchoose(row),
+                    Some(Decision::Chosen(row)) => {
+                        // the solutions with the smallest row left come before those without it
+                        self.unchoose(row);
+                        self.structure.hide_row(row);
+                        decisions.push(Decision::Hidden(row));
+                        break;
+                    }
+                    Some(Decision::Hidden(row)) => self.structure.unhide_row(row),
+                }
+            }
+        }
+    }
+
+    /// Get the row to choose next, or `None` if a column has no rows left.
+    fn decide(&self) -> Option<Decision> {
+        // the rows of a column are in ascending order, so its first one is its smallest
+        let mut smallest: Option<usize> = None;
+        let mut forced = None;
+        for col in self.structure.walk(Structure::ROOT, Structure::right) {
+            match self.structure.nodes[col].data {
+                0 => return None,
+                1 => forced = forced.or(Some(self.structure.down(col))),
+                _ => {}
+            }
+            let first = self.structure.down(col);
+            if smallest.is_none_or(|row| self.structure.nodes[first].data < self.structure.nodes[row].data) { smallest = Some(first); }
+        }
+
+        // every solution left has the only row of a column, so choosing it changes none of them
+        forced.map(Decision::Forced).or(smallest.map(Decision::Chosen))
+    }
+
+    /// Choose a row, covering its columns.
+    fn choose(&mut self, row: usize) {
+        self.structure.cover(self.structure.column(row));
+        self.structure.cover_row(row);
+        self.chosen.push(row);
+    }
+
+    /// Undo choosing a row.
+    fn unchoose(&mut self, row: usize) {
+        self.chosen.pop();
+        self.structure.uncover_row(row);
+        self.structure.uncover(self.structure.column(row));
+    }
+}
+
+/// Find the solution whose rows, ascending, are lexicographically smallest.
+pub(crate) fn solve_lex_min(input: &[Vec<bool>]) -> Option<Vec<usize>> {
+    let mut structure = Node::build(input);
+    let mut search = LexMin { structure: &mut structure, chosen: Vec::new() };
+    if !search.search() { return None; }
+
+    let mut rows: Vec<usize> = search.chosen.iter().map(|&row| search.structure.nodes[row].data).collect();
+    rows.sort_unstable();
+    Some(rows)
+}
@@ -1,380 +1,1238 @@
 //! DLX library to solve exact cover problems and generate nodes.
-
-mod generation;
-
-use std::cell::RefCell;
-use std::rc::{Rc, Weak};
-use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! Without the default `std` feature the solver only needs `alloc`. The generators, the JavaScript
+//! bindings, deadlines and progress callbacks, and the `async`, `parallel`, `arbitrary` and `napi`
+//! features need `std`. The `log` feature, which doesn't, logs how long searches go through the `log`
+//! crate: new depths reached, solutions found and columns branched on, the last two only now and then.
+//!
+//! The crate is only built as a Rust library, so crates without `std` can depend on it. The wasm
+//! package and the Node.js addon are built as C libraries by passing `--crate-type cdylib` to
+//! `cargo rustc`, as `examples/napi` does.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "napi")]
+pub mod addon;
+mod bitset;
+mod bounded;
+mod components;
+mod cover;
+pub mod debugging;
+mod detail;
+mod dimacs;
+mod dot;
+mod duplicates;
+mod error;
+#[cfg(feature = "async")]
+mod future;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+#[cfg(feature = "std")]
+pub mod generation;
+mod hitting;
+mod instance;
+mod invariants;
+mod iter;
+mod labeled;
+mod lexmin;
+mod matrix;
+mod memo;
+mod observer;
+#[cfg(feature = "std")]
+mod output;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod partition;
+mod prepared;
+mod presolve;
+mod problem;
+mod reduce;
+mod search;
+mod solutions;
+mod solver;
+mod stepper;
+mod symmetry;
+mod unsat;
+mod verify;
+mod xcc;
+mod zdd;
+
+use alloc::{vec, vec::Vec};
+use core::ops::ControlFlow;
+use hashbrown::HashSet;
+#[cfg(feature = "std")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "std")]
 use serde_wasm_bindgen::Serializer;
+#[cfg(feature = "std")]
 use serde::Serialize;
 
-static NODE_COUNT: AtomicUsize = AtomicUsize::new(0);
-
-type RcNode = Rc<RefCell<Node>>;
-type WeakNode = Weak<RefCell<Node>>;
-
-/// The type of nodes used by the solver.
-#[derive(Clone)]
+#[cfg(feature = "async")]
+pub use future::SolveFuture;
+pub use detail::SolutionDetail;
+pub use error::DlxError;
+pub use instance::ParseError;
+pub use invariants::InvariantError;
+pub use iter::{Checkpoint, SolutionIter};
+pub use labeled::LabeledMatrix;
+#[cfg(feature = "std")]
+pub use output::SolutionFormat;
+pub use matrix::{ColumnRef, LinkedMatrix, RowRef};
+pub use observer::SearchObserver;
+pub use partition::PartitionError;
+pub use prepared::Dlx;
+pub use presolve::Presolved;
+pub use problem::ExactCoverProblem;
+pub use reduce::{ConflictError, Reduced};
+pub use search::{Backend, ColumnStrategy, Progress, SolveOptions, SolveOutcome, SolveStats};
+#[cfg(feature = "std")]
+pub use search::ProgressCallback;
+pub use solutions::{DiversityMetric, RowSet, SolutionSet, SolutionStorage};
+pub use solver::{RowId, Solver};
+pub use stepper::{StepEvent, Stepper};
+pub use unsat::UnsatReason;
+pub use verify::VerifyError;
+pub use xcc::XccRow;
+pub use zdd::{Zdd, ZddCostIter, ZddIter};
+
+use bitset::BitsetSearch;
+use search::Search;
+
+/// The type of nodes used by the solver, linked to the nodes around them by their index in the
+/// structure holding them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Node {
-    u: WeakNode,
-    d: WeakNode,
-    l: WeakNode,
-    r: WeakNode,
-    c: WeakNode,
+    u: u32,
+    d: u32,
+    l: u32,
+    r: u32,
+    c: u32,
+    /// The color given to a secondary column, 0 for none, or `SETTLED` once the column was given the
+    /// same one by another row. For a header, the color the column was given.
+    color: u32,
+    /// The size of the column for a header, the row for any other node.
     data: usize,
-    id: usize,
 }
-impl Node {
-    /// Create a new node with data.
-    pub fn new(data: usize) -> RcNode {
-        Rc::new_cyclic(|n| RefCell::new(Node {
-            u: n.clone(),
-            d: n.clone(),
-            l: n.clone(),
-            r: n.clone(),
-            c: n.clone(),
-            data,
-            id: NODE_COUNT.fetch_add(1, Ordering::Relaxed),
-        }))
+
+/// Color of the nodes agreeing with the color their column was given, leaving nothing to do when
+/// choosing their row.
+const SETTLED: u32 = u32::MAX;
+
+/// A structure of nodes built from a matrix, all held in one vector: the root, then the column
+/// headers, then the other nodes row by row. Cloning it makes an independent copy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Structure {
+    nodes: Vec<Node>,
+}
+impl Structure {
+    /// Index of the root.
+    const ROOT: usize = 0;
+
+    /// Add a node with data linked to itself, returning its index.
+    fn push(&mut self, data: usize) -> usize {
+        let index = self.nodes.len();
+        let link = u32::try_from(index).expect("more nodes than links can index");
+        self.nodes.push(Node { u: link, d: link, l: link, r: link, c: link, color: 0, data });
+        index
     }
 
-    /// Unlink a node horizontally by node.L.R ← node.R, node.R.L ← node.L
-    fn unlink_lr(node: &RcNode) {
-        let l = weak2rc(&node.borrow().l);
-        let r = weak2rc(&node.borrow().r);
+    fn left(&self, node: usize) -> usize { self.nodes[node].l as usize }
+    fn right(&self, node: usize) -> usize { self.nodes[node].r as usize }
+    fn up(&self, node: usize) -> usize { self.nodes[node].u as usize }
+    fn down(&self, node: usize) -> usize { self.nodes[node].d as usize }
+    fn column(&self, node: usize) -> usize { self.nodes[node].c as usize }
 
-        l.borrow_mut().r = Rc::downgrade(&r);
-        r.borrow_mut().l = Rc::downgrade(&l);
+    /// Check if every primary column is covered.
+    fn is_solved(&self) -> bool {
+        self.right(Self::ROOT) == Self::ROOT
     }
 
-    /// Unlink a node vertically by node.U.D ← node.D, node.D.U ← node.U
-    fn unlink_ud(node: &RcNode) {
-        let u = weak2rc(&node.borrow().u);
-        let d = weak2rc(&node.borrow().d);
+    /// Unlink a node horizontally by node.L.R ← node.R, node.R.L ← node.L
+    fn unlink_lr(&mut self, node: usize) {
+        let Node { l, r, .. } = self.nodes[node];
+        self.nodes[l as usize].r = r;
+        self.nodes[r as usize].l = l;
+    }
 
-        u.borrow_mut().d = Rc::downgrade(&d);
-        d.borrow_mut().u = Rc::downgrade(&u);
+    /// Unlink a node vertically by node.U.D ← node.D, node.D.U ← node.U
+    fn unlink_ud(&mut self, node: usize) {
+        let Node { u, d, .. } = self.nodes[node];
+        self.nodes[u as usize].d = d;
+        self.nodes[d as usize].u = u;
     }
 
     /// Relink a node horizontally by node.L.R ← node, node.R.L ← node
-    fn link_lr(node: &RcNode) {
-        let l = weak2rc(&node.borrow().l);
-        let r = weak2rc(&node.borrow().r);
-
-        l.borrow_mut().r = Rc::downgrade(&node);
-        r.borrow_mut().l = Rc::downgrade(&node);
+    fn link_lr(&mut self, node: usize) {
+        let Node { l, r, .. } = self.nodes[node];
+        self.nodes[l as usize].r = node as u32;
+        self.nodes[r as usize].l = node as u32;
     }
 
     /// Relink a node vertically by node.U.D ← node, node.D.U ← node
-    fn link_ud(node: &RcNode) {
-        let u = weak2rc(&node.borrow().u);
-        let d = weak2rc(&node.borrow().d);
+    fn link_ud(&mut self, node: usize) {
+        let Node { u, d, .. } = self.nodes[node];
+        self.nodes[u as usize].d = node as u32;
+        self.nodes[d as usize].u = node as u32;
+    }
 
-        u.borrow_mut().d = Rc::downgrade(&node);
-        d.borrow_mut().u = Rc::downgrade(&node);
+    /// Cover a column.
+    fn cover(&mut self, header: usize) {
+        self.cover_with(header, &mut |_, _| ());
     }
 
-    /// Build a structure of nodes from a bool matrix, returning the root node.
-    pub fn build(input: &Vec<Vec<bool>>) -> (RcNode, Vec<RcNode>) {
-        let width = input[0].len();
+    /// Cover a column, calling `on_resize` with the index and header of every other column whose size
+    /// changes.
+    fn cover_with(&mut self, header: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        // get rid of it from col headers
+        self.unlink_lr(header);
 
-        let root = Node::new(0);
-        let headers: Vec<RcNode> = (0..width).map(|_| Node::new(0)).collect();
-        let mut all_nodes = headers.clone();
+        // loop through all nodes in the column
+        let mut row = self.down(header);
+        while row != header {
+            self.hide_with(row, on_resize);
+            row = self.down(row);
+        }
+    }
 
-        root.borrow_mut().r = Rc::downgrade(&headers[0]);
-        headers[0].borrow_mut().l = Rc::downgrade(&root);
+    /// Undo the covering operation from a column.
+    fn uncover(&mut self, header: usize) {
+        self.uncover_with(header, &mut |_, _| ());
+    }
 
-        root.borrow_mut().l = Rc::downgrade(&headers[width - 1]);
-        headers[width - 1].borrow_mut().r = Rc::downgrade(&root);
+    /// Undo the covering operation from a column, calling `on_resize` with the index and header of
+    /// every other column whose size changes.
+    fn uncover_with(&mut self, header: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        // put it back into column headers
+        self.link_lr(header);
 
-        for i in 0..width {
-            if i != 0 { headers[i].borrow_mut().l = Rc::downgrade(&headers[i - 1]); }
-            if i != width - 1 { headers[i].borrow_mut().r = Rc::downgrade(&headers[i + 1]); }
+        // loop through all nodes in the column, in reverse
+        let mut row = self.up(header);
+        while row != header {
+            self.unhide_with(row, on_resize);
+            row = self.up(row);
         }
+    }
 
-        for (y, row) in input.iter().enumerate() {
-            let mut row_nodes = Vec::with_capacity(width);
+    /// Take the other nodes of a row out of their columns, calling `on_resize` for every column.
+    /// Nodes agreeing with the color of their column stay, as nothing looks at it until it is reset.
+    fn hide_with(&mut self, row: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        let mut node = self.right(row);
+        while node != row {
+            if self.nodes[node].color != SETTLED {
+                // remove it from its column, decrement size
+                self.unlink_ud(node);
+                let column = self.column(node);
+                self.nodes[column].data -= 1;
+                on_resize(column, &self.nodes[column]);
+            }
+            node = self.right(node);
+        }
+    }
 
-            for (x, val) in row.iter().enumerate() {
-                if *val {
-                    let node = Node::new(y);
-                    node.borrow_mut().c = Rc::downgrade(&headers[x]);
-                    node.borrow_mut().d = Rc::downgrade(&headers[x]);
-                    node.borrow_mut().u = headers[x].borrow().u.clone();
+    /// Put the other nodes of a row back into their columns, in reverse order.
+    fn unhide_with(&mut self, row: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        let mut node = self.left(row);
+        while node != row {
+            if self.nodes[node].color != SETTLED {
+                self.link_ud(node);
+                let column = self.column(node);
+                self.nodes[column].data += 1;
+                on_resize(column, &self.nodes[column]);
+            }
+            node = self.left(node);
+        }
+    }
 
-                    { weak2rc(&headers[x].borrow_mut().u) }.borrow_mut().d = Rc::downgrade(&node);
-                    headers[x].borrow_mut().u = Rc::downgrade(&node);
-                    headers[x].borrow_mut().data += 1;
+    /// Take a row, reached through one of its nodes, out of every column.
+    fn hide_row(&mut self, row: usize) {
+        self.unlink_ud(row);
+        let col = self.column(row);
+        self.nodes[col].data -= 1;
+        self.hide_with(row, &mut |_, _| ());
+    }
 
-                    all_nodes.push(node.clone());
-                    row_nodes.push(node);
-                }
-            }
+    /// Put a row taken out of every column back in.
+    fn unhide_row(&mut self, row: usize) {
+        self.unhide_with(row, &mut |_, _| ());
+        let col = self.column(row);
+        self.nodes[col].data += 1;
+        self.link_ud(row);
+    }
 
-            let len = row_nodes.len();
-            if len != 0 {
-                row_nodes[0].borrow_mut().l = Rc::downgrade(&row_nodes[len - 1]);
-                row_nodes[len - 1].borrow_mut().r = Rc::downgrade(&row_nodes[0]);
-                for i in 0..len {
-                    if i != 0 { row_nodes[i].borrow_mut().l = Rc::downgrade(&row_nodes[i - 1]); }
-                    if i != len - 1 { row_nodes[i].borrow_mut().r = Rc::downgrade(&row_nodes[i + 1]); }
-                }
-            }
+    /// Take rows out of every column by their first nodes in `starts`, once each however often they are
+    /// listed.
+    fn forbid(&mut self, starts: &[Option<usize>], rows: &[usize]) {
+        let mut rows = rows.to_vec();
+        rows.sort_unstable();
+        rows.dedup();
+        for node in rows.iter().filter_map(|&row| starts[row]) {
+            self.hide_row(node);
         }
+    }
 
-        for header in headers {
-            if header.borrow().data == 0 { Self::unlink_lr(&header); }
+    /// Take the rows the options forbid out of a structure of `width` columns and `rows` rows, then
+    /// choose the rows they require, covering their columns.
+    fn choose_rows(&mut self, width: usize, rows: usize, options: &SolveOptions) {
+        let starts = self.row_starts(width, rows);
+        self.forbid(&starts, &options.forbid_rows);
+        for node in options.require_rows.iter().filter_map(|&row| starts[row]) {
+            self.cover(self.column(node));
+            self.cover_row(node);
         }
+    }
+
+    /// Give the column of a colored node the node's color, hiding the rows giving it another one and
+    /// settling those agreeing with it.
+    fn purify_with(&mut self, node: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        let (header, color) = (self.column(node), self.nodes[node].color);
+        self.nodes[header].color = color;
 
-        (root, all_nodes)
+        let mut other = self.down(header);
+        while other != header {
+            if other != node {
+                if self.nodes[other].color == color { self.nodes[other].color = SETTLED; } else { self.hide_with(other, on_resize); }
+            }
+            other = self.down(other);
+        }
     }
 
-    /// Cover a column.
-    #[allow(unused_braces)]
-    fn cover(header: &RcNode) {
-        // get rid of it from col headers
-        Self::unlink_lr(header);
+    /// Undo giving the column of a colored node its color.
+    fn unpurify_with(&mut self, node: usize, on_resize: &mut impl FnMut(usize, &Node)) {
+        let header = self.column(node);
+        let color = core::mem::take(&mut self.nodes[header].color);
 
-        let start_col_id = header.borrow().id;
-        let mut current_col = weak2rc(&header.borrow().d);
-        let mut current_col_id = current_col.borrow().id;
+        let mut other = self.up(header);
+        while other != header {
+            if other != node {
+                if self.nodes[other].color == SETTLED { self.nodes[other].color = color; } else { self.unhide_with(other, on_resize); }
+            }
+            other = self.up(other);
+        }
+    }
 
-        // loop through all nodes in the column
-        while current_col_id != start_col_id {
-            let start_node_id = current_col.borrow().id;
-            let mut current_node = weak2rc(&current_col.borrow().r);
-            let mut current_node_id = current_node.borrow().id;
+    /// Cover the column of a node of a chosen row, or give it the node's color if it has one.
+    fn commit(&mut self, node: usize) {
+        match self.nodes[node].color {
+            0 => self.cover(self.column(node)),
+            SETTLED => {}
+            _ => self.purify_with(node, &mut |_, _| ()),
+        }
+    }
 
-            // loop through all nodes in this row
-            while current_node_id != start_node_id {
-                // remove it from its column, decrement size
-                Self::unlink_ud(&current_node);
-                weak2rc(&current_node.borrow().c).borrow_mut().data -= 1;
+    /// Undo committing a node of a chosen row.
+    fn uncommit(&mut self, node: usize) {
+        match self.nodes[node].color {
+            0 => self.uncover(self.column(node)),
+            SETTLED => {}
+            _ => self.unpurify_with(node, &mut |_, _| ()),
+        }
+    }
 
-                // next node in this row
-                current_node = { weak2rc(&current_node.borrow().r) };
-                current_node_id = current_node.borrow().id;
-            }
+    /// Commit every other node of a row, as done when choosing it.
+    fn cover_row(&mut self, row: usize) {
+        let mut node = self.right(row);
+        while node != row {
+            self.commit(node);
+            node = self.right(node);
+        }
+    }
 
-            // next node in the column
-            current_col = { weak2rc(&current_col.borrow().d) };
-            current_col_id = current_col.borrow().id;
+    /// Undo committing the other nodes of a row, in reverse order.
+    fn uncover_row(&mut self, row: usize) {
+        let mut node = self.left(row);
+        while node != row {
+            self.uncommit(node);
+            node = self.left(node);
         }
     }
 
-    #[allow(unused_braces)]
-    /// Undo the covering operation from a column.
-    fn uncover(header: &RcNode) {
-        // put it back into column headers
-        Self::link_lr(header);
+    /// Build a structure of `width` columns, the ones from `primary` on secondary, from the columns
+    /// and colors of the entries of every row, `entries` of them in all.
+    fn from_rows<R: IntoIterator<Item = (usize, u32)>>(width: usize, primary: usize, entries: usize, rows: impl IntoIterator<Item = R>) -> Structure {
+        let mut structure = Structure { nodes: Vec::with_capacity(1 + width + entries) };
+        structure.push(0);
+        for _ in 0..width { structure.push(0); }
+
+        // secondary headers stay linked to themselves
+        for node in 0..=primary {
+            let next = (node + 1) % (primary + 1);
+            structure.nodes[node].r = next as u32;
+            structure.nodes[next].l = node as u32;
+        }
+
+        for (y, row) in rows.into_iter().enumerate() {
+            structure.append_row(y, row);
+        }
 
-        let start_col_id = header.borrow().id;
-        let mut current_col = weak2rc(&header.borrow().u);
-        let mut current_col_id = current_col.borrow().id;
+        structure
+    }
 
-        // loop through all nodes in the column
-        while current_col_id != start_col_id {
-            let start_node_id = current_col.borrow().id;
-            let mut current_node = weak2rc(&current_col.borrow().l);
-            let mut current_node_id = current_node.borrow().id;
-
-            // loop through all nodes in this row
-            while current_node_id != start_node_id {
-                Self::link_ud(&current_node);
-                weak2rc(&current_node.borrow().c).borrow_mut().data += 1;
-
-                // next node in this row
-                current_node = { weak2rc(&current_node.borrow().l) };
-                current_node_id = current_node.borrow().id;
+    /// Add row `y` at the bottom of the columns of its entries, given by their columns and colors,
+    /// returning its first node, or `None` if it has no entries.
+    fn append_row(&mut self, y: usize, row: impl IntoIterator<Item = (usize, u32)>) -> Option<usize> {
+        let mut first = None;
+
+        for (x, color) in row {
+            let header = x + 1;
+            let node = self.push(y);
+            self.nodes[node].color = color;
+
+            // at the bottom of its column
+            let up = self.up(header);
+            self.nodes[node].c = header as u32;
+            self.nodes[node].d = header as u32;
+            self.nodes[node].u = up as u32;
+            self.nodes[up].d = node as u32;
+            self.nodes[header].u = node as u32;
+            self.nodes[header].data += 1;
+
+            // at the end of its row
+            if let Some(first) = first {
+                let last = self.left(first);
+                self.nodes[node].l = last as u32;
+                self.nodes[node].r = first as u32;
+                self.nodes[last].r = node as u32;
+                self.nodes[first].l = node as u32;
+            } else {
+                first = Some(node);
             }
-
-            // next node in the column
-            current_col = { weak2rc(&current_col.borrow().u) };
-            current_col_id = current_col.borrow().id;
         }
+        first
     }
 
-    /// Search all solutions from the root node using the DLX algorithm.
-    #[allow(unused_braces)]
-    fn search_all(root: &RcNode, all_nodes: &Vec<RcNode>, solution: &mut Vec<usize>, partial_results: &mut Vec<Vec<usize>>) {
-        let root_id = root.borrow_mut().id;
-        if { weak2rc(&root.borrow().r) }.borrow().id == root_id {
-            partial_results.push(solution.clone());
-            return;
+    /// Get the first node of each of `rows` rows of a structure of `width` columns, `None` for a row
+    /// without entries.
+    fn row_starts(&self, width: usize, rows: usize) -> Vec<Option<usize>> {
+        let mut starts = vec![None; rows];
+        for (index, node) in self.nodes.iter().enumerate().skip(1 + width) {
+            starts[node.data].get_or_insert(index);
         }
+        starts
+    }
 
-        let mut current_node = weak2rc(&root.borrow().r);
-        let mut current_node_id = current_node.borrow().id;
-        let mut best_col = None;
-        let mut min_size = usize::MAX;
-
-        // find the column with the smallest amount of ones
-        while current_node_id != root_id {
-            let current_size = current_node.borrow().data;
-            if current_size < min_size {
-                min_size = current_size;
-                best_col = Some(Rc::downgrade(&current_node));
-            }
+    /// Walk a ring of nodes in one direction, starting after `start` and stopping before it again.
+    fn walk(&self, start: usize, next: fn(&Structure, usize) -> usize) -> impl Iterator<Item = usize> + '_ {
+        let mut current = next(self, start);
+        core::iter::from_fn(move || {
+            if current == start { return None; }
+            let node = current;
+            current = next(self, node);
+            Some(node)
+        })
+    }
+}
 
-            current_node = { weak2rc(&current_node.borrow().r) };
-            current_node_id = current_node.borrow().id;
+impl Node {
+    /// Check that a matrix has rows and columns, all rows being as wide as the first, returning its width.
+    pub(crate) fn check_input(input: &[Vec<bool>]) -> Result<usize, DlxError> {
+        let width = input.first().ok_or(DlxError::EmptyMatrix)?.len();
+        if width == 0 { return Err(DlxError::NoColumns); }
+        match input.iter().position(|row| row.len() != width) {
+            Some(row) => Err(DlxError::RaggedRow { row, expected: width, got: input[row].len() }),
+            None => Ok(width),
         }
+    }
 
-        let best_col = best_col.unwrap().upgrade().unwrap();
+    /// Check that the rows the options require or forbid are in the matrix, that none is both, and that
+    /// no two required rows cover the same column.
+    pub(crate) fn check_options(input: &[Vec<bool>], options: &SolveOptions) -> Result<(), DlxError> {
+        let rows = options.require_rows.iter().chain(&options.forbid_rows);
+        if let Some(&row) = rows.clone().find(|&&row| row >= input.len()) { return Err(DlxError::RowOutOfRange { row }); }
+        if let Some(&row) = options.require_rows.iter().find(|row| options.forbid_rows.contains(row)) {
+            return Err(DlxError::RequiredAndForbidden { row });
+        }
 
-        Self::cover(&best_col);
+        reduce::covered_by(input, &options.require_rows)
+            .map_err(|error| DlxError::RequiredConflict { column: error.column, rows: error.rows })?;
+        symmetry::row_maps(input, &options.symmetries).map(|_| ())
+    }
 
-        // loop through all rows that have a one in this column
-        let start_row_id = best_col.borrow().id;
-        let mut current_row = weak2rc(&best_col.borrow().d);
-        let mut current_row_id = current_row.borrow().id;
-        while current_row_id != start_row_id {
-            solution.push(current_row.borrow().data);
+    /// Build a structure of nodes from a bool matrix. A column without rows stays in the ring of the
+    /// root, so a search finds no solution to cover it. Panics if the matrix has no rows or columns,
+    /// or rows of different widths.
+    pub fn build(input: &[Vec<bool>]) -> Structure {
+        Self::build_with_secondary(input, usize::MAX)
+    }
 
-            // loop through all columns intersecting with this row
-            let start_node_id = current_row.borrow().id;
-            let mut current_node = weak2rc(&current_row.borrow().r);
-            let mut current_node_id = current_node.borrow().id;
-            while current_node_id != start_node_id {
-                // cover it
-                Self::cover(&weak2rc(&current_node.borrow().c));
+    /// Build a structure of nodes from a bool matrix like `build`, or tell what is wrong with the matrix.
+    pub fn try_build(input: &[Vec<bool>]) -> Result<Structure, DlxError> {
+        Self::check_input(input)?;
+        Ok(Self::build(input))
+    }
 
-                // next intersecting column
-                current_node = { weak2rc(&current_node.borrow().r) };
-                current_node_id = current_node.borrow().id;
-            }
+    /// Build a structure of nodes from a bool matrix whose columns from `primary` on are secondary.
+    /// Secondary columns are left out of the ring of the root, so a search never branches on them nor
+    /// needs them covered, but rows sharing one still exclude each other.
+    pub fn build_with_secondary(input: &[Vec<bool>], primary: usize) -> Structure {
+        let width = Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let primary = primary.min(width);
+
+        let entries = input.iter().flatten().filter(|val| **val).count();
+        let rows = input.iter().map(|row| row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| (x, 0)));
+        Structure::from_rows(width, primary, entries, rows)
+    }
 
-            Self::search_all(root, &all_nodes, solution, partial_results);
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices.
+    /// Solutions are collected into a flat buffer during the search and only split up at the end.
+    ///
+    /// Solutions come in the order of a depth first search: it branches on the column with the fewest
+    /// rows, the leftmost one on ties, and tries its rows top to bottom, the rows of every solution
+    /// being in the order they were chosen. The same matrix always gives the same order. Other options
+    /// change it, and `SolveOptions::canonical_order` sorts solutions independently of the search.
+    pub fn solve_all(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+        Self::solve_all_set(input, SolutionStorage::Indices).into_indices()
+    }
 
-            // backtracking: loop through all columns intersecting with this row
-            let start_node_id = current_row.borrow().id;
-            let mut current_node = weak2rc(&current_row.borrow().l);
-            let mut current_node_id = current_node.borrow().id;
-            while current_node_id != start_node_id {
-                // cover it
-                Self::uncover(&weak2rc(&current_node.borrow().c));
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices,
+    /// telling every step of the search to an observer: the columns branched on, the rows tried, the
+    /// columns covered and uncovered, and the solutions found. The dancing links are always searched,
+    /// branching on the leftmost column with the fewest rows and trying its rows top to bottom.
+    pub fn solve_all_observed(input: &[Vec<bool>], observer: &mut impl SearchObserver) -> Vec<Vec<usize>> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let options = SolveOptions::default();
+        let mut structure = Self::build(input);
+        let mut search = Search::observed(&mut structure, &options, Self::max_depth(input), observer);
+        let mut solutions = Vec::new();
+        let _ = search.run(&mut |solution| {
+            solutions.push(solution.to_vec());
+            ControlFlow::Continue(())
+        });
+        solutions
+    }
 
-                // next intersecting column
-                current_node = { weak2rc(&current_node.borrow().l) };
-                current_node_id = current_node.borrow().id;
+    /// Solve the exact cover problem from a starting Node, finding all solutions like `solve_all` into
+    /// `out`, which is cleared first. Its solutions are overwritten in place, keeping their capacity, so
+    /// solving many matrices into the same buffer allocates only when a solution is longer than the one
+    /// it replaces, or there are more solutions than before. Solutions past the last one found are
+    /// dropped.
+    pub fn solve_all_into(input: &[Vec<bool>], out: &mut Vec<Vec<usize>>) {
+        let mut found = 0;
+        let _ = Self::search(input, &SolveOptions::default(), &mut |solution| {
+            match out.get_mut(found) {
+                Some(kept) => {
+                    kept.clear();
+                    kept.extend_from_slice(solution);
+                }
+                None => out.push(solution.to_vec()),
             }
+            found += 1;
+            ControlFlow::Continue(())
+        });
+        out.truncate(found);
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices, or
+    /// tell what is wrong with the matrix.
+    pub fn try_solve_all(input: &[Vec<bool>]) -> Result<Vec<Vec<usize>>, DlxError> {
+        Self::check_input(input)?;
+        Ok(Self::solve_all(input))
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices along
+    /// with statistics on how hard the search worked.
+    pub fn solve_all_with_stats(input: &[Vec<bool>]) -> (Vec<Vec<usize>>, SolveStats) {
+        Self::solve_all_with_options(input, &SolveOptions::default())
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions as bitsets over the rows.
+    pub fn solve_all_bitsets(input: &[Vec<bool>]) -> Vec<RowSet> {
+        Self::solve_all_set(input, SolutionStorage::Bitsets).into_bitsets()
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions as packed words over
+    /// the rows, `input.len().div_ceil(64)` of them each, bit `i % 64` of word `i / 64` being set when
+    /// row `i` is chosen. The solutions are those of `solve_all`, in the same order.
+    pub fn solve_all_bits(input: &[Vec<bool>]) -> Vec<Vec<u64>> {
+        Self::solve_all_bitsets(input).into_iter().map(RowSet::into_words).collect()
+    }
+
+    /// Pack the rows of a solution of a matrix with `num_rows` rows into words as `solve_all_bits` does.
+    /// Panics if a row is out of range.
+    pub fn rows_to_bits(rows: &[usize], num_rows: usize) -> Vec<u64> {
+        RowSet::from_indices(rows, num_rows).into_words()
+    }
+
+    /// Get the rows set in words packed as `solve_all_bits` does, in ascending order.
+    pub fn bits_to_rows(bits: &[u64]) -> Vec<usize> {
+        RowSet::from_words(bits.to_vec(), bits.len() * 64).to_indices()
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions in the given storage.
+    pub fn solve_all_set(input: &[Vec<bool>], storage: SolutionStorage) -> SolutionSet {
+        Self::solve_all_set_with_options(input, storage, &SolveOptions::default()).0
+    }
 
-            solution.pop();
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices
+    /// along with statistics on the search.
+    pub fn solve_all_with_options(input: &[Vec<bool>], options: &SolveOptions) -> (Vec<Vec<usize>>, SolveStats) {
+        let (results, stats) = Self::solve_all_set_with_options(input, SolutionStorage::Indices, options);
+        (results.into_indices(), stats)
+    }
 
-            // next row that has a one in the column
-            current_row = { weak2rc(&current_row.borrow().d) };
-            current_row_id = current_row.borrow().id;
+    /// Solve the exact cover problem from a starting Node, finding all solutions along with the row
+    /// covering every column, so they can be read without looking through the matrix again.
+    pub fn solve_all_detailed(input: &[Vec<bool>]) -> Vec<SolutionDetail> {
+        Self::solve_all_detailed_with_options(input, &SolveOptions::default()).0
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions along with the row
+    /// covering every column, and statistics on the search. Secondary columns no row covers have none.
+    pub fn solve_all_detailed_with_options(input: &[Vec<bool>], options: &SolveOptions) -> (Vec<SolutionDetail>, SolveStats) {
+        let (solutions, stats) = Self::solve_all_with_options(input, options);
+        (solutions.iter().map(|rows| detail::detail(input, rows)).collect(), stats)
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices
+    /// along with statistics on the search, or tell what is wrong with the matrix or the rows the
+    /// options require or forbid.
+    pub fn try_solve_all_with_options(input: &[Vec<bool>], options: &SolveOptions) -> Result<(Vec<Vec<usize>>, SolveStats), DlxError> {
+        Self::check_input(input)?;
+        Self::check_options(input, options)?;
+        Ok(Self::solve_all_with_options(input, options))
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions in the given storage
+    /// along with statistics on the search.
+    pub fn solve_all_set_with_options(input: &[Vec<bool>], storage: SolutionStorage, options: &SolveOptions) -> (SolutionSet, SolveStats) {
+        if options.dedup_rows {
+            let (matrix, originals) = Self::dedup_rows(input);
+            let (solutions, stats) = Self::solve_all_set_with_options(&matrix, storage, &SolveOptions { dedup_rows: false, ..options.clone() });
+            let mut results = SolutionSet::new(input.len(), storage);
+            for i in 0..solutions.len() { results.push(&duplicates::first_copies(&solutions.indices(i), &originals)); }
+            return (results, stats);
         }
 
-        Self::uncover(&best_col);
+        let mut results = SolutionSet::new(input.len(), storage);
+        let (_, stats) = Self::search(input, options, &mut |solution| {
+            results.push(solution);
+            ControlFlow::Continue(())
+        });
+
+        if options.canonical_order { results.sort(); }
+        (results, stats)
     }
 
-    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices.
-    pub fn solve_all(input: &Vec<Vec<bool>>) -> Vec<Vec<usize>> {
-        let (root, all_nodes) = Self::build(input);
-        let mut results = Vec::new();
-        Self::search_all(&root, &all_nodes, &mut Vec::new(), &mut results);
-        results
+    /// Count the solutions of the exact cover problem without collecting them, saturating at `u64::MAX`.
+    /// Only a counter is kept at every solution, the partial solution is never copied.
+    pub fn count_solutions(input: &[Vec<bool>]) -> u64 {
+        Self::count_solutions_with_options(input, &SolveOptions::default()).0
+    }
+
+    /// Count the solutions of the exact cover problem without collecting them, saturating at `u64::MAX`,
+    /// along with statistics on the search. A search stopped early counts the solutions found until
+    /// then, which `count_solutions_outcome` tells apart from a full count.
+    pub fn count_solutions_with_options(input: &[Vec<bool>], options: &SolveOptions) -> (u64, SolveStats) {
+        let (outcome, stats) = Self::count_solutions_outcome(input, options);
+        (outcome.into_inner(), stats)
+    }
+
+    /// Count the solutions of the exact cover problem without collecting them, saturating at `u64::MAX`,
+    /// along with statistics on the search, telling whether it was stopped before the end, for instance
+    /// by `node_limit`, `cancel` or `deadline`.
+    pub fn count_solutions_outcome(input: &[Vec<bool>], options: &SolveOptions) -> (SolveOutcome<u64>, SolveStats) {
+        Self::check_options(input, options).unwrap_or_else(|error| panic!("invalid options: {error}"));
+        let (mut structure, merged) = Self::prepare(input, options);
+        let mut search = Search::new(&mut structure, options, 0);
+        search.given = options.require_rows.len();
+        search.stats.merged_columns = merged;
+        let count = search.count();
+
+        let outcome = match search.interrupted {
+            false => SolveOutcome::Complete(count),
+            true => SolveOutcome::Interrupted(count),
+        };
+        (outcome, search.stats)
+    }
+
+    /// Find rows sharing no column that cover as many columns as possible, and how many they cover. When
+    /// there is no exact cover, this tells how close one can get. Rows are tried for each column, then
+    /// leaving it uncovered, and a branch is cut once covering all its columns couldn't do better.
+    /// Panics if the matrix has no rows or columns, or rows of different widths.
+    pub fn solve_max_cover(input: &[Vec<bool>]) -> (Vec<usize>, usize) {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let mut structure = Self::build(input);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, Self::max_depth(input));
+        let mut best = (Vec::new(), 0);
+        search.max_cover(0, input[0].len(), &mut best);
+        best
     }
 
-    /// Search one solution from the root node using the DLX algorithm.
-    #[allow(unused_braces)]
-    fn search_once(root: &RcNode, all_nodes: &Vec<RcNode>, solution: &mut Vec<usize>) -> Option<Vec<usize>> {
-        let root_id = root.borrow_mut().id;
-        if { weak2rc(&root.borrow().r) }.borrow().id == root_id { return Some(solution.clone()) }
+    /// Solve the exact cover problem from a starting Node, finding a solution with the fewest rows, or
+    /// `None` if there is none. Of the shortest solutions, this is the first `solve_all` finds. A branch
+    /// is cut once it can't beat the shortest solution found so far, even covering the columns left
+    /// with rows as wide as the widest one.
+    pub fn solve_fewest_rows(input: &[Vec<bool>]) -> Option<Vec<usize>> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let widest = input.iter().map(|row| row.iter().filter(|&&val| val).count()).max().unwrap_or(0);
+        // every column needs a row
+        if widest == 0 { return None; }
+
+        let mut structure = Self::build(input);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, Self::max_depth(input));
+        let mut best = None;
+        search.fewest_rows(input[0].len(), widest, &mut best);
+        best
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding the solution whose rows, ascending,
+    /// come first in lexicographic order, or `None` if there is none. Rows are decided from the smallest
+    /// one left up, each chosen before being left out, so the first solution found is the smallest
+    /// without going through the others, the only row left in a column being chosen right away.
+    pub fn solve_lex_min(input: &[Vec<bool>]) -> Option<Vec<usize>> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        lexmin::solve_lex_min(input)
+    }
+
+    /// Estimate how many nodes the search tree of `solve_all` has, without searching it all, by walking
+    /// down it along `probes` random paths. The estimate is unbiased, so it gets closer with more probes,
+    /// though lopsided trees can need many. The same seed always gives the same estimate.
+    pub fn estimate_tree_size(input: &[Vec<bool>], probes: usize, seed: u64) -> f64 {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let mut structure = Self::build(input);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, Self::max_depth(input));
+        search.estimate(probes, seed)
+    }
+
+    /// Solve the exact cover problem from a starting Node, building a diagram of all solutions.
+    pub fn solve_to_zdd(input: &[Vec<bool>]) -> Zdd {
+        let options = SolveOptions { memo_capacity: Some(usize::MAX), ..SolveOptions::default() };
+        Self::solve_to_zdd_with_options(input, &options).0
+    }
 
-        let mut current_node = weak2rc(&root.borrow().r);
-        let mut current_node_id = current_node.borrow().id;
-        let mut best_col = None;
-        let mut min_size = usize::MAX;
+    /// Solve the exact cover problem from a starting Node, building a diagram of all solutions along with
+    /// statistics on the search. Subproblems are only shared when `memo_capacity` is set. A search
+    /// stopped early gives the diagram of the solutions found until then, which
+    /// `solve_to_zdd_outcome` tells apart from a full one.
+    pub fn solve_to_zdd_with_options(input: &[Vec<bool>], options: &SolveOptions) -> (Zdd, SolveStats) {
+        let (outcome, stats) = Self::solve_to_zdd_outcome(input, options);
+        (outcome.into_inner(), stats)
+    }
+
+    /// Solve the exact cover problem from a starting Node, building a diagram of all solutions along with
+    /// statistics on the search, telling whether it was stopped before the end.
+    pub fn solve_to_zdd_outcome(input: &[Vec<bool>], options: &SolveOptions) -> (SolveOutcome<Zdd>, SolveStats) {
+        let (mut structure, merged) = Self::build_for(input, options);
+        let mut search = Search::new(&mut structure, options, 0);
+        search.stats.merged_columns = merged;
+        let mut zdd = Zdd::new();
+        let node = search.zdd(&mut zdd);
+        zdd.set_root(node);
+
+        let outcome = match search.interrupted {
+            false => SolveOutcome::Complete(zdd),
+            true => SolveOutcome::Interrupted(zdd),
+        };
+        (outcome, search.stats)
+    }
+
+    /// For every row, count the solutions containing it. A row in every solution is forced, and a row in
+    /// none of them is useless. Counted on the diagram of all solutions, so it stays cheap even when
+    /// there are far too many solutions to list.
+    pub fn row_participation(input: &[Vec<bool>]) -> Vec<u64> {
+        Self::solve_to_zdd(input).row_participation(input.len())
+    }
+
+    /// Solve the exact cover problem from a starting Node, lazily finding all solutions by nondecreasing
+    /// total cost, `costs[row]` being the cost of a row. Each solution comes with its cost.
+    pub fn solve_ordered_by_cost(input: &[Vec<bool>], costs: &[u64]) -> ZddCostIter {
+        assert_eq!(costs.len(), input.len(), "expected one cost per row");
+        Self::solve_to_zdd(input).into_iter_by_cost(costs)
+    }
+
+    /// Commit to some rows, returning the residual problem: the columns they leave uncovered and the rows
+    /// sharing no column with them, with maps back to the original numbering. Errors with the clashing
+    /// column if two chosen rows overlap.
+    pub fn reduce(input: &[Vec<bool>], chosen: &[usize]) -> Result<Reduced, ConflictError> {
+        reduce::reduce(input, chosen)
+    }
+
+    /// Solve the exact cover problem from a starting Node with some rows chosen up front, finding all
+    /// solutions that include them, the given rows first. Their columns are covered before the search
+    /// starts, so with no givens this is `solve_all`. Errors with the clashing column if two given rows
+    /// overlap. Panics if a given row is out of range.
+    pub fn solve_all_with_givens(input: &[Vec<bool>], givens: &[usize]) -> Result<Vec<Vec<usize>>, ConflictError> {
+        Solver::new(input).solve_with_givens(givens)
+    }
+
+    /// Find a minimal set of columns that can't be covered exactly once together, pinpointing why a problem
+    /// has no solution. Columns are dropped one by one as long as the rest stays infeasible, each check
+    /// being a search of at most `node_budget` nodes. Returns `None` if the problem has a solution, or
+    /// its infeasibility couldn't be shown within the budget.
+    pub fn unsat_core(input: &[Vec<bool>], node_budget: Option<u64>) -> Option<Vec<usize>> {
+        unsat::unsat_core(input, node_budget)
+    }
 
-        // find the column with the smallest amount of ones
-        while current_node_id != root_id {
-            let current_size = current_node.borrow().data;
-            if current_size < min_size {
-                min_size = current_size;
-                best_col = Some(Rc::downgrade(&current_node));
+    /// Explain why a problem has no solution, or give `None` if it has one. The reasons tried first are
+    /// a column no row covers, then a column left without rows by choosing the only row of every column
+    /// with one, over and over. Otherwise the whole search tree is searched. Panics if the matrix has no
+    /// rows or columns, or rows of different widths.
+    pub fn explain_unsat(input: &[Vec<bool>]) -> Option<UnsatReason> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        unsat::explain_unsat(input)
+    }
+
+    /// Find the fewest columns, at most `max_drop` of them, that would make a problem feasible if they
+    /// could be left uncovered. Sets of columns are tried by increasing size, each check being a search
+    /// of at most `node_budget` nodes. An empty set means the problem is already feasible, and `None`
+    /// that no set could be shown to work.
+    pub fn suggest_relaxations(input: &[Vec<bool>], max_drop: usize, node_budget: Option<u64>) -> Option<Vec<usize>> {
+        let candidates: Vec<usize> = (0..input.first().map_or(0, Vec::len)).collect();
+        unsat::relaxations(input, &candidates, max_drop, node_budget)
+    }
+
+    /// Solve the exact cover problem from a starting Node, handing every solution to `f` as it is found
+    /// instead of collecting them, until `f` breaks. Returns whether it broke.
+    pub fn solve_with<F: FnMut(&[usize]) -> ControlFlow<()>>(input: &[Vec<bool>], f: F) -> ControlFlow<()> {
+        Self::solve_with_options(input, &SolveOptions::default(), f).0
+    }
+
+    /// Solve the exact cover problem from a starting Node, handing every solution to `f` as it is found
+    /// until `f` breaks, along with statistics on the search.
+    pub fn solve_with_options<F: FnMut(&[usize]) -> ControlFlow<()>>(input: &[Vec<bool>], options: &SolveOptions, mut f: F) -> (ControlFlow<()>, SolveStats) {
+        let mut broke = false;
+        let (_, stats) = Self::search(input, options, &mut |solution| {
+            let flow = f(solution);
+            broke = flow.is_break();
+            flow
+        });
+
+        let flow = if broke { ControlFlow::Break(()) } else { ControlFlow::Continue(()) };
+        (flow, stats)
+    }
+
+    /// Solve the exact cover problem from a starting Node, writing every solution to `w` as it is found in
+    /// the order of `solve_all`, one per line, returning how many were written. The writer is flushed
+    /// after every solution, so stopping the process midway leaves the solutions found until then, and
+    /// the first error writing stops the search and is returned.
+    #[cfg(feature = "std")]
+    pub fn solve_all_to_writer<W: std::io::Write>(input: &[Vec<bool>], w: &mut W, format: SolutionFormat) -> std::io::Result<u64> {
+        output::solve_all_to_writer(input, w, format)
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding the first `limit` solutions returning
+    /// indices, in the order `solve_all` finds them.
+    pub fn solve_n(input: &[Vec<bool>], limit: usize) -> Vec<Vec<usize>> {
+        Self::solve_n_outcome(input, limit, &SolveOptions::default()).0.into_solutions()
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding the first `limit` solutions returning
+    /// indices along with statistics on the search. The outcome is complete when `limit` solutions were
+    /// found or there are no more, and interrupted when the search was stopped before that, for instance
+    /// by `node_limit`.
+    pub fn solve_n_outcome(input: &[Vec<bool>], limit: usize, options: &SolveOptions) -> (SolveOutcome, SolveStats) {
+        if limit == 0 { return (SolveOutcome::Complete(Vec::new()), SolveStats::default()); }
+
+        let mut results = Vec::new();
+        let (interrupted, stats) = Self::search(input, options, &mut |solution| {
+            results.push(solution.to_vec());
+            if results.len() == limit { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+
+        let outcome = match interrupted {
+            false => SolveOutcome::Complete(results),
+            true => SolveOutcome::Interrupted(results),
+        };
+        (outcome, stats)
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices, but
+    /// only the first solution of every key `canonicalize` maps them to, such as solutions turned into
+    /// each other by a symmetry of the problem. Only the keys seen are kept, not the solutions left out.
+    pub fn solve_all_dedup(input: &[Vec<bool>], canonicalize: impl Fn(&[usize]) -> Vec<usize>) -> Vec<Vec<usize>> {
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        let _ = Self::solve_with(input, |solution| {
+            if seen.insert(canonicalize(solution)) { results.push(solution.to_vec()); }
+            ControlFlow::Continue(())
+        });
+        results
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding up to `k` solutions that each differ from
+    /// every other one in at least `min_hamming` rows (the size of the symmetric difference of their row
+    /// sets). Solutions are kept greedily in search order, so fewer than `k` may come back even when a
+    /// different pick would have allowed more.
+    pub fn solve_diverse(input: &[Vec<bool>], k: usize, min_hamming: usize) -> Vec<Vec<usize>> {
+        if k == 0 { return Vec::new(); }
+
+        let mut structure = Self::build(input);
+        let options = SolveOptions::default();
+        let mut search = Search::new(&mut structure, &options, Self::max_depth(input));
+        let mut kept: Vec<(Vec<usize>, RowSet)> = Vec::new();
+
+        let _ = search.run(&mut |solution| {
+            let bits = RowSet::from_indices(solution, input.len());
+            if kept.iter().all(|(_, other)| bits.symmetric_difference(other).len() >= min_hamming) {
+                kept.push((solution.to_vec(), bits));
             }
+            if kept.len() == k { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+        kept.into_iter().map(|(solution, _)| solution).collect()
+    }
 
-            current_node = { weak2rc(&current_node.borrow().r) };
-            current_node_id = current_node.borrow().id;
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices
+    /// along with statistics on the search, telling whether it was stopped before the end.
+    pub fn solve_all_outcome(input: &[Vec<bool>], options: &SolveOptions) -> (SolveOutcome, SolveStats) {
+        if options.dedup_rows {
+            let (matrix, originals) = Self::dedup_rows(input);
+            let (outcome, stats) = Self::solve_all_outcome(&matrix, &SolveOptions { dedup_rows: false, ..options.clone() });
+            let complete = outcome.is_complete();
+            let solutions = outcome.into_solutions().iter().map(|solution| duplicates::first_copies(solution, &originals)).collect();
+            return (if complete { SolveOutcome::Complete(solutions) } else { SolveOutcome::Interrupted(solutions) }, stats);
         }
 
-        let best_col = best_col.unwrap().upgrade().unwrap();
+        let mut results = SolutionSet::new(input.len(), SolutionStorage::Indices);
+        let (interrupted, stats) = Self::search(input, options, &mut |solution| {
+            results.push(solution);
+            ControlFlow::Continue(())
+        });
+
+        if options.canonical_order { results.sort(); }
+        let outcome = match interrupted {
+            false => SolveOutcome::Complete(results.into_indices()),
+            true => SolveOutcome::Interrupted(results.into_indices()),
+        };
+        (outcome, stats)
+    }
 
-        Self::cover(&best_col);
+    /// Solve the exact cover problem on all cores, finding all solutions returning indices in the same
+    /// order as `solve_all`. The rows of the first column branched on are shared out between threads,
+    /// each searching on a structure of its own.
+    #[cfg(feature = "parallel")]
+    pub fn par_solve_all(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+        parallel::par_solve_all(input)
+    }
 
-        // loop through all rows that have a one in this column
-        let start_row_id = best_col.borrow().id;
-        let mut current_row = weak2rc(&best_col.borrow().d);
-        let mut current_row_id = current_row.borrow().id;
-        while current_row_id != start_row_id {
-            solution.push(current_row.borrow().data);
+    /// Solve the exact cover problem on another thread, finding all solutions returning indices.
+    /// The future works with any executor, and dropping it cancels the search through the `cancel`
+    /// flag of the options, creating one if there is none. The matrix and options are checked before
+    /// the thread starts, panicking if they are invalid; a panic of the search itself is raised again
+    /// when the future is polled.
+    #[cfg(feature = "async")]
+    pub fn solve_all_async(input: Vec<Vec<bool>>, options: SolveOptions) -> SolveFuture {
+        Self::check_input(&input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        Self::check_options(&input, &options).unwrap_or_else(|error| panic!("invalid options: {error}"));
+        SolveFuture::spawn(input, options)
+    }
 
-            // loop through all columns intersecting with this row
-            let start_node_id = current_row.borrow().id;
-            let mut current_node = weak2rc(&current_row.borrow().r);
-            let mut current_node_id = current_node.borrow().id;
-            while current_node_id != start_node_id {
-                // cover it
-                Self::cover(&weak2rc(&current_node.borrow().c));
+    /// Solve the exact cover problem on another thread like `solve_all_async`, or tell what is wrong
+    /// with the matrix or the rows the options require or forbid, without starting the thread.
+    #[cfg(feature = "async")]
+    pub fn try_solve_all_async(input: Vec<Vec<bool>>, options: SolveOptions) -> Result<SolveFuture, DlxError> {
+        Self::check_input(&input)?;
+        Self::check_options(&input, &options)?;
+        Ok(SolveFuture::spawn(input, options))
+    }
 
-                // next intersecting column
-                current_node = { weak2rc(&current_node.borrow().r) };
-                current_node_id = current_node.borrow().id;
+    /// Search the solutions of a matrix on the backend of the options, handing each one to `on_solution`
+    /// until it breaks, telling whether the search was stopped early.
+    fn search(input: &[Vec<bool>], options: &SolveOptions, on_solution: &mut impl FnMut(&[usize]) -> ControlFlow<()>) -> (bool, SolveStats) {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        Self::check_options(input, options).unwrap_or_else(|error| panic!("invalid options: {error}"));
+        let primary = options.primary_columns.unwrap_or(usize::MAX);
+        let required = &options.require_rows;
+        let mut full = Vec::new();
+        // checked with the options
+        let row_maps = symmetry::row_maps(input, &options.symmetries).unwrap_or_default();
+        let mut orbits = HashSet::new();
+        let mut capped_orbits = 0;
+        let on_solution = &mut |solution: &[usize]| {
+            let solution = if required.is_empty() { solution } else {
+                full.clear();
+                full.extend(required.iter().chain(solution));
+                &full
+            };
+            debug_assert_eq!(verify::verify(input, solution, primary), Ok(()), "the search found a wrong solution");
+            if !row_maps.is_empty() {
+                let (canonical, whole) = symmetry::canonical(solution, &row_maps);
+                if !whole { capped_orbits += 1; }
+                if !orbits.insert(canonical) { return ControlFlow::Continue(()); }
             }
+            on_solution(solution)
+        };
+
+        if bitset::applies(input, options) {
+            let mut search = BitsetSearch::new(input, options);
+            let _ = search.run(search.required(), on_solution);
+            search.stats.capped_orbits = capped_orbits;
+            return (search.interrupted, search.stats);
+        }
 
-            if let Some(solution) = Self::search_once(root, &all_nodes, solution) { return Some(solution); }
+        let (mut structure, merged) = Self::prepare(input, options);
+        debug_assert_eq!(structure.check_invariants(), Ok(()), "the structure was built with broken links");
+        let mut search = Search::new(&mut structure, options, Self::max_depth(input));
+        search.given = required.len();
+        search.stats.merged_columns = merged;
+        let _ = search.run(on_solution);
+        search.stats.capped_orbits = capped_orbits;
+        let (interrupted, stats) = (search.interrupted, search.stats);
+        debug_assert_eq!(structure.check_invariants(), Ok(()), "the search broke the links of the structure");
+        (interrupted, stats)
+    }
 
-            // backtracking: loop through all columns intersecting with this row
-            let start_node_id = current_row.borrow().id;
-            let mut current_node = weak2rc(&current_row.borrow().l);
-            let mut current_node_id = current_node.borrow().id;
-            while current_node_id != start_node_id {
-                // cover it
-                Self::uncover(&weak2rc(&current_node.borrow().c));
+    /// Build the structure of a matrix with the secondary columns of the options, without the rows they
+    /// forbid, and with the rows they require chosen, along with the columns merged.
+    fn prepare(input: &[Vec<bool>], options: &SolveOptions) -> (Structure, Vec<(usize, usize)>) {
+        let (mut structure, merged) = Self::build_for(input, options);
+        structure.choose_rows(input[0].len() - merged.len(), input.len(), options);
+        (structure, merged)
+    }
 
-                // next intersecting column
-                current_node = { weak2rc(&current_node.borrow().l) };
-                current_node_id = current_node.borrow().id;
-            }
+    /// Build the structure of a matrix with the secondary columns of the options, without the columns
+    /// merged into earlier copies of them if asked to, along with those columns and their copies.
+    fn build_for(input: &[Vec<bool>], options: &SolveOptions) -> (Structure, Vec<(usize, usize)>) {
+        let primary = options.primary_columns.unwrap_or(usize::MAX);
+        if !options.merge_duplicate_columns { return (Self::build_with_secondary(input, primary), Vec::new()); }
+
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        let merged = duplicates::duplicate_columns(input, primary);
+        let kept: Vec<usize> = (0..input[0].len()).filter(|&x| merged.iter().all(|&(col, _)| col != x)).collect();
+        let matrix: Vec<Vec<bool>> = input.iter().map(|row| kept.iter().map(|&x| row[x]).collect()).collect();
+        let primary = kept.iter().filter(|&&x| x < primary).count();
+        (Self::build_with_secondary(&matrix, primary), merged)
+    }
 
-            solution.pop();
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices, the
+    /// columns from `primary_count` on being secondary: covered at most once instead of exactly once.
+    pub fn solve_all_with_secondary(input: &[Vec<bool>], primary_count: usize) -> Vec<Vec<usize>> {
+        let options = SolveOptions { primary_columns: Some(primary_count), ..SolveOptions::default() };
+        Self::solve_all_with_options(input, &options).0
+    }
 
-            // next row that has a one in the column
-            current_row = { weak2rc(&current_row.borrow().d) };
-            current_row_id = current_row.borrow().id;
-        }
+    /// Read a problem from text: a header `p dlx <rows> <cols>`, then a line for every row listing the
+    /// columns it covers, counted from 1 and separated by spaces, or `-` for a row covering none. Lines
+    /// starting with `#` and blank lines are skipped. Errors tell the line that is wrong.
+    pub fn parse_instance(text: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+        instance::parse_instance(text)
+    }
+
+    /// Write a problem as text that `parse_instance` reads back.
+    pub fn write_instance(input: &[Vec<bool>]) -> alloc::string::String {
+        instance::write_instance(input)
+    }
+
+    /// Write the exact cover problem as a boolean formula in the DIMACS CNF format, to check solutions
+    /// against a SAT solver. Variable `y + 1` is true when row `y` is chosen, and every column is covered
+    /// exactly once: by at least one of its rows, and at most one of them, pair by pair for narrow
+    /// columns and with extra counter variables, after the rows, for wide ones. A column without rows
+    /// gives an empty clause, so the formula is unsatisfiable like the problem.
+    pub fn to_dimacs_cnf(input: &[Vec<bool>]) -> alloc::string::String {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        dimacs::to_dimacs_cnf(input)
+    }
+
+    /// Draw the structure of a matrix as a graph in the DOT format of Graphviz, with the headers on one
+    /// rank, the nodes of every row boxed together, and the right and down links. Render it with
+    /// `dot -Tsvg`.
+    pub fn to_dot(input: &[Vec<bool>]) -> alloc::string::String {
+        Self::build(input).to_dot()
+    }
+
+    /// Get the rows chosen by a model of the formula of `to_dimacs_cnf`, the value of every variable from
+    /// the first on, the ones after the rows being ignored.
+    pub fn solution_from_assignment(input: &[Vec<bool>], assignment: &[bool]) -> Vec<usize> {
+        dimacs::solution_from_assignment(input.len(), assignment)
+    }
+
+    /// Check that rows make up a solution, covering every column exactly once.
+    pub fn verify_solution(input: &[Vec<bool>], rows: &[usize]) -> Result<(), VerifyError> {
+        verify::verify(input, rows, usize::MAX)
+    }
+
+    /// Check that rows make up a solution when the columns from `primary` on are secondary: covering
+    /// every primary column exactly once, and every secondary one at most once.
+    pub fn verify_solution_with_secondary(input: &[Vec<bool>], rows: &[usize], primary: usize) -> Result<(), VerifyError> {
+        verify::verify(input, rows, primary)
+    }
+
+    /// Group the columns of a matrix into components sharing no row, two columns being in the same one
+    /// when a row covers both, giving the columns and rows of every component. Components come in the
+    /// order of their first column, with columns and rows ascending. Rows without entries are in none.
+    pub fn split_components(input: &[Vec<bool>]) -> Vec<(Vec<usize>, Vec<usize>)> {
+        components::split_components(input)
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices, by
+    /// solving every component of `split_components` on its own and combining their solutions. This is
+    /// the same set of solutions as `solve_all`, rows in the order of their components, and as soon as a
+    /// component has no solution the others aren't solved. Panics if the matrix has no rows or columns,
+    /// or rows of different widths.
+    pub fn solve_all_decomposed(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        components::solve_all_decomposed(input)
+    }
+
+    /// Simplify a matrix before searching it: a column with a single row forces that row, covering its
+    /// columns and dropping the rows sharing them, and a row that would leave a column it doesn't cover
+    /// without rows is dropped, until neither applies. Every column is taken as primary. Panics if the
+    /// matrix has no rows or columns, or rows of different widths.
+    pub fn presolve(input: &[Vec<bool>]) -> Presolved {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        presolve::presolve(input)
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding all solutions returning indices, by
+    /// searching what is left after `presolve`. This is the same set of solutions as `solve_all`, the
+    /// forced rows first in every one.
+    pub fn solve_all_presolved(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+        Self::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+        presolve::solve_all_presolved(input)
+    }
+
+    /// Drop the rows that are copies of earlier ones, giving the rows left, in order, and the original
+    /// indices of the copies of each of them, ascending. The first copy of every row is the one kept.
+    pub fn dedup_rows(input: &[Vec<bool>]) -> (Vec<Vec<bool>>, Vec<Vec<usize>>) {
+        duplicates::dedup_rows(input)
+    }
+
+    /// Solve an exact cover problem handing over its rows one at a time, finding all solutions returning
+    /// indices, in the order of `solve_all` on its matrix. The rows are built into the links as they
+    /// come, so the matrix, which can take more memory than the search, is never held. Panics if a row
+    /// has a column out of range or twice.
+    pub fn solve_problem<P: ExactCoverProblem>(problem: &P) -> Vec<Vec<usize>> {
+        problem::solve_problem(problem)
+    }
+
+    /// Solve the set partition problem, finding every way to partition `universe` into some of `subsets`,
+    /// each element being in exactly one of the subsets chosen, returning the indices of the subsets.
+    /// An empty universe has one partition, using no subsets. Errors if an element is in the universe
+    /// twice, or a subset has an element outside the universe or the same element twice.
+    pub fn solve_partition<T: Eq + core::hash::Hash>(universe: &[T], subsets: &[Vec<T>]) -> Result<Vec<Vec<usize>>, PartitionError> {
+        partition::solve_partition(universe, subsets)
+    }
+
+    /// Swap the rows and columns of a matrix, whose rows must be as wide as the first one, turning an
+    /// exact cover problem into an exact hitting set problem and back.
+    pub fn transpose(input: &[Vec<bool>]) -> Vec<Vec<bool>> {
+        hitting::transpose(input)
+    }
+
+    /// Solve the exact hitting set problem, finding every set of elements out of `0..universe` hitting
+    /// each of `sets`, listing the elements in them, exactly once. Elements come ascending in every
+    /// solution, and those in no set are in none. With no sets, the only solution has no elements, and
+    /// with an empty set, there are no solutions. Panics if a set has an element outside the universe.
+    pub fn solve_hitting_set(sets: &[Vec<usize>], universe: usize) -> Vec<Vec<usize>> {
+        hitting::solve_hitting_set(sets, universe)
+    }
+
+    /// Solve the set cover problem, finding sets of rows covering every column at least once, rows being
+    /// allowed to overlap. These are all the minimal covers, from which no row can be left out, each set
+    /// of rows once, or with `minimize_rows` a single cover with the fewest rows, the first found of
+    /// that size. Panics if the matrix has no rows or columns, or rows of different widths.
+    pub fn solve_cover(input: &[Vec<bool>], minimize_rows: bool) -> Vec<Vec<usize>> {
+        cover::solve_cover(input, minimize_rows)
+    }
 
-        Self::uncover(&best_col);
-        None
+    /// Solve a generalized cover problem, finding all sets of rows covering every column at least
+    /// `min` and at most `max` times, from its `(min, max)` in `bounds`, returning indices. Columns past
+    /// the end of `bounds` are covered exactly once. A column with bounds `(0, 1)` acts like a secondary
+    /// one, except that rows covering nothing else may be chosen too. Panics if the matrix has no rows
+    /// or columns, rows of different widths, or a column's `min` is above its `max`.
+    pub fn solve_all_bounded(input: &[Vec<bool>], bounds: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        bounded::solve_all_bounded(input, bounds)
+    }
+
+    /// Solve a problem with `primary` primary and `secondary` secondary columns whose rows give colors
+    /// to their secondary columns, finding all solutions returning indices. Every primary column is
+    /// covered exactly once, and rows sharing a secondary column must give it the same color. Panics if
+    /// a column is out of range, or a color is `u32::MAX`.
+    pub fn solve_xcc(rows: &[XccRow], primary: usize, secondary: usize) -> Vec<Vec<usize>> {
+        xcc::solve_xcc(rows, primary, secondary)
+    }
+
+    /// The deepest a search can go: every chosen row covers at least one column and no two share one.
+    fn max_depth(input: &[Vec<bool>]) -> usize {
+        input.len().min(input.first().map_or(0, Vec::len))
     }
 
     /// Solve the exact cover problem from a starting Node, finding one solution returning indices.
-    pub fn solve_once(input: &Vec<Vec<bool>>) -> Option<Vec<usize>> {
-        let (root, all_nodes) = Self::build(input);
-        Self::search_once(&root, &all_nodes, &mut Vec::new())
+    pub fn solve_once(input: &[Vec<bool>]) -> Option<Vec<usize>> {
+        Self::solve_once_with_options(input, &SolveOptions::default()).0
     }
-}
 
-/// Stupid helper function... data structures in rust 😔
-fn weak2rc(weak: &WeakNode) -> RcNode { weak.upgrade().unwrap() }
+    /// Solve the exact cover problem from a starting Node, finding one solution returning indices, trying
+    /// rows in an order shuffled from `seed`. Different seeds tend to give different solutions, the same
+    /// seed always gives the same one, and one is found whenever there is one. This isn't uniform: for
+    /// that, sample the diagram of `solve_to_zdd`.
+    pub fn solve_random(input: &[Vec<bool>], seed: u64) -> Option<Vec<usize>> {
+        Self::solve_once_with_options(input, &SolveOptions { row_seed: Some(seed), ..Default::default() }).0
+    }
 
-#[wasm_bindgen]
-pub fn js_solve_once(input: JsValue) -> JsValue {
-    let input = serde_wasm_bindgen::from_value(input).unwrap();
+    /// Solve the exact cover problem from a starting Node, finding one solution returning indices, or
+    /// tell what is wrong with the matrix.
+    pub fn try_solve_once(input: &[Vec<bool>]) -> Result<Option<Vec<usize>>, DlxError> {
+        Self::check_input(input)?;
+        Ok(Self::solve_once(input))
+    }
 
-    match Node::solve_once(&input) {
-        Some(solution) => solution.serialize(&Serializer::json_compatible()).unwrap(),
-        None => JsValue::NULL,
+    /// Solve the exact cover problem from a starting Node, finding the solution of `solve_once` into
+    /// `out`, which is cleared first and keeps its capacity. Returns whether there is one, `out` being
+    /// left empty if not.
+    pub fn solve_once_into(input: &[Vec<bool>], out: &mut Vec<usize>) -> bool {
+        out.clear();
+        let (flow, _) = Self::solve_with_options(input, &SolveOptions::default(), |solution| {
+            out.extend_from_slice(solution);
+            ControlFlow::Break(())
+        });
+        flow.is_break()
+    }
+
+    /// Solve the exact cover problem from a starting Node, finding one solution returning indices
+    /// along with statistics on the search.
+    pub fn solve_once_with_options(input: &[Vec<bool>], options: &SolveOptions) -> (Option<Vec<usize>>, SolveStats) {
+        let mut result = None;
+        let (_, stats) = Self::solve_with_options(input, options, |solution| {
+            result = Some(solution.to_vec());
+            ControlFlow::Break(())
+        });
+        (result, stats)
+    }
+}
+
+#[cfg(feature = "std")]
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** Rows of an exact cover problem, each one marking the columns it covers. */
+export type Matrix = boolean[][];
+/** Indices of the rows making up a solution. */
+export type Solution = number[];
+/**
+ * What every export throws when its arguments don't have the shapes of its parameter types, or can't
+ * be used as given, such as a matrix with rows of different widths. The message tells what is wrong.
+ */
+export interface DlxError extends Error {
+    message: string;
+}
+"#;
+
+/// Find one solution of a matrix, or `null` if there is none.
+///
+/// @throws {DlxError} if the matrix isn't rows of booleans, has no rows or columns, or has rows of
+/// different widths.
+#[cfg(feature = "std")]
+#[wasm_bindgen(unchecked_return_type = "Solution | null")]
+pub fn js_solve_once(#[wasm_bindgen(unchecked_param_type = "Matrix")] input: JsValue) -> Result<JsValue, JsError> {
+    let input: Vec<Vec<bool>> = serde_wasm_bindgen::from_value(input)?;
+
+    match Node::try_solve_once(&input)? {
+        Some(solution) => Ok(solution.serialize(&Serializer::json_compatible())?),
+        None => Ok(JsValue::NULL),
     }
 }
 
-#[wasm_bindgen]
-pub fn js_solve_all(input: JsValue) -> JsValue {
-    let input = serde_wasm_bindgen::from_value(input).unwrap();
+/// Find every solution of a matrix.
+///
+/// @throws {DlxError} if the matrix isn't rows of booleans, has no rows or columns, or has rows of
+/// different widths.
+#[cfg(feature = "std")]
+#[wasm_bindgen(unchecked_return_type = "Solution[]")]
+pub fn js_solve_all(#[wasm_bindgen(unchecked_param_type = "Matrix")] input: JsValue) -> Result<JsValue, JsError> {
+    let input: Vec<Vec<bool>> = serde_wasm_bindgen::from_value(input)?;
 
-    Node::solve_all(&input).serialize(&Serializer::json_compatible()).unwrap()
+    Ok(Node::try_solve_all(&input)?.serialize(&Serializer::json_compatible())?)
 }
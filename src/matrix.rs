@@ -0,0 +1,82 @@
+//! The structure of nodes opened up for searches written outside the crate.
+
+use alloc::vec::Vec;
+use crate::{Node, Structure};
+
+/// A column of a matrix, by the index of its header in the structure.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ColumnRef {
+    header: usize,
+}
+impl ColumnRef {
+    /// The index of the column in the matrix it was built from.
+    pub fn index(&self) -> usize { self.header - 1 }
+}
+
+/// A row of a matrix, reached through one of its nodes.
+#[derive(Clone, Copy, Debug)]
+pub struct RowRef<'a> {
+    structure: &'a Structure,
+    node: usize,
+}
+impl<'a> RowRef<'a> {
+    /// The index of the row in the matrix it was built from.
+    pub fn index(&self) -> usize { self.structure.nodes[self.node].data }
+
+    /// The columns of the row, the one it was reached through first, then the others left to right.
+    pub fn cells(&self) -> impl Iterator<Item = ColumnRef> + 'a {
+        let structure = self.structure;
+        core::iter::once(self.node)
+            .chain(structure.walk(self.node, Structure::right))
+            .map(|node| ColumnRef { header: structure.column(node) })
+    }
+}
+
+/// A matrix built into dancing links, for searches of one's own on top of covering and uncovering.
+///
+/// Columns must be uncovered in the reverse order they were covered, the links of a column only
+/// being right as long as every column covered after it was uncovered first. Debug builds check
+/// this, and that no column is covered twice.
+#[derive(Clone, Debug)]
+pub struct LinkedMatrix {
+    structure: Structure,
+    /// The columns covered, in order.
+    covered: Vec<usize>,
+}
+impl LinkedMatrix {
+    /// Build the structure of a matrix. Panics if the matrix has no rows or columns, or rows of
+    /// different widths.
+    pub fn build(input: &[Vec<bool>]) -> LinkedMatrix {
+        LinkedMatrix { structure: Node::build(input), covered: Vec::new() }
+    }
+
+    /// Check if every column is covered.
+    pub fn is_solved(&self) -> bool { self.structure.is_solved() }
+
+    /// The columns left uncovered, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = ColumnRef> + '_ {
+        self.structure.walk(Structure::ROOT, Structure::right).map(|header| ColumnRef { header })
+    }
+
+    /// The amount of rows left in a column.
+    pub fn size(&self, col: ColumnRef) -> usize { self.structure.nodes[col.header].data }
+
+    /// The rows left in a column, top to bottom.
+    pub fn rows_in(&self, col: ColumnRef) -> impl Iterator<Item = RowRef<'_>> {
+        self.structure.walk(col.header, Structure::down).map(|node| RowRef { structure: &self.structure, node })
+    }
+
+    /// Cover a column: take it out of the columns left, along with every row left in it.
+    pub fn cover(&mut self, col: ColumnRef) {
+        debug_assert!(!self.covered.contains(&col.header), "column {} is already covered", col.index());
+        self.structure.cover(col.header);
+        self.covered.push(col.header);
+    }
+
+    /// Undo covering a column, which must be the last one covered still covered.
+    pub fn uncover(&mut self, col: ColumnRef) {
+        let last = self.covered.pop();
+        debug_assert_eq!(last, Some(col.header), "columns must be uncovered in the reverse order they were covered");
+        self.structure.uncover(col.header);
+    }
+}
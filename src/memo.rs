@@ -0,0 +1,68 @@
+//! A bounded transposition table keyed by the set of covered columns.
+
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Get the Zobrist key of a column from the index of its header in the structure, so the keys of a
+/// problem are the same from one build to the next.
+pub(crate) fn zobrist(header: usize) -> u128 {
+    let hi = splitmix64(header as u64);
+    let lo = splitmix64(hi ^ header as u64);
+    ((hi as u128) << 64) | lo as u128
+}
+
+/// Get the Zobrist key of a secondary column given a color, distinct from the key of covering it.
+pub(crate) fn zobrist_colored(header: usize, color: u32) -> u128 {
+    zobrist(header) ^ zobrist(!splitmix64(color as u64) as usize)
+}
+
+/// The splitmix64 mixing function, also used as a small seeded generator.
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Subproblem results keyed by state hash, evicting a random entry once `capacity` is reached.
+pub(crate) struct MemoTable {
+    capacity: usize,
+    values: HashMap<u128, (u64, usize)>,
+    keys: Vec<u128>,
+    rng: u64,
+}
+impl MemoTable {
+    /// Create an empty table holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> MemoTable {
+        MemoTable { capacity, values: HashMap::new(), keys: Vec::new(), rng: 0 }
+    }
+
+    /// Get the value stored for a state.
+    pub fn get(&self, key: u128) -> Option<u64> {
+        self.values.get(&key).map(|&(value, _)| value)
+    }
+
+    /// Store the value of a state, evicting a random entry if the table is full.
+    pub fn insert(&mut self, key: u128, value: u64) {
+        if self.capacity == 0 { return; }
+        if let Some(entry) = self.values.get_mut(&key) {
+            entry.0 = value;
+            return;
+        }
+
+        if self.keys.len() == self.capacity {
+            self.rng = splitmix64(self.rng);
+            let i = self.rng as usize % self.capacity;
+            let evicted = self.keys.swap_remove(i);
+            self.values.remove(&evicted);
+
+            // the last key moved into the evicted slot
+            if let Some(&moved) = self.keys.get(i) {
+                self.values.get_mut(&moved).unwrap().1 = i;
+            }
+        }
+
+        self.values.insert(key, (value, self.keys.len()));
+        self.keys.push(key);
+    }
+}
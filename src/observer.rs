@@ -0,0 +1,28 @@
+//! Following the search step by step, for visualizing or tracing it.
+
+/// Handed every step of a search by `Node::solve_all_observed`. Every method does nothing unless
+/// implemented, and the search is built for the observer's type, so ignored steps cost nothing.
+/// Columns and rows are counted from 0, as in the matrix.
+pub trait SearchObserver {
+    /// The search branches on a column with `size` rows left.
+    fn on_choose_column(&mut self, col: usize, size: usize) { let _ = (col, size); }
+    /// The search tries a row, with `depth` rows chosen before it.
+    fn on_try_row(&mut self, row: usize, depth: usize) { let _ = (row, depth); }
+    /// A column is covered, by branching on it or by choosing a row in it.
+    fn on_cover(&mut self, col: usize) { let _ = col; }
+    /// A column is uncovered, in the reverse order it was covered.
+    fn on_uncover(&mut self, col: usize) { let _ = col; }
+    /// The rows chosen make up a solution.
+    fn on_solution(&mut self, rows: &[usize]) { let _ = rows; }
+}
+
+/// Observing nothing.
+impl SearchObserver for () {}
+
+impl<T: SearchObserver + ?Sized> SearchObserver for &mut T {
+    fn on_choose_column(&mut self, col: usize, size: usize) { (**self).on_choose_column(col, size); }
+    fn on_try_row(&mut self, row: usize, depth: usize) { (**self).on_try_row(row, depth); }
+    fn on_cover(&mut self, col: usize) { (**self).on_cover(col); }
+    fn on_uncover(&mut self, col: usize) { (**self).on_uncover(col); }
+    fn on_solution(&mut self, rows: &[usize]) { (**self).on_solution(rows); }
+}
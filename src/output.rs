@@ -0,0 +1,51 @@
+//! Writing solutions out as they are found.
+
+use std::io::{self, Write};
+use core::ops::ControlFlow;
+use crate::Node;
+
+/// How `Node::solve_all_to_writer` writes every solution.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SolutionFormat {
+    /// A line of row indices separated by spaces.
+    #[default]
+    Lines,
+    /// A line holding a JSON array of row indices.
+    JsonLines,
+}
+
+/// Write every solution of a matrix to `w` as it is found, flushing after each one, returning how many
+/// were written. The first error writing stops the search.
+pub(crate) fn solve_all_to_writer<W: Write>(input: &[Vec<bool>], w: &mut W, format: SolutionFormat) -> io::Result<u64> {
+    let (open, separator, close) = match format {
+        SolutionFormat::Lines => ("", " ", ""),
+        SolutionFormat::JsonLines => ("[", ",", "]"),
+    };
+    let mut count = 0;
+    let mut line = Vec::new();
+    let mut error = None;
+    let _ = Node::solve_with(input, |solution| {
+        // a whole line at once, writing to a vector never failing
+        line.clear();
+        let _ = write!(line, "{open}");
+        for (i, row) in solution.iter().enumerate() {
+            let _ = write!(line, "{}{row}", if i == 0 { "" } else { separator });
+        }
+        let _ = writeln!(line, "{close}");
+
+        match w.write_all(&line).and_then(|()| w.flush()) {
+            Ok(()) => {
+                count += 1;
+                ControlFlow::Continue(())
+            }
+            Err(e) => {
+                error = Some(e);
+                ControlFlow::Break(())
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(count),
+    }
+}
@@ -0,0 +1,35 @@
+//! Solving on several threads, each searching the subtrees of some rows of the first column.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::{Node, Solver};
+
+/// Find all solutions on as many threads as there are cores. The search branches first on the
+/// smallest column, leftmost on ties, as a single thread would; every thread builds its own structure
+/// and takes the rows of that column one at a time, searching the solutions including each. They are
+/// put back in row order, so the solutions come out in the same order as with `Node::solve_all`.
+pub(crate) fn par_solve_all(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+    let width = Node::check_input(input).unwrap_or_else(|error| panic!("invalid matrix: {error}"));
+    let Some(column) = (0..width).min_by_key(|&x| input.iter().filter(|row| row[x]).count()) else { return Vec::new() };
+    let rows: Vec<usize> = (0..input.len()).filter(|&row| input[row][column]).collect();
+
+    let threads = std::thread::available_parallelism().map_or(1, usize::from).min(rows.len());
+    let next = AtomicUsize::new(0);
+    let found: Mutex<Vec<Vec<Vec<usize>>>> = Mutex::new(vec![Vec::new(); rows.len()]);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut solver = Solver::new(input);
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&row) = rows.get(i) else { break };
+                    let solutions = solver.solve_with_givens(&[row]).expect("a single row can't conflict");
+                    found.lock().unwrap()[i] = solutions;
+                }
+            });
+        }
+    });
+
+    found.into_inner().unwrap().into_iter().flatten().collect()
+}
@@ -0,0 +1,62 @@
+//! Partitioning a universe of elements into given subsets, without building a matrix by hand.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+use core::ops::ControlFlow;
+use hashbrown::{HashMap, HashSet};
+use crate::{SolveOptions, Structure};
+use crate::search::Search;
+
+/// Why subsets can't be used to partition a universe. Positions are counted from 0.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PartitionError {
+    /// An element is in the universe more than once.
+    DuplicateInUniverse { index: usize },
+    /// An element of a subset isn't in the universe.
+    UnknownElement { subset: usize, position: usize },
+    /// An element is in a subset more than once, so it could never be chosen.
+    DuplicateElement { subset: usize, position: usize },
+}
+impl fmt::Display for PartitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PartitionError::DuplicateInUniverse { index } => write!(f, "element {index} of the universe is in it more than once"),
+            PartitionError::UnknownElement { subset, position } => write!(f, "element {position} of subset {subset} isn't in the universe"),
+            PartitionError::DuplicateElement { subset, position } => write!(f, "element {position} of subset {subset} is in it more than once"),
+        }
+    }
+}
+impl core::error::Error for PartitionError {}
+
+/// Find every way to partition a universe into subsets, as exact covers of the elements by the
+/// subsets.
+pub(crate) fn solve_partition<T: Eq + Hash>(universe: &[T], subsets: &[Vec<T>]) -> Result<Vec<Vec<usize>>, PartitionError> {
+    let mut columns = HashMap::with_capacity(universe.len());
+    for (index, element) in universe.iter().enumerate() {
+        if columns.insert(element, index).is_some() { return Err(PartitionError::DuplicateInUniverse { index }); }
+    }
+
+    let mut rows = Vec::with_capacity(subsets.len());
+    for (subset, elements) in subsets.iter().enumerate() {
+        let mut row = Vec::with_capacity(elements.len());
+        let mut seen = HashSet::with_capacity(elements.len());
+        for (position, element) in elements.iter().enumerate() {
+            let &x = columns.get(element).ok_or(PartitionError::UnknownElement { subset, position })?;
+            if !seen.insert(x) { return Err(PartitionError::DuplicateElement { subset, position }); }
+            row.push((x, 0));
+        }
+        rows.push(row);
+    }
+
+    let entries = rows.iter().map(Vec::len).sum();
+    let mut structure = Structure::from_rows(universe.len(), universe.len(), entries, rows);
+    let options = SolveOptions::default();
+    let mut search = Search::new(&mut structure, &options, subsets.len().min(universe.len()));
+    let mut results = Vec::new();
+    let _ = search.run(&mut |solution| {
+        results.push(solution.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(results)
+}
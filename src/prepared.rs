@@ -0,0 +1,199 @@
+//! A problem built once into a flat table of links, which can be saved as bytes and loaded back.
+
+use alloc::{vec, vec::Vec};
+use core::ops::ControlFlow;
+use crate::{DlxError, Node, SolveOptions, SolveStats, Structure};
+use crate::search::Search;
+
+/// Bytes every saved structure starts with.
+const MAGIC: &[u8; 4] = b"DLX\0";
+/// Version of the format of saved structures.
+const VERSION: u32 = 1;
+/// Length of the magic number, version, width, row count and node count.
+const HEADER_LEN: usize = 4 + 4 + 8 + 8 + 8;
+/// Length of the links and data of a node.
+const NODE_LEN: usize = 5 * 4 + 8;
+
+/// The links of a node as indices into the table: up, down, left, right, and column.
+type Links = [u32; 5];
+
+/// A problem built once into a table of links. Solving makes a structure from the table without
+/// going through the matrix again, and the table can be saved with `to_bytes` and loaded with
+/// `from_bytes`, for instance to cache a prepared problem on disk.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dlx {
+    width: usize,
+    rows: usize,
+    /// The links of the root, then of the column headers, then of the other nodes row by row.
+    links: Vec<Links>,
+    /// The size of every column for the headers, and the row of every other node.
+    data: Vec<usize>,
+}
+impl Dlx {
+    /// Build the structure of a matrix.
+    pub fn new(input: &[Vec<bool>]) -> Dlx {
+        let structure = Node::build(input);
+        let links = structure.nodes.iter().map(|node| [node.u, node.d, node.l, node.r, node.c]).collect();
+        let data = structure.nodes.iter().map(|node| node.data).collect();
+        Dlx { width: input[0].len(), rows: input.len(), links, data }
+    }
+
+    /// Make the structure from the table.
+    fn structure(&self) -> Structure {
+        let nodes = self.links.iter().zip(&self.data)
+            .map(|(&[u, d, l, r, c], &data)| Node { u, d, l, r, c, color: 0, data })
+            .collect();
+        Structure { nodes }
+    }
+
+    /// Get the matrix the table was built from.
+    fn matrix(&self) -> Vec<Vec<bool>> {
+        let mut matrix = vec![vec![false; self.width]; self.rows];
+        for (&[.., c], &row) in self.links.iter().zip(&self.data).skip(1 + self.width) {
+            matrix[row][c as usize - 1] = true;
+        }
+        matrix
+    }
+
+    /// Find all solutions returning indices, along with statistics on the search. As with
+    /// `Node::solve_all_with_options`, the rows the options forbid are never chosen and the rows they
+    /// require start every solution. Panics if those rows aren't in the matrix, a row is both, or two
+    /// required rows cover the same column.
+    pub fn solve_all_with_options(&self, options: &SolveOptions) -> (Vec<Vec<usize>>, SolveStats) {
+        let required = &options.require_rows;
+        if !required.is_empty() || !options.forbid_rows.is_empty() {
+            Node::check_options(&self.matrix(), options).unwrap_or_else(|error| panic!("invalid options: {error}"));
+        }
+
+        let mut solutions = Vec::new();
+        let mut structure = self.structure();
+        structure.choose_rows(self.width, self.rows, options);
+        let mut search = Search::new(&mut structure, options, self.rows.min(self.width));
+        search.given = required.len();
+        let _ = search.run(&mut |solution| {
+            solutions.push(required.iter().chain(solution).copied().collect());
+            ControlFlow::Continue(())
+        });
+        (solutions, search.stats)
+    }
+
+    /// Find all solutions returning indices.
+    pub fn solve_all(&self) -> Vec<Vec<usize>> {
+        self.solve_all_with_options(&SolveOptions::default()).0
+    }
+
+    /// Find one solution returning indices.
+    pub fn solve_once(&self) -> Option<Vec<usize>> {
+        let (mut found, options, mut structure) = (None, SolveOptions::default(), self.structure());
+        let mut search = Search::new(&mut structure, &options, self.rows.min(self.width));
+        let _ = search.run(&mut |solution| {
+            found = Some(solution.to_vec());
+            ControlFlow::Break(())
+        });
+        found
+    }
+
+    /// Save the structure: a magic number and format version, the sizes, the links and data of every
+    /// node, and a checksum of everything before it, all little endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.links.len() * NODE_LEN + 8);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        for size in [self.width, self.rows, self.links.len()] {
+            bytes.extend_from_slice(&(size as u64).to_le_bytes());
+        }
+        for (links, data) in self.links.iter().zip(&self.data) {
+            for link in links { bytes.extend_from_slice(&link.to_le_bytes()); }
+            bytes.extend_from_slice(&(*data as u64).to_le_bytes());
+        }
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Load a structure saved by `to_bytes`, checking every link before it can be used.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Dlx, DlxError> {
+        if !bytes.starts_with(MAGIC) { return Err(DlxError::NotDlx); }
+        if bytes.len() < HEADER_LEN + 8 { return Err(DlxError::Truncated); }
+
+        let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+        let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+
+        let version = u32_at(4);
+        if version != VERSION { return Err(DlxError::UnsupportedVersion { version }); }
+
+        let (width, rows, count) = (u64_at(8) as usize, u64_at(16) as usize, u64_at(24) as usize);
+        let len = count.checked_mul(NODE_LEN).and_then(|len| len.checked_add(HEADER_LEN + 8));
+        if len != Some(bytes.len()) { return Err(DlxError::Truncated); }
+
+        let (body, sum) = bytes.split_at(bytes.len() - 8);
+        if checksum(body) != u64::from_le_bytes(sum.try_into().unwrap()) { return Err(DlxError::ChecksumMismatch); }
+
+        let mut links = Vec::with_capacity(count);
+        let mut data = Vec::with_capacity(count);
+        for node in 0..count {
+            let at = HEADER_LEN + node * NODE_LEN;
+            links.push(core::array::from_fn(|i| u32_at(at + i * 4)));
+            data.push(u64_at(at + 20) as usize);
+        }
+
+        let dlx = Dlx { width, rows, links, data };
+        dlx.check()?;
+        Ok(dlx)
+    }
+
+    /// Check that every link is in range, that links go both ways, that the root's ring holds headers,
+    /// and that every other node is in the ring of its column and of its row.
+    fn check(&self) -> Result<(), DlxError> {
+        let len = self.links.len();
+        if len <= self.width { return Err(DlxError::Truncated); }
+        let is_header = |node: usize| (1..=self.width).contains(&node);
+
+        for (node, links) in self.links.iter().enumerate() {
+            let broken = Err(DlxError::BrokenLink { node });
+            if links.iter().any(|&link| link as usize >= len) { return broken; }
+
+            let [u, d, l, r, c] = links.map(|link| link as usize);
+            let mirrored = self.links[u][1] as usize == node && self.links[d][0] as usize == node
+                && self.links[l][3] as usize == node && self.links[r][2] as usize == node;
+            let column = if node <= self.width { c == node } else { is_header(c) && self.data[node] < self.rows };
+            // the nodes of a row are all of that row
+            let row = node <= self.width || [l, r].iter().all(|&next| next > self.width && self.data[next] == self.data[node]);
+            if !mirrored || !column || !row { return broken; }
+        }
+
+        // the ring of the root, every header left out of it being alone
+        let mut in_ring = vec![false; self.width + 1];
+        let mut header = self.links[0][3] as usize;
+        while header != 0 {
+            if !is_header(header) || core::mem::replace(&mut in_ring[header], true) { return Err(DlxError::BrokenLink { node: 0 }); }
+            header = self.links[header][3] as usize;
+        }
+
+        let mut seen = vec![false; len];
+        for (header, &in_ring) in in_ring.iter().enumerate().skip(1) {
+            if !in_ring && self.links[header][3] as usize != header { return Err(DlxError::BrokenLink { node: header }); }
+
+            let mut size = 0;
+            let mut node = self.links[header][1] as usize;
+            while node != header {
+                if node <= self.width || self.links[node][4] as usize != header || core::mem::replace(&mut seen[node], true) {
+                    return Err(DlxError::BrokenLink { node });
+                }
+                size += 1;
+                node = self.links[node][1] as usize;
+            }
+            if size != self.data[header] { return Err(DlxError::BrokenLink { node: header }); }
+        }
+
+        match (self.width + 1..len).find(|&node| !seen[node]) {
+            Some(node) => Err(DlxError::BrokenLink { node }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Get the 64 bit FNV-1a hash of bytes.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
@@ -0,0 +1,151 @@
+//! Simplifying a matrix before searching it, by choosing the rows it can't do without and dropping
+//! the rows it can't use.
+
+use alloc::{vec, vec::Vec};
+use crate::Node;
+
+/// A matrix simplified by `Node::presolve`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Presolved {
+    /// The rows every solution has, in the order they were forced.
+    pub forced_rows: Vec<usize>,
+    /// The columns not covered by the forced rows, restricted to the rows left. With no columns left,
+    /// the forced rows are already a solution.
+    pub reduced: Vec<Vec<bool>>,
+    /// The original index of every row left.
+    pub row_map: Vec<usize>,
+    /// Whether a column was left without rows, so the matrix has no solution.
+    pub infeasible: bool,
+}
+impl Presolved {
+    /// Map a solution of the reduced matrix to a solution of the original one, forced rows first.
+    pub fn original_rows(&self, solution: &[usize]) -> Vec<usize> {
+        self.forced_rows.iter().copied().chain(solution.iter().map(|&row| self.row_map[row])).collect()
+    }
+}
+
+/// The rows and columns still in a matrix being simplified.
+struct State {
+    /// The columns of every row.
+    rows: Vec<Vec<usize>>,
+    /// The rows of every column.
+    columns: Vec<Vec<usize>>,
+    live_rows: Vec<bool>,
+    live_columns: Vec<bool>,
+    forced: Vec<usize>,
+}
+impl State {
+    /// Get the rows and columns of a matrix, every column being left and every row with entries.
+    fn new(input: &[Vec<bool>]) -> State {
+        let width = input.first().map_or(0, Vec::len);
+        let rows: Vec<Vec<usize>> = input.iter()
+            .map(|row| row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| x).collect())
+            .collect();
+        let mut columns = vec![Vec::new(); width];
+        for (y, row) in rows.iter().enumerate() {
+            for &x in row { columns[x].push(y); }
+        }
+        // a row without entries is in no solution
+        let live_rows = rows.iter().map(|row| !row.is_empty()).collect();
+        State { rows, columns, live_rows, live_columns: vec![true; width], forced: Vec::new() }
+    }
+
+    /// Choose the only row of every column left with one, in order, telling if any was chosen, or
+    /// erroring with the first column left without rows.
+    fn force_singles(&mut self) -> Result<bool, usize> {
+        let mut changed = false;
+        for col in 0..self.columns.len() {
+            if !self.live_columns[col] { continue; }
+            let mut rows = self.rows_in(col);
+            let (first, second) = (rows.next(), rows.next());
+            drop(rows);
+            match (first, second) {
+                (None, _) => return Err(col),
+                (Some(row), None) => {
+                    self.force(row);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Choose a row, covering its columns and dropping every other row in them.
+    fn force(&mut self, row: usize) {
+        self.forced.push(row);
+        for &col in &self.rows[row] {
+            self.live_columns[col] = false;
+            for &other in &self.columns[col] { self.live_rows[other] = false; }
+        }
+    }
+
+    /// Get the rows left in a column.
+    fn rows_in(&self, col: usize) -> impl Iterator<Item = usize> + '_ {
+        self.columns[col].iter().copied().filter(|&row| self.live_rows[row])
+    }
+
+    /// Check if two rows share a column.
+    fn overlap(&self, a: usize, b: usize) -> bool {
+        self.rows[a].iter().any(|col| self.rows[b].contains(col))
+    }
+
+    /// Check if choosing a row would leave a column it doesn't cover without rows.
+    fn blocks_column(&self, row: usize) -> bool {
+        (0..self.columns.len())
+            .filter(|&col| self.live_columns[col] && !self.rows[row].contains(&col))
+            .any(|col| self.rows_in(col).all(|other| self.overlap(row, other)))
+    }
+}
+
+/// Choose the only row of every column with one until none is left, and drop every row that would
+/// leave a column without rows, over and over as either makes more of the other possible.
+pub(crate) fn presolve(input: &[Vec<bool>]) -> Presolved {
+    let width = input.first().map_or(0, Vec::len);
+    let mut state = State::new(input);
+
+    let infeasible = loop {
+        let Ok(mut changed) = state.force_singles() else { break true };
+
+        for row in 0..state.rows.len() {
+            if state.live_rows[row] && state.blocks_column(row) {
+                state.live_rows[row] = false;
+                changed = true;
+            }
+        }
+        if !changed { break false; }
+    };
+
+    if infeasible {
+        return Presolved { forced_rows: state.forced, reduced: Vec::new(), row_map: Vec::new(), infeasible };
+    }
+    let columns: Vec<usize> = (0..width).filter(|&x| state.live_columns[x]).collect();
+    let row_map: Vec<usize> = (0..input.len()).filter(|&y| state.live_rows[y]).collect();
+    let reduced = row_map.iter()
+        .map(|&y| columns.iter().map(|&x| input[y][x]).collect())
+        .collect();
+    Presolved { forced_rows: state.forced, reduced, row_map, infeasible }
+}
+
+/// Presolve a matrix, then solve what is left of it, returning solutions of the original matrix.
+pub(crate) fn solve_all_presolved(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+    let presolved = presolve(input);
+    if presolved.infeasible { return Vec::new(); }
+    // no columns left to cover
+    if presolved.reduced.first().is_none_or(Vec::is_empty) { return vec![presolved.forced_rows]; }
+
+    Node::solve_all(&presolved.reduced).iter().map(|solution| presolved.original_rows(solution)).collect()
+}
+
+/// Choose the only row of every column with one until none is left, giving the rows chosen in order,
+/// and the first column left without rows if one is.
+pub(crate) fn force_singles(input: &[Vec<bool>]) -> (Vec<usize>, Option<usize>) {
+    let mut state = State::new(input);
+    loop {
+        match state.force_singles() {
+            Ok(true) => {}
+            Ok(false) => return (state.forced, None),
+            Err(col) => return (state.forced, Some(col)),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Problems handing their rows over one at a time, so the matrix is never held in full.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::ControlFlow;
+use crate::{SolveOptions, Structure};
+use crate::search::Search;
+
+/// An exact cover problem giving its rows as the columns they cover, built into the links as they
+/// come instead of as a matrix of every column of every row.
+pub trait ExactCoverProblem {
+    /// The number of columns, all to be covered exactly once.
+    fn num_columns(&self) -> usize;
+    /// The rows, each listing the columns it covers, in any order and without repeats.
+    fn rows(&self) -> Box<dyn Iterator<Item = Vec<usize>> + '_>;
+}
+
+/// Find all solutions to a problem, building its links row by row. Panics if a row has a column out of
+/// range or twice.
+pub(crate) fn solve_problem<P: ExactCoverProblem + ?Sized>(problem: &P) -> Vec<Vec<usize>> {
+    let width = problem.num_columns();
+    let rows = problem.rows().enumerate().map(|(y, mut row)| {
+        row.sort_unstable();
+        if let Some(&x) = row.last() { assert!(x < width, "column {x} of row {y} out of range"); }
+        if let Some(pair) = row.windows(2).find(|pair| pair[0] == pair[1]) { panic!("column {} repeated in row {y}", pair[0]); }
+        row.into_iter().map(|x| (x, 0))
+    });
+    let mut structure = Structure::from_rows(width, width, 0, rows);
+
+    let options = SolveOptions::default();
+    let mut search = Search::new(&mut structure, &options, width);
+    let mut results = Vec::new();
+    let _ = search.run(&mut |solution| {
+        results.push(solution.to_vec());
+        ControlFlow::Continue(())
+    });
+    results
+}
@@ -0,0 +1,68 @@
+//! Committing to rows up front and keeping the residual problem.
+
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// Two chosen rows both cover a column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConflictError {
+    pub column: usize,
+    pub rows: (usize, usize),
+}
+impl fmt::Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rows {} and {} both cover column {}", self.rows.0, self.rows.1, self.column)
+    }
+}
+impl core::error::Error for ConflictError {}
+
+/// The problem left after choosing some rows.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reduced {
+    /// The columns not covered by the chosen rows, restricted to the rows sharing no column with them.
+    /// With no columns left, the chosen rows are already a solution.
+    pub matrix: Vec<Vec<bool>>,
+    /// The original index of every residual row.
+    pub rows: Vec<usize>,
+    /// The original index of every residual column.
+    pub columns: Vec<usize>,
+}
+impl Reduced {
+    /// Map a solution of the residual matrix back to original row indices.
+    pub fn original_rows(&self, solution: &[usize]) -> Vec<usize> {
+        solution.iter().map(|&row| self.rows[row]).collect()
+    }
+}
+
+/// Get which chosen row covers every column, erroring if two of them overlap. Panics if a chosen row
+/// is out of range.
+pub(crate) fn covered_by(input: &[Vec<bool>], chosen: &[usize]) -> Result<Vec<Option<usize>>, ConflictError> {
+    let width = input.first().map_or(0, Vec::len);
+    let mut covered_by: Vec<Option<usize>> = vec![None; width];
+    for &row in chosen {
+        for (column, _) in input[row].iter().enumerate().filter(|(_, val)| **val) {
+            if let Some(other) = covered_by[column] {
+                return Err(ConflictError { column, rows: (other, row) });
+            }
+            covered_by[column] = Some(row);
+        }
+    }
+    Ok(covered_by)
+}
+
+/// Apply the chosen rows to a matrix. Panics if a chosen row is out of range.
+pub(crate) fn reduce(input: &[Vec<bool>], chosen: &[usize]) -> Result<Reduced, ConflictError> {
+    let width = input.first().map_or(0, Vec::len);
+    let covered_by = covered_by(input, chosen)?;
+
+    let columns: Vec<usize> = (0..width).filter(|&x| covered_by[x].is_none()).collect();
+    let rows: Vec<usize> = (0..input.len())
+        .filter(|&y| !chosen.contains(&y))
+        .filter(|&y| input[y].iter().zip(&covered_by).all(|(val, by)| !val || by.is_none()))
+        .collect();
+    let matrix = rows.iter()
+        .map(|&y| columns.iter().map(|&x| input[y][x]).collect())
+        .collect();
+
+    Ok(Reduced { matrix, rows, columns })
+}
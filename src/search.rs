@@ -0,0 +1,1012 @@
+//! The DLX search over a built structure of nodes, with its options and statistics.
+
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use core::fmt;
+use core::ops::ControlFlow;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use std::time::Instant;
+use crate::{Node, SETTLED, SearchObserver, Structure};
+use crate::memo::{MemoTable, splitmix64, zobrist, zobrist_colored};
+use crate::zdd::Zdd;
+
+/// Nodes of the search tree visited between two looks at the clock when there is a deadline.
+#[cfg(feature = "std")]
+const DEADLINE_INTERVAL: u64 = 256;
+
+/// Check if a search that visited `nodes` nodes was cancelled, ran out of nodes, or passed its deadline.
+pub(crate) fn should_stop(options: &SolveOptions, nodes: u64) -> bool {
+    let cancelled = options.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::Relaxed));
+    #[cfg(feature = "std")]
+    let late = nodes.is_multiple_of(DEADLINE_INTERVAL) && options.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+    #[cfg(not(feature = "std"))]
+    let late = false;
+    cancelled || late || options.node_limit.is_some_and(|limit| nodes >= limit)
+}
+
+/// Solutions found between two log lines about them.
+#[cfg(feature = "log")]
+const LOG_SOLUTION_INTERVAL: u64 = 10_000;
+
+/// Nodes of the search tree visited between two log lines about the column branched on.
+#[cfg(feature = "log")]
+const LOG_NODE_INTERVAL: u64 = 4096;
+
+/// Log that a search reached a depth it never reached before.
+#[cfg(feature = "log")]
+pub(crate) fn log_depth(depth: usize, stats: &SolveStats) {
+    log::debug!("search reached depth {depth} after {} nodes", stats.nodes);
+}
+
+/// Log the solutions found so far, once every `LOG_SOLUTION_INTERVAL` of them.
+#[cfg(feature = "log")]
+pub(crate) fn log_solution(stats: &SolveStats) {
+    if stats.solutions.is_multiple_of(LOG_SOLUTION_INTERVAL) {
+        log::info!("search found {} solutions after {} nodes", stats.solutions, stats.nodes);
+    }
+}
+
+/// Log the column a node of the search tree branches on, once every `LOG_NODE_INTERVAL` nodes.
+#[cfg(feature = "log")]
+pub(crate) fn log_column(col: usize, size: usize, stats: &SolveStats) {
+    if stats.nodes.is_multiple_of(LOG_NODE_INTERVAL) {
+        log::trace!("search node {} branches on column {col} with {size} rows", stats.nodes);
+    }
+}
+
+/// How the search picks the column to branch on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColumnStrategy {
+    /// The column with the fewest rows, the leftmost one on ties.
+    #[default]
+    Smallest,
+    /// Take the `candidates` smallest columns, and for each count the rows that don't immediately
+    /// leave another column empty once chosen. Branch on the column with the fewest such rows.
+    Lookahead { candidates: usize },
+    /// The same column as `Smallest`, found in ordered buckets of columns by size kept up to date while
+    /// covering instead of scanning every column. Faster on wide matrices with shallow searches.
+    Bucketed,
+    /// The leftmost column, whatever its size.
+    First,
+    /// A column picked at random, the same ones for the same seed.
+    Random { seed: u64 },
+}
+
+/// What a search runs on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Backend {
+    /// Bitsets when the matrix has at most 128 columns and the options don't need dancing links,
+    /// dancing links otherwise.
+    #[default]
+    Auto,
+    /// A structure of nodes linked in rows and columns.
+    DancingLinks,
+    /// Every row a `u128` mask of its columns, covering columns by setting bits. It branches on the
+    /// smallest column and tries its rows top to bottom, so it finds the same solutions in the same
+    /// order as dancing links with the default strategy, but the column strategy, memo, row seed,
+    /// early contradiction and progress options don't apply. Panics if the matrix has more than 128
+    /// columns.
+    Bitset,
+}
+
+/// Options for a search.
+#[derive(Clone, Debug, Default)]
+pub struct SolveOptions {
+    /// What the functions enumerating solutions over a matrix search on.
+    pub backend: Backend,
+    pub column_strategy: ColumnStrategy,
+    /// Remember subproblems (identified by their set of remaining columns) in a table of at most
+    /// this many entries: ones without solutions when enumerating, and their counts when counting.
+    /// The state is hashed to 128 bits, so a collision is astronomically unlikely but not impossible.
+    pub memo_capacity: Option<usize>,
+    /// Stop the search as soon as this flag is set, keeping the solutions found so far.
+    /// It is checked once per node of the search tree, when counting solutions too.
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Stop the search once it has visited this many nodes, keeping the solutions found so far, to put a
+    /// hard cap on the work done. The `_outcome` functions of `Node` tell whether it was reached.
+    pub node_limit: Option<u64>,
+    /// Stop the search once this time has passed, keeping the solutions found so far.
+    /// Reading the clock isn't free, so it is checked every few hundred nodes.
+    #[cfg(feature = "std")]
+    pub deadline: Option<Instant>,
+    /// After choosing a row, backtrack at once if it left a column without rows, instead of finding
+    /// out when branching on that column one level deeper.
+    pub early_contradiction: bool,
+    /// Columns from this index on are secondary: covered at most once instead of exactly once.
+    pub primary_columns: Option<usize>,
+    /// Leave out of the structure the columns covered by the same rows as an earlier column of the same
+    /// kind, as covering one covers the other. Solutions are the same, found with less work, and the
+    /// columns left out are in the statistics. The bitset backend ignores this, and isn't picked for it.
+    pub merge_duplicate_columns: bool,
+    /// Give up on partial solutions of this many rows that aren't solutions yet, instead of adding more
+    /// rows to them. Solutions never have more rows. The memo isn't used with bounds on solution size.
+    pub max_rows: Option<usize>,
+    /// Drop solutions with fewer rows.
+    pub min_rows: Option<usize>,
+    /// Rows every solution must include, chosen before the search and put first in every solution.
+    /// These and `forbid_rows` apply when enumerating or counting the solutions of a matrix, not to
+    /// diagrams of solutions. With `dedup_rows`, they are indices into the matrix without copies.
+    pub require_rows: Vec<usize>,
+    /// Rows no solution may include, taken out of the structure before the search.
+    pub forbid_rows: Vec<usize>,
+    /// Try the rows of every column in an order shuffled from this seed instead of top to bottom,
+    /// the same order for the same seed. Only affects the order solutions are found in.
+    pub row_seed: Option<u64>,
+    /// Hand back the solutions of the functions collecting them in canonical order, each solution's
+    /// rows ascending and the solutions sorted lexicographically, instead of the order of the search.
+    /// This order only depends on the set of solutions, so it stays the same across versions.
+    pub canonical_order: bool,
+    /// Solve without the copies of rows seen before, the functions collecting solutions handing back
+    /// every distinct solution once, using the first copy of every row.
+    pub dedup_rows: bool,
+    /// Permutations of the columns, each giving the column every column is moved to, that move every
+    /// row to a row, such as the rotations and reflections of a board. The functions of `Node`
+    /// enumerating the solutions of a matrix hand back only the first solution found of every orbit of
+    /// the group they generate, rows with the same columns counting as the same row, while counting and
+    /// the statistics still see every solution. Orbits can be as large as the group, so at most 10 000
+    /// solutions of one are looked at: a larger orbit may then be handed back more than once, which
+    /// `SolveStats::capped_orbits` tells, but two solutions that aren't symmetric never count as one.
+    pub symmetries: Vec<Vec<usize>>,
+    /// Report how far the search got every so many nodes of the search tree, when enumerating
+    /// solutions.
+    #[cfg(feature = "std")]
+    pub progress: Option<ProgressCallback>,
+}
+
+impl SolveOptions {
+    /// Check if there is a progress callback to report to.
+    #[cfg(feature = "std")]
+    pub(crate) fn reports_progress(&self) -> bool { self.progress.is_some() }
+
+    /// Check if there is a progress callback to report to, which there never is without `std`.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn reports_progress(&self) -> bool { false }
+}
+
+/// Where a search is in its tree, handed to a progress callback.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    /// Rows chosen on the way to the current node.
+    pub depth: usize,
+    /// For every chosen row, its index among the rows of the column branched on, and how many
+    /// rows that column had.
+    pub branches: Vec<(usize, usize)>,
+    /// Knuth's estimate of the part of the tree searched so far, between 0 and 1: the branches
+    /// before the current one at every level, weighing every branch of a level the same. It never
+    /// goes down as the search goes on.
+    pub fraction: f64,
+    /// Nodes of the search tree visited so far.
+    pub nodes: u64,
+}
+
+/// A function reporting progress, shared between the clones of the options.
+#[cfg(feature = "std")]
+type ProgressFn = Arc<Mutex<dyn FnMut(&Progress) + Send>>;
+
+/// A callback called with the progress of a search every `every` nodes of the search tree.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct ProgressCallback {
+    every: u64,
+    f: ProgressFn,
+}
+#[cfg(feature = "std")]
+impl ProgressCallback {
+    /// Call `f` every `every` nodes, which must be at least 1.
+    pub fn new(every: u64, f: impl FnMut(&Progress) + Send + 'static) -> ProgressCallback {
+        assert!(every > 0, "progress must be reported at least every node");
+        ProgressCallback { every, f: Arc::new(Mutex::new(f)) }
+    }
+}
+#[cfg(feature = "std")]
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressCallback").field("every", &self.every).finish_non_exhaustive()
+    }
+}
+
+/// What a search found, its solutions unless said otherwise, and whether it ran to the end.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveOutcome<T = Vec<Vec<usize>>> {
+    /// The search ran to the end, finding everything.
+    Complete(T),
+    /// The search was stopped early, this is what it found until then.
+    Interrupted(T),
+}
+impl<T> SolveOutcome<T> {
+    /// Check if the search ran to the end.
+    pub fn is_complete(&self) -> bool { matches!(self, SolveOutcome::Complete(_)) }
+
+    /// Take what the search found, complete or not.
+    pub fn into_inner(self) -> T {
+        match self {
+            SolveOutcome::Complete(value) | SolveOutcome::Interrupted(value) => value,
+        }
+    }
+}
+impl SolveOutcome {
+    /// The solutions found.
+    pub fn solutions(&self) -> &[Vec<usize>] {
+        match self {
+            SolveOutcome::Complete(solutions) | SolveOutcome::Interrupted(solutions) => solutions,
+        }
+    }
+
+    /// Take the solutions found.
+    pub fn into_solutions(self) -> Vec<Vec<usize>> {
+        self.into_inner()
+    }
+}
+
+/// Statistics on how much work a search did.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolveStats {
+    /// Nodes of the search tree visited, the root included.
+    pub nodes: u64,
+    /// Solutions found.
+    pub solutions: u64,
+    /// Rows tried while simulating columns for the lookahead strategy.
+    pub lookahead_rows: u64,
+    /// Subproblems answered from the memo table instead of being searched again.
+    pub memo_hits: u64,
+    /// Rows given up on right after choosing them, for leaving a column without rows.
+    pub early_backtracks: u64,
+    /// Column headers, or buckets of them, looked at while picking columns to branch on.
+    pub headers_scanned: u64,
+    /// Columns covered, when branching on them or choosing rows, lookahead simulations aside.
+    pub covers: u64,
+    /// Nodes of the search tree with no row to try, the column to branch on having none left.
+    pub backtracks: u64,
+    /// Most rows in a partial solution, the depth of the deepest node of the search tree.
+    pub max_depth: usize,
+    /// Columns left out of the structure by `merge_duplicate_columns` for being copies of an earlier
+    /// one, along with that one, ascending.
+    pub merged_columns: Vec<(usize, usize)>,
+    /// Solutions found whose orbit under the symmetries of the options was too large to search whole.
+    /// These may be handed back along with solutions symmetric to them.
+    pub capped_orbits: u64,
+}
+
+/// Marks a column filed under no size.
+const UNFILED: usize = usize::MAX;
+
+/// The active columns grouped by size in ordered sets, to find the smallest one without scanning them
+/// all. Filing a column under another size takes logarithmic time.
+struct ColumnIndex {
+    /// The columns of every size, by header.
+    buckets: Vec<BTreeSet<usize>>,
+    /// The size every column is filed under, by header, `UNFILED` for one that isn't.
+    sizes: Vec<usize>,
+    /// Whether every column was active when indexing, the only ones ever filed again.
+    members: Vec<bool>,
+}
+impl ColumnIndex {
+    /// Index the active columns of a structure.
+    fn new(structure: &Structure) -> ColumnIndex {
+        let len = structure.walk(Structure::ROOT, Structure::right).max().map_or(0, |col| col + 1);
+        let mut index = ColumnIndex { buckets: Vec::new(), sizes: vec![UNFILED; len], members: vec![false; len] };
+        for col in structure.walk(Structure::ROOT, Structure::right) {
+            index.members[col] = true;
+            index.insert(col, structure.nodes[col].data);
+        }
+        index
+    }
+
+    /// File a column of some size again after uncovering it, unless it wasn't active when indexing,
+    /// like a secondary column.
+    fn restore(&mut self, col: usize, size: usize) {
+        if self.members.get(col) == Some(&true) { self.insert(col, size); }
+    }
+
+    /// File a column under a size.
+    fn insert(&mut self, col: usize, size: usize) {
+        if self.buckets.len() <= size { self.buckets.resize_with(size + 1, BTreeSet::new); }
+        self.buckets[size].insert(col);
+        self.sizes[col] = size;
+    }
+
+    /// Take a column out of the index, if it is in it.
+    fn remove(&mut self, col: usize) {
+        let Some(&size) = self.sizes.get(col).filter(|&&size| size != UNFILED) else { return };
+        self.buckets[size].remove(&col);
+        self.sizes[col] = UNFILED;
+    }
+
+    /// File an indexed column again after its size changed.
+    fn resize(&mut self, col: usize, size: usize) {
+        if self.sizes.get(col).is_some_and(|&filed| filed != UNFILED) {
+            self.remove(col);
+            self.insert(col, size);
+        }
+    }
+
+    /// Get the leftmost smallest column, the one with the smallest header in the first set that isn't
+    /// empty, counting the sets looked at and the column taken.
+    fn smallest(&self, scanned: &mut u64) -> Option<usize> {
+        let col = self.buckets.iter().inspect(|_| *scanned += 1).find_map(|bucket| bucket.first())?;
+        *scanned += 1;
+        Some(*col)
+    }
+}
+
+/// The rows of the column a node of the search tree branches on, in the order they are tried.
+enum Rows {
+    /// Top to bottom, holding the next row, or the header once all were tried.
+    Walk(usize),
+    /// Shuffled from the row seed.
+    Shuffled(alloc::vec::IntoIter<usize>),
+}
+impl Rows {
+    /// Get the next row of a column to try.
+    fn next(&mut self, structure: &Structure, col: usize) -> Option<usize> {
+        match self {
+            Rows::Walk(next) => {
+                if *next == col { return None; }
+                Some(core::mem::replace(next, structure.down(*next)))
+            }
+            Rows::Shuffled(rows) => rows.next(),
+        }
+    }
+}
+
+/// A node of the search tree being searched, branching on a column.
+struct Frame {
+    col: usize,
+    rows: Rows,
+    /// The row being tried, covered.
+    row: Option<usize>,
+    /// Rows tried so far, the one being tried included, out of the rows of the column.
+    tried: usize,
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    size: usize,
+    /// The state hash and solutions found before covering the column.
+    hash: u128,
+    solutions: u64,
+    /// Emptied columns recorded before and after covering the column.
+    emptied: usize,
+    covered: usize,
+}
+
+/// A node of the search tree being counted or turned into a diagram, branching on a column.
+struct Branch<T> {
+    col: usize,
+    /// The row being tried, covered, or the header before the first one.
+    row: usize,
+    /// The state hash and emptied columns recorded before covering the column.
+    hash: u128,
+    emptied: usize,
+    /// Emptied columns recorded after covering the column.
+    covered: usize,
+    /// What the rows tried so far add up to.
+    value: T,
+}
+
+/// A node of the search tree looking for the best cover or the fewest rows, branching on a column.
+struct CoverFrame {
+    col: usize,
+    /// The row being tried, or the header before the first one and after the last.
+    row: usize,
+    /// Columns covered by the rows chosen, for the best cover, and columns still active.
+    covered: usize,
+    remaining: usize,
+    /// Whether the branch leaving the column uncovered is being searched.
+    skipped: bool,
+}
+
+/// A search over a structure, keeping the partial solution and statistics, and telling its steps to
+/// an observer.
+pub(crate) struct Search<'a, O: SearchObserver = ()> {
+    structure: &'a mut Structure,
+    options: &'a SolveOptions,
+    solution: Vec<usize>,
+    /// Zobrist hash of the covered columns.
+    hash: u128,
+    memo: Option<MemoTable>,
+    /// The columns by size, for the bucketed strategy.
+    index: Option<ColumnIndex>,
+    /// Headers of the columns left without rows by covering, for early contradiction detection.
+    emptied: Vec<usize>,
+    /// Rows chosen on the way to the current node.
+    depth: usize,
+    /// State of the generator picking columns, for the random strategy.
+    rng: u64,
+    /// State of the generator shuffling rows.
+    row_rng: u64,
+    /// Rows chosen before the search, counting towards the bounds on solution size.
+    pub given: usize,
+    /// Whether the search stopped because it was cancelled, ran out of nodes, or passed its deadline.
+    pub interrupted: bool,
+    pub stats: SolveStats,
+    observer: O,
+}
+impl<'a> Search<'a> {
+    /// Create a search over a structure, reserving `depth` rows for the partial solution.
+    pub fn new(structure: &'a mut Structure, options: &'a SolveOptions, depth: usize) -> Search<'a> {
+        Search::observed(structure, options, depth, ())
+    }
+}
+impl<'a, O: SearchObserver> Search<'a, O> {
+    /// Create a search over a structure telling its steps to an observer, reserving `depth` rows for
+    /// the partial solution.
+    pub fn observed(structure: &'a mut Structure, options: &'a SolveOptions, depth: usize, observer: O) -> Search<'a, O> {
+        let index = (options.column_strategy == ColumnStrategy::Bucketed).then(|| ColumnIndex::new(structure));
+        let rng = match options.column_strategy {
+            ColumnStrategy::Random { seed } => seed,
+            _ => 0,
+        };
+        Search {
+            structure,
+            index,
+            emptied: Vec::new(),
+            depth: 0,
+            rng,
+            row_rng: options.row_seed.unwrap_or(0),
+            options,
+            solution: Vec::with_capacity(depth),
+            hash: 0,
+            // a subproblem has solutions or not depending on the rows chosen before it with bounds
+            memo: options.memo_capacity.filter(|_| options.min_rows.is_none() && options.max_rows.is_none()).map(MemoTable::new),
+            given: 0,
+            interrupted: false,
+            stats: SolveStats::default(),
+            observer,
+        }
+    }
+
+    /// Check if the search was cancelled, ran out of nodes, or passed its deadline, marking it
+    /// interrupted if so. Once interrupted, it stays so.
+    fn stopped(&mut self) -> bool {
+        if !self.interrupted && should_stop(self.options, self.stats.nodes) { self.interrupted = true; }
+        self.interrupted
+    }
+
+    /// Search solutions, handing each one to `on_solution` until it breaks.
+    /// The structure is fully uncovered again when this returns. The nodes of the search tree being
+    /// searched are kept in a stack of frames rather than the call stack, so any depth is fine.
+    pub fn run(&mut self, on_solution: &mut impl FnMut(&[usize]) -> ControlFlow<()>) -> ControlFlow<()> {
+        let mut frames = Vec::new();
+        let mut flow = self.enter(&mut frames, on_solution);
+
+        while let Some(mut frame) = frames.pop() {
+            // take back the row tried last
+            if let Some(row) = frame.row.take() {
+                self.uncover_row(row);
+                self.emptied.truncate(frame.covered);
+                self.solution.pop();
+            }
+
+            let next = if flow.is_break() { None } else { frame.rows.next(self.structure, frame.col) };
+            let Some(row) = next else {
+                self.uncover(frame.col);
+                self.emptied.truncate(frame.emptied);
+
+                // a fully searched subproblem without solutions never needs searching again
+                if flow.is_continue() && self.stats.solutions == frame.solutions && let Some(memo) = &mut self.memo {
+                    memo.insert(frame.hash, 0);
+                }
+                continue;
+            };
+
+            self.solution.push(self.structure.nodes[row].data);
+            self.observer.on_try_row(self.structure.nodes[row].data, self.depth);
+            self.cover_row(row);
+            let dead = self.dead_end(frame.emptied, row);
+            frame.row = Some(row);
+            frame.tried += 1;
+            frames.push(frame);
+
+            if !dead { flow = self.enter(&mut frames, on_solution); }
+        }
+        flow
+    }
+
+    /// Start on a node of the search tree: hand over the solution if it is one, or cover the column to
+    /// branch on and push a frame to try its rows.
+    fn enter(&mut self, frames: &mut Vec<Frame>, on_solution: &mut impl FnMut(&[usize]) -> ControlFlow<()>) -> ControlFlow<()> {
+        if self.stopped() { return ControlFlow::Break(()); }
+
+        self.visit();
+        #[cfg(feature = "std")]
+        if let Some(callback) = &self.options.progress && self.stats.nodes.is_multiple_of(callback.every) {
+            self.report(callback, frames);
+        }
+
+        if self.structure.is_solved() {
+            if self.too_small() { return ControlFlow::Continue(()); }
+            self.stats.solutions += 1;
+            #[cfg(feature = "log")]
+            log_solution(&self.stats);
+            self.observer.on_solution(&self.solution);
+            return on_solution(&self.solution);
+        }
+        if self.full() { return ControlFlow::Continue(()); }
+
+        if self.memo.as_ref().is_some_and(|memo| memo.get(self.hash) == Some(0)) {
+            self.stats.memo_hits += 1;
+            return ControlFlow::Continue(());
+        }
+
+        let Some(col) = self.choose_column() else { return ControlFlow::Continue(()) };
+        let (hash, solutions, emptied) = (self.hash, self.stats.solutions, self.emptied.len());
+        self.observer.on_choose_column(col - 1, self.structure.nodes[col].data);
+        #[cfg(feature = "log")]
+        log_column(col - 1, self.structure.nodes[col].data, &self.stats);
+        self.cover(col);
+
+        // all rows that have a one in this column
+        let rows = match self.options.row_seed {
+            Some(_) => Rows::Shuffled(self.shuffled_rows(col).into_iter()),
+            None => Rows::Walk(self.structure.down(col)),
+        };
+        let size = self.structure.nodes[col].data;
+        frames.push(Frame { col, rows, row: None, tried: 0, size, hash, solutions, emptied, covered: self.emptied.len() });
+        ControlFlow::Continue(())
+    }
+
+    /// Hand where the search is, from the rows being tried in the frames, to a progress callback.
+    #[cfg(feature = "std")]
+    fn report(&self, callback: &ProgressCallback, frames: &[Frame]) {
+        let branches: Vec<(usize, usize)> = frames.iter().map(|frame| (frame.tried - 1, frame.size)).collect();
+
+        // every branch of a level holds an equal share of the branch above it
+        let (mut fraction, mut share) = (0.0, 1.0);
+        for &(index, size) in &branches {
+            share /= size as f64;
+            fraction += index as f64 * share;
+        }
+
+        let progress = Progress { depth: self.depth, branches, fraction, nodes: self.stats.nodes };
+        (callback.f.lock().unwrap())(&progress);
+    }
+
+    /// Count solutions without collecting them, saturating at `u64::MAX`, or those found until the
+    /// search was interrupted. The structure is fully uncovered again when this returns. Like `run`, it
+    /// keeps the nodes of the search tree in a stack of frames.
+    pub fn count(&mut self) -> u64 {
+        let mut frames = Vec::new();
+        let mut count = self.enter_count(&mut frames);
+
+        while let Some(frame) = frames.last_mut() {
+            // take back the row tried last, adding the solutions below it
+            if frame.row != frame.col {
+                frame.value = frame.value.saturating_add(count);
+                self.uncover_row(frame.row);
+                self.emptied.truncate(frame.covered);
+            }
+
+            frame.row = self.structure.down(frame.row);
+            if frame.row == frame.col || self.interrupted {
+                let frame = frames.pop().unwrap();
+                self.uncover(frame.col);
+                self.emptied.truncate(frame.emptied);
+                // a subproblem searched in part only has part of its count
+                if !self.interrupted && let Some(memo) = &mut self.memo { memo.insert(frame.hash, frame.value); }
+                count = frame.value;
+                continue;
+            }
+
+            let (row, emptied) = (frame.row, frame.emptied);
+            self.cover_row(row);
+            count = if self.dead_end(emptied, row) { 0 } else { self.enter_count(&mut frames) };
+        }
+        count
+    }
+
+    /// Start counting a node of the search tree: get its count if it doesn't branch, or cover the
+    /// column to branch on and push a frame to try its rows.
+    fn enter_count(&mut self, frames: &mut Vec<Branch<u64>>) -> u64 {
+        if self.stopped() { return 0; }
+        self.visit();
+        if self.structure.is_solved() {
+            if self.too_small() { return 0; }
+            self.stats.solutions = self.stats.solutions.saturating_add(1);
+            return 1;
+        }
+        if self.full() { return 0; }
+
+        if let Some(count) = self.memo.as_ref().and_then(|memo| memo.get(self.hash)) {
+            self.stats.memo_hits += 1;
+            self.stats.solutions = self.stats.solutions.saturating_add(count);
+            return count;
+        }
+
+        let Some(col) = self.choose_column() else { return 0 };
+        frames.push(self.branch(col, 0));
+        0
+    }
+
+    /// Build the diagram of all solutions into `zdd`, or of those found until the search was
+    /// interrupted, returning its root node. Subproblems found in the memo table reuse their node. The
+    /// structure is fully uncovered again when this returns. Like `run`, it keeps the nodes of the
+    /// search tree in a stack of frames.
+    pub fn zdd(&mut self, zdd: &mut Zdd) -> usize {
+        let mut frames = Vec::new();
+        let mut node = self.enter_zdd(&mut frames);
+
+        while let Some(frame) = frames.last_mut() {
+            // take back the row tried last, keeping the node below it
+            if frame.row != frame.col {
+                frame.value.push((self.structure.nodes[frame.row].data, node));
+                self.uncover_row(frame.row);
+                self.emptied.truncate(frame.covered);
+            }
+
+            frame.row = self.structure.down(frame.row);
+            if frame.row == frame.col || self.interrupted {
+                let frame = frames.pop().unwrap();
+                self.uncover(frame.col);
+                self.emptied.truncate(frame.emptied);
+
+                // chain the rows of the column together, the first row being tried first
+                node = frame.value.into_iter().rev()
+                    .fold(Zdd::terminal(false), |lo, (row, hi)| zdd.make(row, lo, hi));
+                if !self.interrupted && let Some(memo) = &mut self.memo { memo.insert(frame.hash, node as u64); }
+                continue;
+            }
+
+            let (row, emptied) = (frame.row, frame.emptied);
+            self.cover_row(row);
+            node = if self.dead_end(emptied, row) { Zdd::terminal(false) } else { self.enter_zdd(&mut frames) };
+        }
+        node
+    }
+
+    /// Start building the diagram of a node of the search tree: get its node if it doesn't branch, or
+    /// cover the column to branch on and push a frame to try its rows.
+    fn enter_zdd(&mut self, frames: &mut Vec<Branch<Vec<(usize, usize)>>>) -> usize {
+        if self.stopped() { return Zdd::terminal(false); }
+        self.visit();
+        if self.structure.is_solved() {
+            return Zdd::terminal(true);
+        }
+
+        if let Some(node) = self.memo.as_ref().and_then(|memo| memo.get(self.hash)) {
+            self.stats.memo_hits += 1;
+            return node as usize;
+        }
+
+        let Some(col) = self.choose_column() else { return Zdd::terminal(false) };
+        frames.push(self.branch(col, Vec::new()));
+        Zdd::terminal(false)
+    }
+
+    /// Cover the column a node of the search tree branches on, getting the frame to try its rows.
+    fn branch<T>(&mut self, col: usize, value: T) -> Branch<T> {
+        let (hash, emptied) = (self.hash, self.emptied.len());
+        self.cover(col);
+        Branch { col, row: col, hash, emptied, covered: self.emptied.len(), value }
+    }
+
+    /// Find rows sharing no column that cover as many columns as possible, `covered` columns being
+    /// covered so far and `remaining` still active. The best rows found are kept in `best` with how many
+    /// columns they cover, and a subtree is cut once covering all its columns couldn't beat them. An
+    /// interrupted search leaves the best rows found until then. The structure is fully uncovered again
+    /// when this returns. Like `run`, it keeps the nodes of the search tree in a stack of frames.
+    pub fn max_cover(&mut self, covered: usize, remaining: usize, best: &mut (Vec<usize>, usize)) {
+        let mut frames = Vec::new();
+        self.enter_max_cover(covered, remaining, best, &mut frames);
+
+        while let Some(frame) = frames.last_mut() {
+            if frame.skipped {
+                self.uncover(frame.col);
+                frames.pop();
+                continue;
+            }
+            // take back the row tried last
+            if frame.row != frame.col {
+                self.uncover_row(frame.row);
+                self.solution.pop();
+            }
+
+            frame.row = self.structure.down(frame.row);
+            let (row, covered, remaining) = (frame.row, frame.covered, frame.remaining);
+            if row == frame.col || self.interrupted {
+                // or leave the column uncovered
+                frame.skipped = true;
+                self.enter_max_cover(covered, remaining - 1, best, &mut frames);
+                continue;
+            }
+
+            let size = self.structure.walk(row, Structure::right).count() + 1;
+            self.solution.push(self.structure.nodes[row].data);
+            self.cover_row(row);
+            self.enter_max_cover(covered + size, remaining - size, best, &mut frames);
+        }
+    }
+
+    /// Start on a node of the search tree looking for the best cover: keep the rows chosen if they
+    /// beat the best ones, and unless the subtree is cut, cover the column to branch on and push a
+    /// frame to try its rows.
+    fn enter_max_cover(&mut self, covered: usize, remaining: usize, best: &mut (Vec<usize>, usize), frames: &mut Vec<CoverFrame>) {
+        if self.stopped() { return; }
+        self.visit();
+        if covered > best.1 {
+            // the buffer of the best rows so far is reused
+            best.0.clear();
+            best.0.extend_from_slice(&self.solution);
+            best.1 = covered;
+        }
+        if remaining == 0 || covered + remaining <= best.1 { return; }
+
+        let Some(col) = self.choose_column() else { return };
+        self.cover(col);
+        frames.push(CoverFrame { col, row: col, covered, remaining, skipped: false });
+    }
+
+    /// Find a solution with the fewest rows, with `remaining` columns active and no row wider than
+    /// `widest`. The shortest solution found so far is kept in `best`, only replaced by a shorter one,
+    /// and a subtree is cut once the rows chosen and the fewest more needed to cover the columns left,
+    /// each as wide as the widest row, couldn't beat it. An interrupted search leaves the shortest
+    /// solution found until then. The structure is fully uncovered again when this returns. Like `run`,
+    /// it keeps the nodes of the search tree in a stack of frames.
+    pub fn fewest_rows(&mut self, remaining: usize, widest: usize, best: &mut Option<Vec<usize>>) {
+        let mut frames = Vec::new();
+        self.enter_fewest_rows(remaining, widest, best, &mut frames);
+
+        while let Some(frame) = frames.last_mut() {
+            // take back the row tried last
+            if frame.row != frame.col {
+                self.uncover_row(frame.row);
+                self.solution.pop();
+            }
+
+            frame.row = self.structure.down(frame.row);
+            let (row, remaining) = (frame.row, frame.remaining);
+            if row == frame.col || self.interrupted {
+                self.uncover(frame.col);
+                frames.pop();
+                continue;
+            }
+
+            let size = self.structure.walk(row, Structure::right).count() + 1;
+            self.solution.push(self.structure.nodes[row].data);
+            self.cover_row(row);
+            self.enter_fewest_rows(remaining - size, widest, best, &mut frames);
+        }
+    }
+
+    /// Start on a node of the search tree looking for the fewest rows: keep the solution if it is the
+    /// shortest yet, and unless the subtree is cut, cover the column to branch on and push a frame to
+    /// try its rows.
+    fn enter_fewest_rows(&mut self, remaining: usize, widest: usize, best: &mut Option<Vec<usize>>, frames: &mut Vec<CoverFrame>) {
+        if self.stopped() { return; }
+        self.visit();
+        if self.structure.is_solved() {
+            if best.as_ref().is_none_or(|best| self.solution.len() < best.len()) { *best = Some(self.solution.clone()); }
+            return;
+        }
+        if best.as_ref().is_some_and(|best| self.depth + remaining.div_ceil(widest) >= best.len()) { return; }
+
+        let Some(col) = self.choose_column() else { return };
+        self.cover(col);
+        frames.push(CoverFrame { col, row: col, covered: 0, remaining, skipped: false });
+    }
+
+    /// Estimate how many nodes the search tree has with Knuth's estimator: walk down from the root
+    /// `probes` times choosing a random row of every column branched on, and average the sums of the
+    /// products of the branching factors along the way. Rows are picked from the row generator, which
+    /// starts from `seed`. An interrupted search averages the walks finished until then. The structure
+    /// is fully uncovered after every walk.
+    pub fn estimate(&mut self, probes: usize, seed: u64) -> f64 {
+        self.row_rng = seed;
+        let (mut total, mut finished) = (0.0, 0);
+        let mut path = Vec::new();
+        for _ in 0..probes {
+            let (mut nodes, mut product) = (1.0, 1.0);
+            while !self.structure.is_solved() && !self.stopped() {
+                self.visit();
+                let Some(col) = self.choose_column() else { break };
+                let size = self.structure.nodes[col].data;
+                if size == 0 { break; }
+                product *= size as f64;
+                nodes += product;
+
+                self.row_rng = splitmix64(self.row_rng);
+                let row = self.structure.walk(col, Structure::down).nth(self.row_rng as usize % size).unwrap();
+                self.cover(col);
+                self.cover_row(row);
+                path.push((col, row));
+            }
+            for (col, row) in path.drain(..).rev() {
+                self.uncover_row(row);
+                self.uncover(col);
+            }
+            if self.interrupted { break; }
+            total += nodes;
+            finished += 1;
+        }
+        if finished == 0 { 0.0 } else { total / finished as f64 }
+    }
+
+    /// Check if the rows chosen are fewer than solutions need.
+    fn too_small(&self) -> bool {
+        self.options.min_rows.is_some_and(|min| self.given + self.depth < min)
+    }
+
+    /// Check if the rows chosen leave no room for another one.
+    fn full(&self) -> bool {
+        self.options.max_rows.is_some_and(|max| self.given + self.depth >= max)
+    }
+
+    /// Count a node of the search tree.
+    fn visit(&mut self) {
+        self.stats.nodes += 1;
+        #[cfg(feature = "log")]
+        if self.depth > self.stats.max_depth { log_depth(self.depth, &self.stats); }
+        self.stats.max_depth = self.stats.max_depth.max(self.depth);
+    }
+
+    /// Cover a column, keeping the state hash, column index and emptied columns up to date.
+    fn cover(&mut self, col: usize) {
+        self.stats.covers += 1;
+        self.observer.on_cover(col - 1);
+        self.hash ^= zobrist(col);
+        if self.index.is_none() && !self.options.early_contradiction { return self.structure.cover(col); }
+
+        if let Some(index) = &mut self.index { index.remove(col); }
+        let (structure, mut on_resize) = self.tracked();
+        structure.cover_with(col, &mut on_resize);
+    }
+
+    /// Uncover a column, keeping the state hash and column index up to date.
+    fn uncover(&mut self, col: usize) {
+        match &mut self.index {
+            Some(index) => {
+                self.structure.uncover_with(col, &mut |resized, header| index.resize(resized, header.data));
+                index.restore(col, self.structure.nodes[col].data);
+            }
+            None => self.structure.uncover(col),
+        }
+        self.hash ^= zobrist(col);
+        self.observer.on_uncover(col - 1);
+    }
+
+    /// Give a secondary column the color of a node, keeping the state hash, column index and emptied
+    /// columns up to date.
+    fn purify(&mut self, node: usize) {
+        self.hash ^= zobrist_colored(self.structure.column(node), self.structure.nodes[node].color);
+        let (structure, mut on_resize) = self.tracked();
+        structure.purify_with(node, &mut on_resize);
+    }
+
+    /// Undo giving a secondary column the color of a node.
+    fn unpurify(&mut self, node: usize) {
+        self.hash ^= zobrist_colored(self.structure.column(node), self.structure.nodes[node].color);
+        let (structure, mut on_resize) = self.tracked();
+        structure.unpurify_with(node, &mut on_resize);
+    }
+
+    /// Get the structure with a callback filing resized columns in the index and recording the ones
+    /// left empty.
+    fn tracked(&mut self) -> (&mut Structure, impl FnMut(usize, &Node) + '_) {
+        let (index, emptied, track) = (&mut self.index, &mut self.emptied, self.options.early_contradiction);
+        (&mut *self.structure, move |resized, header: &Node| {
+            if let Some(index) = index { index.resize(resized, header.data); }
+            // a secondary column, linked to itself, may stay empty
+            let secondary = header.r as usize == resized;
+            if track && header.data == 0 && !secondary { emptied.push(resized); }
+        })
+    }
+
+    /// Check if choosing a row, after emptied columns were recorded from `start` on, left a column
+    /// without rows that the row doesn't cover.
+    fn dead_end(&mut self, start: usize, row: usize) -> bool {
+        if self.emptied.len() == start { return false; }
+        let columns: Vec<usize> = self.structure.walk(row, Structure::right).map(|node| self.structure.column(node)).collect();
+        let dead = self.emptied[start..].iter().any(|col| !columns.contains(col));
+        if dead { self.stats.early_backtracks += 1; }
+        dead
+    }
+
+    /// Cover every other column of a chosen row, or give it the color of the row, keeping the state
+    /// hash up to date.
+    fn cover_row(&mut self, row: usize) {
+        self.depth += 1;
+        let mut node = self.structure.right(row);
+        while node != row {
+            match self.structure.nodes[node].color {
+                0 => self.cover(self.structure.column(node)),
+                SETTLED => {}
+                _ => self.purify(node),
+            }
+            node = self.structure.right(node);
+        }
+    }
+
+    /// Undo covering the other columns of a row, keeping the state hash up to date.
+    fn uncover_row(&mut self, row: usize) {
+        self.depth -= 1;
+        let mut node = self.structure.left(row);
+        while node != row {
+            match self.structure.nodes[node].color {
+                0 => self.uncover(self.structure.column(node)),
+                SETTLED => {}
+                _ => self.unpurify(node),
+            }
+            node = self.structure.left(node);
+        }
+    }
+
+    /// Get the rows of a column in a random order, shuffling them from the row generator.
+    fn shuffled_rows(&mut self, col: usize) -> Vec<usize> {
+        let mut rows: Vec<usize> = self.structure.walk(col, Structure::down).collect();
+        for i in (1..rows.len()).rev() {
+            self.row_rng = splitmix64(self.row_rng);
+            rows.swap(i, self.row_rng as usize % (i + 1));
+        }
+        rows
+    }
+
+    /// Pick the column to branch on, or `None` if no branch can succeed.
+    fn choose_column(&mut self) -> Option<usize> {
+        let col = self.pick_column();
+        if col.is_none_or(|col| self.structure.nodes[col].data == 0) { self.stats.backtracks += 1; }
+        col
+    }
+
+    /// Pick the column to branch on with the strategy of the options.
+    fn pick_column(&mut self) -> Option<usize> {
+        let structure = &*self.structure;
+        match self.options.column_strategy {
+            ColumnStrategy::Smallest => structure.walk(Structure::ROOT, Structure::right)
+                .inspect(|_| self.stats.headers_scanned += 1)
+                .min_by_key(|&col| structure.nodes[col].data),
+            ColumnStrategy::Lookahead { candidates } => self.lookahead(candidates.max(1)),
+            ColumnStrategy::Bucketed => self.index.as_ref()?.smallest(&mut self.stats.headers_scanned),
+            ColumnStrategy::First => {
+                self.stats.headers_scanned += 1;
+                structure.walk(Structure::ROOT, Structure::right).next()
+            }
+            ColumnStrategy::Random { .. } => {
+                let columns: Vec<usize> = structure.walk(Structure::ROOT, Structure::right).collect();
+                self.stats.headers_scanned += columns.len() as u64;
+                self.rng = splitmix64(self.rng);
+                columns.get(self.rng as usize % columns.len().max(1)).copied()
+            }
+        }
+    }
+
+    /// Branch on the column with the fewest viable rows out of the `candidates` smallest ones.
+    fn lookahead(&mut self, candidates: usize) -> Option<usize> {
+        let mut columns: Vec<usize> = self.structure.walk(Structure::ROOT, Structure::right).collect();
+        self.stats.headers_scanned += columns.len() as u64;
+        // stable, so the leftmost column wins on ties
+        columns.sort_by_key(|&col| self.structure.nodes[col].data);
+        columns.truncate(candidates);
+
+        let mut best: Option<(usize, usize)> = None;
+        for col in columns {
+            let viable = self.viable_rows(col);
+            if best.is_none_or(|(min, _)| viable < min) {
+                best = Some((viable, col));
+            }
+        }
+
+        // a column without viable rows is a dead end
+        best.filter(|(viable, _)| *viable > 0).map(|(_, col)| col)
+    }
+
+    /// Count the rows of a column that leave no other column empty once chosen.
+    fn viable_rows(&mut self, col: usize) -> usize {
+        let structure = &mut *self.structure;
+        structure.cover(col);
+
+        let mut viable = 0;
+        let mut row = structure.down(col);
+        while row != col {
+            self.stats.lookahead_rows += 1;
+            structure.cover_row(row);
+            if structure.walk(Structure::ROOT, Structure::right).all(|c| structure.nodes[c].data > 0) { viable += 1; }
+            structure.uncover_row(row);
+            row = structure.down(row);
+        }
+
+        structure.uncover(col);
+        viable
+    }
+}
@@ -0,0 +1,303 @@
+//! Solution containers and the bitset row representation.
+
+use alloc::{vec, vec::Vec};
+
+/// A set of row indices stored as a packed bitset of `u64` words, sized to the row count.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RowSet {
+    rows: usize,
+    words: Vec<u64>,
+}
+impl RowSet {
+    /// Create an empty set able to hold rows `0..rows`.
+    pub fn new(rows: usize) -> RowSet {
+        RowSet { rows, words: vec![0; rows.div_ceil(64)] }
+    }
+
+    /// Create a set from row indices. Panics if an index is not below `rows`.
+    pub fn from_indices(indices: &[usize], rows: usize) -> RowSet {
+        let mut set = RowSet::new(rows);
+        for &i in indices { set.insert(i); }
+        set
+    }
+
+    /// Create a set from packed words as given by `words`, able to hold rows `0..rows`. Panics if there
+    /// isn't a word for every 64 rows, or a row past them is in the set.
+    pub fn from_words(words: Vec<u64>, rows: usize) -> RowSet {
+        assert_eq!(words.len(), rows.div_ceil(64), "{} words for a set of {rows} rows", words.len());
+        let set = RowSet { rows, words };
+        if let Some(row) = set.iter().find(|&row| row >= rows) { panic!("row {row} out of range for a set of {rows} rows"); }
+        set
+    }
+
+    /// Take the packed words, bit `i % 64` of word `i / 64` being row `i`.
+    pub fn into_words(self) -> Vec<u64> { self.words }
+
+    /// Get the row indices in the set, in ascending order.
+    pub fn to_indices(&self) -> Vec<usize> {
+        self.iter().collect()
+    }
+
+    /// The amount of rows this set can hold.
+    pub fn capacity(&self) -> usize { self.rows }
+
+    /// The packed words, bit `i % 64` of word `i / 64` being row `i`.
+    pub fn words(&self) -> &[u64] { &self.words }
+
+    /// Add a row to the set.
+    pub fn insert(&mut self, row: usize) {
+        assert!(row < self.rows, "row {row} out of range for a set of {} rows", self.rows);
+        self.words[row / 64] |= 1 << (row % 64);
+    }
+
+    /// Check if a row is in the set.
+    pub fn contains(&self, row: usize) -> bool {
+        row < self.rows && self.words[row / 64] & (1 << (row % 64)) != 0
+    }
+
+    /// The amount of rows in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Check if the set has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Rows in both sets.
+    pub fn intersection(&self, other: &RowSet) -> RowSet {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Rows in either set.
+    pub fn union(&self, other: &RowSet) -> RowSet {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Rows in exactly one of the sets.
+    pub fn symmetric_difference(&self, other: &RowSet) -> RowSet {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    /// Iterate over the row indices in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            core::iter::from_fn(move || {
+                if word == 0 { return None; }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    /// Combine two sets word by word. Both sets must have the same capacity.
+    fn zip_with(&self, other: &RowSet, f: impl Fn(u64, u64) -> u64) -> RowSet {
+        assert_eq!(self.rows, other.rows, "row sets have different capacities");
+        RowSet {
+            rows: self.rows,
+            words: self.words.iter().zip(&other.words).map(|(&a, &b)| f(a, b)).collect(),
+        }
+    }
+}
+
+/// How a solution set stores its solutions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SolutionStorage {
+    /// Row indices in the order the search chose them.
+    Indices,
+    /// One packed bitset per solution.
+    Bitsets,
+}
+
+/// Index solutions live back to back in `data`, solution `i` being `data[offsets[i]..offsets[i + 1]]`,
+/// so collecting a solution is a copy into one shared buffer instead of an allocation of its own.
+#[derive(Clone, Debug)]
+enum Storage {
+    Indices { data: Vec<usize>, offsets: Vec<usize> },
+    Bitsets(Vec<RowSet>),
+}
+
+/// The solutions of a problem, stored either as row indices or as bitsets over the rows.
+#[derive(Clone, Debug)]
+pub struct SolutionSet {
+    rows: usize,
+    storage: Storage,
+}
+impl SolutionSet {
+    /// Create an empty solution set for a problem with `rows` rows.
+    pub fn new(rows: usize, storage: SolutionStorage) -> SolutionSet {
+        let storage = match storage {
+            SolutionStorage::Indices => Storage::Indices { data: Vec::new(), offsets: vec![0] },
+            SolutionStorage::Bitsets => Storage::Bitsets(Vec::new()),
+        };
+        SolutionSet { rows, storage }
+    }
+
+    /// Wrap solutions given as row indices.
+    pub fn from_indices(solutions: Vec<Vec<usize>>, rows: usize) -> SolutionSet {
+        let mut set = SolutionSet::new(rows, SolutionStorage::Indices);
+        for solution in &solutions { set.push(solution); }
+        set
+    }
+
+    /// Wrap solutions given as bitsets.
+    pub fn from_bitsets(solutions: Vec<RowSet>, rows: usize) -> SolutionSet {
+        SolutionSet { rows, storage: Storage::Bitsets(solutions) }
+    }
+
+    /// Add a solution given as row indices.
+    pub fn push(&mut self, solution: &[usize]) {
+        match &mut self.storage {
+            Storage::Indices { data, offsets } => {
+                data.extend_from_slice(solution);
+                offsets.push(data.len());
+            }
+            Storage::Bitsets(solutions) => solutions.push(RowSet::from_indices(solution, self.rows)),
+        }
+    }
+
+    /// The amount of rows of the problem the solutions come from.
+    pub fn rows(&self) -> usize { self.rows }
+
+    /// The storage currently used.
+    pub fn storage(&self) -> SolutionStorage {
+        match self.storage {
+            Storage::Indices { .. } => SolutionStorage::Indices,
+            Storage::Bitsets(_) => SolutionStorage::Bitsets,
+        }
+    }
+
+    /// The amount of solutions.
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Indices { offsets, .. } => offsets.len() - 1,
+            Storage::Bitsets(solutions) => solutions.len(),
+        }
+    }
+
+    /// Check if there are no solutions.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Get a solution as row indices. Bitset solutions come out in ascending order.
+    pub fn indices(&self, i: usize) -> Vec<usize> {
+        match &self.storage {
+            Storage::Indices { data, offsets } => data[offsets[i]..offsets[i + 1]].to_vec(),
+            Storage::Bitsets(solutions) => solutions[i].to_indices(),
+        }
+    }
+
+    /// Get the flat index storage as `(data, offsets)`, solution `i` being `data[offsets[i]..offsets[i + 1]]`.
+    /// Returns `None` for bitset storage.
+    pub fn flat(&self) -> Option<(&[usize], &[usize])> {
+        match &self.storage {
+            Storage::Indices { data, offsets } => Some((data, offsets)),
+            Storage::Bitsets(_) => None,
+        }
+    }
+
+    /// Get a solution as a bitset.
+    pub fn bitset(&self, i: usize) -> RowSet {
+        match &self.storage {
+            Storage::Indices { data, offsets } => RowSet::from_indices(&data[offsets[i]..offsets[i + 1]], self.rows),
+            Storage::Bitsets(solutions) => solutions[i].clone(),
+        }
+    }
+
+    /// Convert into solutions given as row indices.
+    pub fn into_indices(self) -> Vec<Vec<usize>> {
+        match self.storage {
+            Storage::Indices { data, offsets } => offsets.windows(2).map(|w| data[w[0]..w[1]].to_vec()).collect(),
+            Storage::Bitsets(solutions) => solutions.iter().map(RowSet::to_indices).collect(),
+        }
+    }
+
+    /// Convert into solutions given as bitsets.
+    pub fn into_bitsets(self) -> Vec<RowSet> {
+        match self.storage {
+            Storage::Indices { data, offsets } => offsets.windows(2)
+                .map(|w| RowSet::from_indices(&data[w[0]..w[1]], self.rows))
+                .collect(),
+            Storage::Bitsets(solutions) => solutions,
+        }
+    }
+
+    /// Put the solutions in canonical order: the rows of every solution ascending, and the solutions
+    /// sorted lexicographically, whatever order the search found them in.
+    pub fn sort(&mut self) {
+        let storage = self.storage();
+        let set = core::mem::replace(self, SolutionSet::new(self.rows, storage));
+        let mut solutions = set.into_indices();
+        sort_solutions(&mut solutions);
+        *self = SolutionSet::from_indices(solutions, self.rows).convert(storage);
+    }
+
+    /// Switch to another storage.
+    pub fn convert(self, storage: SolutionStorage) -> SolutionSet {
+        let rows = self.rows;
+        match storage {
+            SolutionStorage::Indices => SolutionSet::from_indices(self.into_indices(), rows),
+            SolutionStorage::Bitsets => SolutionSet::from_bitsets(self.into_bitsets(), rows),
+        }
+    }
+}
+
+/// Sort the rows of every solution ascending, then the solutions lexicographically.
+pub(crate) fn sort_solutions(solutions: &mut [Vec<usize>]) {
+    for solution in solutions.iter_mut() { solution.sort_unstable(); }
+    solutions.sort_unstable();
+}
+
+/// How different two solutions are, for picking diverse ones.
+#[derive(Clone, Copy)]
+pub enum DiversityMetric<'a> {
+    /// The amount of rows in exactly one of the two solutions.
+    SymmetricDifference,
+    /// A distance between two solutions given as row indices.
+    Custom(&'a dyn Fn(&[usize], &[usize]) -> usize),
+}
+
+impl SolutionSet {
+    /// Pick up to `m` mutually different solutions, returning their indices in the set.
+    ///
+    /// Greedy farthest-point selection: start from the solution farthest from the first one, then keep
+    /// adding the solution farthest from everything picked so far. The lowest index wins all ties,
+    /// so the selection is deterministic.
+    pub fn select_diverse(&self, m: usize, metric: DiversityMetric) -> Vec<usize> {
+        match metric {
+            DiversityMetric::SymmetricDifference => {
+                let bitsets: Vec<RowSet> = (0..self.len()).map(|i| self.bitset(i)).collect();
+                farthest_points(self.len(), m, |a, b| bitsets[a].symmetric_difference(&bitsets[b]).len())
+            }
+            DiversityMetric::Custom(distance) => {
+                let solutions: Vec<Vec<usize>> = (0..self.len()).map(|i| self.indices(i)).collect();
+                farthest_points(self.len(), m, |a, b| distance(&solutions[a], &solutions[b]))
+            }
+        }
+    }
+}
+
+/// Greedily pick up to `m` of `n` points far apart from each other, the lowest index winning ties.
+pub(crate) fn farthest_points(n: usize, m: usize, distance: impl Fn(usize, usize) -> usize) -> Vec<usize> {
+    if n == 0 || m == 0 { return Vec::new(); }
+
+    // the farthest point from an arbitrary one is a good end of a long diameter
+    let first = (0..n).rev().max_by_key(|&i| distance(0, i)).unwrap();
+    let mut picked = vec![first];
+    let mut nearest: Vec<usize> = (0..n).map(|i| distance(first, i)).collect();
+
+    while picked.len() < m.min(n) {
+        let next = (0..n).rev()
+            .filter(|i| !picked.contains(i))
+            .max_by_key(|&i| nearest[i])
+            .unwrap();
+        picked.push(next);
+        for (i, d) in nearest.iter_mut().enumerate() {
+            *d = (*d).min(distance(next, i));
+        }
+    }
+
+    picked
+}
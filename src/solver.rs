@@ -0,0 +1,163 @@
+//! A problem built once into a structure of nodes, then solved as many times as needed.
+
+use alloc::{vec, vec::Vec};
+use core::ops::ControlFlow;
+use crate::{ConflictError, Node, SolveOptions, SolveStats, Structure, reduce};
+use crate::search::Search;
+use crate::solutions::sort_solutions;
+
+/// A row of the matrix of a solver, rows added to it numbering on from the rows it was built with.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RowId {
+    pub index: usize,
+}
+
+/// A problem built once into a structure of nodes and solved many times over it. Every solve covers
+/// and uncovers the same structure, leaving it as it was when it returns, so no call rebuilds anything.
+pub struct Solver {
+    matrix: Vec<Vec<bool>>,
+    options: SolveOptions,
+    structure: Structure,
+    /// The first node of every row, `None` for a row without entries.
+    first: Vec<Option<usize>>,
+    /// The columns left out of the structure for being copies of earlier ones, with those ones.
+    merged: Vec<(usize, usize)>,
+    stats: SolveStats,
+}
+impl Solver {
+    /// Build the structure of a matrix. Panics if the matrix has no rows or columns, or rows of
+    /// different widths.
+    pub fn new(input: &[Vec<bool>]) -> Solver {
+        Self::with_options(input, SolveOptions::default())
+    }
+
+    /// Build the structure of a matrix, every solve using the options, without the rows they forbid.
+    /// Panics if the matrix has no rows or columns, rows of different widths, or if the options
+    /// require or forbid rows that aren't in it, require and forbid a row, or require rows that
+    /// overlap.
+    pub fn with_options(input: &[Vec<bool>], options: SolveOptions) -> Solver {
+        let (mut structure, merged) = Node::build_for(input, &options);
+        Node::check_options(input, &options).unwrap_or_else(|error| panic!("invalid options: {error}"));
+        let first = structure.row_starts(input[0].len() - merged.len(), input.len());
+        structure.forbid(&first, &options.forbid_rows);
+
+        Solver { matrix: input.to_vec(), options, structure, first, merged, stats: SolveStats::default() }
+    }
+
+    /// Add a row covering some columns, at the bottom of each of them, returning its index. Columns
+    /// listed twice are covered once. Panics if a column isn't in the matrix, or if the options merge
+    /// duplicate columns, as a new row could tell them apart.
+    pub fn add_row(&mut self, cols: &[usize]) -> RowId {
+        assert!(!self.options.merge_duplicate_columns, "rows can't be added with duplicate columns merged");
+        let mut row = vec![false; self.matrix[0].len()];
+        for &col in cols {
+            assert!(col < row.len(), "column {col} isn't in the matrix");
+            row[col] = true;
+        }
+
+        let index = self.matrix.len();
+        let entries = row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| (x, 0));
+        self.first.push(self.structure.append_row(index, entries));
+        self.matrix.push(row);
+        RowId { index }
+    }
+
+    /// Take a row out of every column. It keeps its index, left without columns, so no search chooses
+    /// it again. Panics if the row isn't in the matrix or the options require it.
+    pub fn remove_row(&mut self, row: RowId) {
+        let RowId { index } = row;
+        assert!(index < self.matrix.len(), "row {index} isn't in the matrix");
+        assert!(!self.options.require_rows.contains(&index), "row {index} is required by the options");
+
+        // a forbidden row is already out of its columns
+        if let Some(first) = self.first[index].take() && !self.options.forbid_rows.contains(&index) {
+            self.structure.hide_row(first);
+        }
+        self.matrix[index].fill(false);
+    }
+
+    /// Statistics on the last solve.
+    pub fn stats(&self) -> &SolveStats {
+        &self.stats
+    }
+
+    /// Find all solutions returning indices.
+    pub fn solve_all(&mut self) -> Vec<Vec<usize>> {
+        let mut results = Vec::new();
+        self.run(&[], |solution| {
+            results.push(solution.to_vec());
+            ControlFlow::Continue(())
+        });
+
+        if self.options.canonical_order { sort_solutions(&mut results); }
+        results
+    }
+
+    /// Find one solution returning indices.
+    pub fn solve_once(&mut self) -> Option<Vec<usize>> {
+        let mut result = None;
+        self.run(&[], |solution| {
+            result = Some(solution.to_vec());
+            ControlFlow::Break(())
+        });
+        result
+    }
+
+    /// Count the solutions without collecting them, saturating at `u64::MAX`.
+    pub fn count_solutions(&mut self) -> u64 {
+        self.with_givens(&[], |search| search.count())
+    }
+
+    /// Find all solutions that include the given rows, the rows the options require first, then the
+    /// given rows, like `Node::solve_all_with_givens`. Errors with the clashing column if two given or
+    /// required rows overlap. Panics if a given row is out of range.
+    pub fn solve_with_givens(&mut self, givens: &[usize]) -> Result<Vec<Vec<usize>>, ConflictError> {
+        let chosen: Vec<usize> = self.options.require_rows.iter().chain(givens).copied().collect();
+        reduce::covered_by(&self.matrix, &chosen)?;
+
+        let mut results = Vec::new();
+        self.run(givens, |solution| {
+            results.push(solution.to_vec());
+            ControlFlow::Continue(())
+        });
+        Ok(results)
+    }
+
+    /// Search with the required and given rows chosen first, handing every solution, those rows
+    /// included, to `f` until it breaks.
+    fn run(&mut self, givens: &[usize], mut f: impl FnMut(&[usize]) -> ControlFlow<()>) {
+        let chosen: Vec<usize> = self.options.require_rows.iter().chain(givens).copied().collect();
+        let mut full = chosen.clone();
+        self.with_givens(givens, |search| {
+            let _ = search.run(&mut |solution| {
+                full.truncate(chosen.len());
+                full.extend_from_slice(solution);
+                f(&full)
+            });
+        });
+    }
+
+    /// Hand a search to `f` with the columns of the required and given rows covered, then undo every
+    /// cover.
+    fn with_givens<T>(&mut self, givens: &[usize], f: impl FnOnce(&mut Search) -> T) -> T {
+        // an empty row has no node, and nothing to cover
+        let rows = self.options.require_rows.iter().chain(givens);
+        let given: Vec<usize> = rows.filter_map(|&row| self.first[row]).collect();
+        for &node in &given {
+            self.structure.cover(self.structure.column(node));
+            self.structure.cover_row(node);
+        }
+
+        let mut search = Search::new(&mut self.structure, &self.options, Node::max_depth(&self.matrix));
+        search.given = self.options.require_rows.len() + givens.len();
+        search.stats.merged_columns = self.merged.clone();
+        let result = f(&mut search);
+        self.stats = search.stats;
+
+        for &node in given.iter().rev() {
+            self.structure.uncover_row(node);
+            self.structure.uncover(self.structure.column(node));
+        }
+        result
+    }
+}
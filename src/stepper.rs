@@ -0,0 +1,150 @@
+//! The search taken one step at a time, for showing it as it goes.
+
+use alloc::{vec, vec::Vec};
+use crate::{Node, Structure};
+
+/// A step of the search taken by `Stepper::step`. Columns and rows are counted from 0, as in the matrix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StepEvent {
+    /// The search branches on a column with `size` rows left.
+    ChoseColumn { col: usize, size: usize },
+    /// The search tries a row, with `depth` rows chosen before it.
+    TriedRow { row: usize, depth: usize },
+    /// A column was covered, by branching on it or by choosing a row in it.
+    Covered { col: usize },
+    /// A column was uncovered, in the reverse order it was covered.
+    Uncovered { col: usize },
+    /// The rows chosen make up a solution.
+    FoundSolution(Vec<usize>),
+    /// The search is over, and every later step is this.
+    Done,
+}
+
+/// What the next step does.
+#[derive(Clone, Copy)]
+enum Phase {
+    /// Hand over the solution if the rows chosen are one, or choose the column to branch on.
+    Enter,
+    /// Cover the column just chosen.
+    CoverColumn(usize),
+    /// Try the next row of the column branched on last, or uncover it once all were tried.
+    Advance,
+    /// Cover the column of the node right of `node`, in the row tried.
+    CoverRow { row: usize, node: usize },
+    /// Uncover the column of the node left of `node`, in the row tried.
+    UncoverRow { row: usize, node: usize },
+    Done,
+}
+
+/// A node of the search tree, branching on a column.
+struct Frame {
+    col: usize,
+    /// The row being tried.
+    row: Option<usize>,
+    /// The next row to try, or the header once all were.
+    next: usize,
+}
+
+/// The search over the solutions of a problem taken one step at a time, in the order of
+/// `Node::solve_all`, for instance to show it in a user interface. The rows chosen and the sizes of the
+/// columns can be looked at between steps.
+pub struct Stepper {
+    structure: Structure,
+    width: usize,
+    frames: Vec<Frame>,
+    phase: Phase,
+}
+impl Stepper {
+    /// Start on the search of a matrix. Panics if the matrix has no rows or columns, or rows of
+    /// different widths.
+    pub fn new(input: &[Vec<bool>]) -> Stepper {
+        Stepper { structure: Node::build(input), width: input[0].len(), frames: Vec::new(), phase: Phase::Enter }
+    }
+
+    /// Take the next step of the search.
+    pub fn step(&mut self) -> StepEvent {
+        loop {
+            match self.phase {
+                Phase::Enter => {
+                    if self.structure.is_solved() {
+                        self.phase = Phase::Advance;
+                        return StepEvent::FoundSolution(self.partial_solution());
+                    }
+                    // the leftmost column with the fewest rows, even if it has none
+                    let col = self.structure.walk(Structure::ROOT, Structure::right)
+                        .min_by_key(|&col| self.structure.nodes[col].data)
+                        .unwrap();
+                    self.frames.push(Frame { col, row: None, next: self.structure.down(col) });
+                    self.phase = Phase::CoverColumn(col);
+                    return StepEvent::ChoseColumn { col: col - 1, size: self.structure.nodes[col].data };
+                }
+                Phase::CoverColumn(col) => {
+                    self.structure.cover(col);
+                    self.phase = Phase::Advance;
+                    return StepEvent::Covered { col: col - 1 };
+                }
+                Phase::Advance => {
+                    let depth = self.frames.len().saturating_sub(1);
+                    let Some(frame) = self.frames.last_mut() else {
+                        self.phase = Phase::Done;
+                        return StepEvent::Done;
+                    };
+                    if let Some(row) = frame.row {
+                        self.phase = Phase::UncoverRow { row, node: row };
+                        continue;
+                    }
+                    let (col, row) = (frame.col, frame.next);
+                    if row == col {
+                        self.frames.pop();
+                        self.structure.uncover(col);
+                        return StepEvent::Uncovered { col: col - 1 };
+                    }
+
+                    frame.row = Some(row);
+                    frame.next = self.structure.down(row);
+                    self.phase = Phase::CoverRow { row, node: row };
+                    return StepEvent::TriedRow { row: self.structure.nodes[row].data, depth };
+                }
+                Phase::CoverRow { row, node } => {
+                    let node = self.structure.right(node);
+                    if node == row {
+                        self.phase = Phase::Enter;
+                        continue;
+                    }
+                    let col = self.structure.column(node);
+                    self.structure.cover(col);
+                    self.phase = Phase::CoverRow { row, node };
+                    return StepEvent::Covered { col: col - 1 };
+                }
+                Phase::UncoverRow { row, node } => {
+                    let node = self.structure.left(node);
+                    if node == row {
+                        // the row taken back was tried by the last frame
+                        self.frames.last_mut().unwrap().row = None;
+                        self.phase = Phase::Advance;
+                        continue;
+                    }
+                    let col = self.structure.column(node);
+                    self.structure.uncover(col);
+                    self.phase = Phase::UncoverRow { row, node };
+                    return StepEvent::Uncovered { col: col - 1 };
+                }
+                Phase::Done => return StepEvent::Done,
+            }
+        }
+    }
+
+    /// Get the rows chosen so far, from the first one.
+    pub fn partial_solution(&self) -> Vec<usize> {
+        self.frames.iter().filter_map(|frame| frame.row).map(|row| self.structure.nodes[row].data).collect()
+    }
+
+    /// Get the number of rows left in every column, or `None` for a covered column.
+    pub fn column_sizes(&self) -> Vec<Option<usize>> {
+        let mut sizes = vec![None; self.width];
+        for col in self.structure.walk(Structure::ROOT, Structure::right) {
+            sizes[col - 1] = Some(self.structure.nodes[col].data);
+        }
+        sizes
+    }
+}
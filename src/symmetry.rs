@@ -0,0 +1,60 @@
+//! Solutions up to symmetries of the matrix, given as permutations of its columns.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use hashbrown::{HashMap, HashSet};
+use crate::DlxError;
+
+/// Most solutions the orbit of a solution is searched for before settling on the smallest found so far.
+const MAX_ORBIT: usize = 10_000;
+
+/// Get the row every row is moved to by every symmetry, moving the columns of a row to give the
+/// columns of another. Rows with the same columns are moved to the first of them. Errors if a symmetry
+/// isn't a permutation of the columns, or moves a row to columns no row has.
+pub(crate) fn row_maps(input: &[Vec<bool>], symmetries: &[Vec<usize>]) -> Result<Vec<Vec<usize>>, DlxError> {
+    let width = input[0].len();
+    let rows: Vec<Vec<usize>> = input.iter()
+        .map(|row| row.iter().enumerate().filter(|(_, val)| **val).map(|(x, _)| x).collect())
+        .collect();
+    let mut by_columns = HashMap::with_capacity(rows.len());
+    for (y, columns) in rows.iter().enumerate() { by_columns.entry(columns).or_insert(y); }
+
+    symmetries.iter().enumerate().map(|(symmetry, permutation)| {
+        let mut seen = HashSet::with_capacity(width);
+        if permutation.len() != width || !permutation.iter().all(|&x| x < width && seen.insert(x)) {
+            return Err(DlxError::NotAPermutation { symmetry });
+        }
+
+        rows.iter().enumerate().map(|(row, columns)| {
+            let mut moved: Vec<usize> = columns.iter().map(|&x| permutation[x]).collect();
+            moved.sort_unstable();
+            by_columns.get(&moved).copied().ok_or(DlxError::SymmetryBreaksRow { symmetry, row })
+        }).collect()
+    }).collect()
+}
+
+/// Get the smallest solution, its rows ascending, that the symmetries of `row_maps` move a solution to,
+/// looking at no more than `MAX_ORBIT` of them, and whether that was the whole orbit. If it wasn't, the
+/// solution found depends on the one given, so symmetric solutions may not get the same one.
+pub(crate) fn canonical(solution: &[usize], row_maps: &[Vec<usize>]) -> (Vec<usize>, bool) {
+    let mut start = solution.to_vec();
+    start.sort_unstable();
+    let mut orbit = HashSet::new();
+    orbit.insert(start.clone());
+    let mut queue = VecDeque::from([start]);
+    let mut whole = true;
+
+    'search: while let Some(current) = queue.pop_front() {
+        for map in row_maps {
+            let mut moved: Vec<usize> = current.iter().map(|&row| map[row]).collect();
+            moved.sort_unstable();
+            if orbit.contains(&moved) { continue; }
+            if orbit.len() >= MAX_ORBIT {
+                whole = false;
+                break 'search;
+            }
+            orbit.insert(moved.clone());
+            queue.push_back(moved);
+        }
+    }
+    (orbit.into_iter().min().unwrap(), whole)
+}
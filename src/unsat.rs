@@ -0,0 +1,118 @@
+//! Explaining why a problem has no solution.
+
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::ControlFlow;
+use crate::{Node, SolveOptions, presolve};
+use crate::search::Search;
+
+/// Why a problem has no solution, the simplest reason found.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnsatReason {
+    /// No row covers the column.
+    EmptyColumn { column: usize },
+    /// Choosing the rows forced by columns with a single row, in order, leaves the column without rows.
+    ForcedOut { column: usize, forced_rows: Vec<usize> },
+    /// No simpler reason was found, but searching every branch found no solution.
+    SearchExhausted { nodes_visited: u64 },
+}
+impl fmt::Display for UnsatReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnsatReason::EmptyColumn { column } => write!(f, "no row covers column {column}"),
+            UnsatReason::ForcedOut { column, forced_rows } => write!(f, "the forced rows {forced_rows:?} leave column {column} without rows"),
+            UnsatReason::SearchExhausted { nodes_visited } => write!(f, "no solution in a search of {nodes_visited} nodes"),
+        }
+    }
+}
+
+/// Explain why a problem has no solution, or give `None` if it has one.
+pub(crate) fn explain_unsat(input: &[Vec<bool>]) -> Option<UnsatReason> {
+    let width = input.first().map_or(0, Vec::len);
+    if let Some(column) = (0..width).find(|&x| input.iter().all(|row| !row[x])) {
+        return Some(UnsatReason::EmptyColumn { column });
+    }
+    if let (forced_rows, Some(column)) = presolve::force_singles(input) {
+        return Some(UnsatReason::ForcedOut { column, forced_rows });
+    }
+
+    let (solution, stats) = Node::solve_once_with_options(input, &SolveOptions::default());
+    solution.is_none().then_some(UnsatReason::SearchExhausted { nodes_visited: stats.nodes })
+}
+
+/// Check if a problem has no solution when only `columns` have to be covered exactly once, the
+/// others being ignored. Returns `None` if the search runs out of nodes before knowing.
+pub(crate) fn is_infeasible(input: &[Vec<bool>], columns: &[usize], node_budget: Option<u64>) -> Option<bool> {
+    // rows covering none of the columns can't make a difference
+    let matrix: Vec<Vec<bool>> = input.iter()
+        .map(|row| columns.iter().map(|&x| row[x]).collect::<Vec<bool>>())
+        .filter(|row| row.contains(&true))
+        .collect();
+
+    // a column without rows can never be covered
+    if (0..columns.len()).any(|x| matrix.iter().all(|row| !row[x])) { return Some(true); }
+    if matrix.is_empty() { return Some(false); }
+
+    let options = SolveOptions { node_limit: node_budget, ..Default::default() };
+    let mut structure = Node::build(&matrix);
+    let mut search = Search::new(&mut structure, &options, 0);
+    let flow = search.run(&mut |_| ControlFlow::Break(()));
+    match (flow, search.interrupted) {
+        (_, true) => None,
+        (ControlFlow::Break(()), false) => Some(false),
+        (ControlFlow::Continue(()), false) => Some(true),
+    }
+}
+
+/// Shrink the columns of an infeasible problem down to a subset that is still infeasible, but
+/// feasible without any one of its columns as far as the budget can tell.
+pub(crate) fn unsat_core(input: &[Vec<bool>], node_budget: Option<u64>) -> Option<Vec<usize>> {
+    let width = input.first().map_or(0, Vec::len);
+    let mut core: Vec<usize> = (0..width).collect();
+    if is_infeasible(input, &core, node_budget) != Some(true) { return None; }
+
+    let mut i = 0;
+    while i < core.len() {
+        let without: Vec<usize> = core.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &x)| x).collect();
+        // the column is only dropped once the rest is known to be infeasible
+        if is_infeasible(input, &without, node_budget) == Some(true) {
+            core = without;
+        } else {
+            i += 1;
+        }
+    }
+
+    Some(core)
+}
+
+/// Find the fewest columns out of `candidates`, at most `max_drop` of them, that make a problem
+/// feasible once they only have to be covered at most once. Sets of the same size are tried in
+/// lexicographic order, each check being a search of at most `node_budget` nodes.
+pub(crate) fn relaxations(input: &[Vec<bool>], candidates: &[usize], max_drop: usize, node_budget: Option<u64>) -> Option<Vec<usize>> {
+    let width = input.first().map_or(0, Vec::len);
+    let columns: Vec<usize> = (0..width).collect();
+
+    for size in 0..=max_drop.min(candidates.len()) {
+        // positions in `candidates` of the current set
+        let mut picks: Vec<usize> = (0..size).collect();
+        loop {
+            // a row of its own lets a column stay uncovered
+            let mut matrix = input.to_vec();
+            for &i in &picks {
+                let mut filler = vec![false; width];
+                filler[candidates[i]] = true;
+                matrix.push(filler);
+            }
+            if is_infeasible(&matrix, &columns, node_budget) == Some(false) {
+                return Some(picks.iter().map(|&i| candidates[i]).collect());
+            }
+
+            // move on to the next set of the same size
+            let Some(i) = (0..size).rev().find(|&i| picks[i] < candidates.len() - size + i) else { break };
+            picks[i] += 1;
+            for j in i + 1..size { picks[j] = picks[j - 1] + 1; }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,46 @@
+//! Checking that rows make up a solution.
+
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// Why rows don't make up a solution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyError {
+    /// No row covers a primary column.
+    Uncovered(usize),
+    /// Two rows both cover a column.
+    DoublyCovered { col: usize, rows: (usize, usize) },
+    /// A row isn't in the matrix.
+    RowOutOfRange(usize),
+}
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::Uncovered(col) => write!(f, "column {col} isn't covered"),
+            VerifyError::DoublyCovered { col, rows } => write!(f, "rows {} and {} both cover column {col}", rows.0, rows.1),
+            VerifyError::RowOutOfRange(row) => write!(f, "row {row} isn't in the matrix"),
+        }
+    }
+}
+impl core::error::Error for VerifyError {}
+
+/// Check that rows cover every column at most once, and the ones before `primary` exactly once.
+pub(crate) fn verify(input: &[Vec<bool>], rows: &[usize], primary: usize) -> Result<(), VerifyError> {
+    if let Some(&row) = rows.iter().find(|&&row| row >= input.len()) { return Err(VerifyError::RowOutOfRange(row)); }
+
+    let width = input.first().map_or(0, Vec::len);
+    let mut covered_by: Vec<Option<usize>> = vec![None; width];
+    for &row in rows {
+        for (col, _) in input[row].iter().enumerate().filter(|(_, val)| **val) {
+            if let Some(other) = covered_by[col] {
+                return Err(VerifyError::DoublyCovered { col, rows: (other, row) });
+            }
+            covered_by[col] = Some(row);
+        }
+    }
+
+    match covered_by.iter().take(primary).position(Option::is_none) {
+        Some(col) => Err(VerifyError::Uncovered(col)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,45 @@
+//! Exact covering with colors: rows giving secondary columns a color may share them as long as they
+//! agree on it.
+
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use crate::{SETTLED, SolveOptions, Structure};
+use crate::search::Search;
+
+/// A row of a problem with colors.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct XccRow {
+    /// The primary columns the row covers, each to be covered exactly once.
+    pub primary: Vec<usize>,
+    /// The secondary columns the row covers, with the color it gives them. Rows giving one the same
+    /// color may be chosen together, color 0 meaning none: the row then excludes every other row
+    /// with the column.
+    pub secondary: Vec<(usize, u32)>,
+}
+
+/// Find all solutions to a problem with colors, following Knuth's algorithm C: choosing a row covers
+/// its primary columns and purifies its colored secondary columns, hiding only the rows giving them
+/// another color. Panics if a column is out of range, or a color is `u32::MAX`.
+pub(crate) fn solve_xcc(rows: &[XccRow], primary: usize, secondary: usize) -> Vec<Vec<usize>> {
+    for row in rows {
+        if let Some(x) = row.primary.iter().find(|&&x| x >= primary) { panic!("primary column {x} out of range"); }
+        if let Some((x, _)) = row.secondary.iter().find(|(x, _)| *x >= secondary) { panic!("secondary column {x} out of range"); }
+        assert!(row.secondary.iter().all(|&(_, color)| color != SETTLED), "color {SETTLED} is reserved");
+    }
+
+    let entries = rows.iter().map(|row| row.primary.len() + row.secondary.len()).sum();
+    let lists = rows.iter().map(|row| {
+        let primaries = row.primary.iter().map(|&x| (x, 0));
+        primaries.chain(row.secondary.iter().map(move |&(x, color)| (primary + x, color)))
+    });
+    let mut structure = Structure::from_rows(primary + secondary, primary, entries, lists);
+
+    let options = SolveOptions::default();
+    let mut search = Search::new(&mut structure, &options, rows.len().min(primary));
+    let mut results = Vec::new();
+    let _ = search.run(&mut |solution| {
+        results.push(solution.to_vec());
+        ControlFlow::Continue(())
+    });
+    results
+}
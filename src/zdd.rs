@@ -0,0 +1,242 @@
+//! Zero-suppressed decision diagrams holding whole families of solutions.
+
+use alloc::collections::BinaryHeap;
+use alloc::{vec, vec::Vec};
+use core::cmp::Reverse;
+use hashbrown::HashMap;
+use crate::memo::splitmix64;
+
+/// The diagram node for the empty family.
+const FALSE: usize = 0;
+/// The diagram node for the family holding only the empty solution.
+const TRUE: usize = 1;
+
+#[derive(Clone, Copy, Debug)]
+struct ZddNode {
+    row: usize,
+    lo: usize,
+    hi: usize,
+}
+
+/// A family of solutions stored as a zero-suppressed decision diagram.
+///
+/// A node stands for the solutions of its `lo` branch, plus those of its `hi` branch with its row
+/// added. Diagrams built by the solver branch on the rows of one column at a time, so subproblems
+/// reached in several ways are shared and the diagram can be exponentially smaller than the list of
+/// solutions. A row appears at most once on any path, so the solutions of different branches never
+/// overlap.
+#[derive(Clone, Debug)]
+pub struct Zdd {
+    nodes: Vec<ZddNode>,
+    unique: HashMap<(usize, usize, usize), usize>,
+    root: usize,
+}
+impl Default for Zdd {
+    fn default() -> Zdd { Zdd::new() }
+}
+impl Zdd {
+    /// Create a diagram of the empty family.
+    pub fn new() -> Zdd {
+        let terminal = ZddNode { row: usize::MAX, lo: FALSE, hi: FALSE };
+        Zdd { nodes: vec![terminal, terminal], unique: HashMap::new(), root: FALSE }
+    }
+
+    /// Get the node for `lo ∪ (hi with row added)`, sharing existing nodes.
+    pub(crate) fn make(&mut self, row: usize, lo: usize, hi: usize) -> usize {
+        // zero suppression: adding a row to nothing is nothing
+        if hi == FALSE { return lo; }
+
+        *self.unique.entry((row, lo, hi)).or_insert_with(|| {
+            self.nodes.push(ZddNode { row, lo, hi });
+            self.nodes.len() - 1
+        })
+    }
+
+    pub(crate) fn set_root(&mut self, root: usize) { self.root = root; }
+
+    pub(crate) const fn terminal(accept: bool) -> usize { if accept { TRUE } else { FALSE } }
+
+    /// The amount of nodes in the diagram, terminals included.
+    pub fn node_count(&self) -> usize { self.nodes.len() }
+
+    /// The amount of solutions in the family, saturating at `u64::MAX`.
+    pub fn count(&self) -> u64 {
+        self.counts()[self.root]
+    }
+
+    /// Count the solutions below every node. Children are always created before their parents.
+    fn counts(&self) -> Vec<u64> {
+        let mut counts: Vec<u64> = vec![0, 1];
+        for node in &self.nodes[2..] {
+            counts.push(counts[node.lo].saturating_add(counts[node.hi]));
+        }
+        counts
+    }
+
+    /// For every row below `rows`, count the solutions containing it, saturating at `u64::MAX`.
+    pub fn row_participation(&self, rows: usize) -> Vec<u64> {
+        let counts = self.counts();
+
+        // paths from the root down to every node, parents always coming after their children
+        let mut paths = vec![0u64; self.nodes.len()];
+        paths[self.root] = 1;
+        let mut participation = vec![0u64; rows];
+        for node in (2..self.nodes.len()).rev() {
+            let ZddNode { row, lo, hi } = self.nodes[node];
+            if paths[node] == 0 { continue; }
+
+            paths[lo] = paths[lo].saturating_add(paths[node]);
+            paths[hi] = paths[hi].saturating_add(paths[node]);
+            if row < rows {
+                participation[row] = participation[row].saturating_add(paths[node].saturating_mul(counts[hi]));
+            }
+        }
+
+        participation
+    }
+
+    /// Iterate over the solutions by nondecreasing total cost, `costs[row]` being the cost of a row.
+    /// Solutions of equal cost come out in the order they are first reached. Panics if a row of the
+    /// diagram has no cost.
+    pub fn into_iter_by_cost(self, costs: &[u64]) -> ZddCostIter {
+        // the cheapest way down from every node, `None` if there is none
+        let mut to_go: Vec<Option<u64>> = vec![None, Some(0)];
+        for node in &self.nodes[2..] {
+            let hi = to_go[node.hi].map(|c| c.saturating_add(costs[node.row]));
+            to_go.push(match (to_go[node.lo], hi) {
+                (Some(lo), Some(hi)) => Some(lo.min(hi)),
+                (lo, hi) => lo.or(hi),
+            });
+        }
+
+        let mut heap = BinaryHeap::new();
+        if let Some(cost) = to_go[self.root] {
+            heap.push(Reverse((cost, 0, self.root, 0, Vec::new())));
+        }
+
+        ZddCostIter { zdd: self, costs: costs.to_vec(), to_go, heap, seq: 1 }
+    }
+
+    /// Lazily iterate over the solutions, in the order the solver would find them.
+    pub fn iter(&self) -> ZddIter<'_> {
+        ZddIter { zdd: self, stack: vec![(self.root, 0)], path: Vec::new() }
+    }
+
+    /// Pick a solution uniformly at random, reproducibly for a given seed.
+    /// Returns `None` for the empty family.
+    pub fn sample(&self, seed: u64) -> Option<Vec<usize>> {
+        let counts = self.counts();
+        if counts[self.root] == 0 { return None; }
+
+        let mut pick = splitmix64(seed) % counts[self.root];
+        let mut solution = Vec::new();
+        let mut node = self.root;
+        while node != TRUE {
+            let ZddNode { row, lo, hi } = self.nodes[node];
+            if pick < counts[hi] {
+                solution.push(row);
+                node = hi;
+            } else {
+                pick -= counts[hi];
+                node = lo;
+            }
+        }
+
+        Some(solution)
+    }
+
+    /// Keep only the solutions that use a row (`chosen`), or only those that don't.
+    pub fn restrict(&self, row: usize, chosen: bool) -> Zdd {
+        let mut zdd = self.clone();
+        let mut done = HashMap::new();
+        zdd.root = zdd.restrict_node(self.root, row, chosen, &mut done);
+        zdd
+    }
+
+    fn restrict_node(&mut self, node: usize, row: usize, chosen: bool, done: &mut HashMap<usize, usize>) -> usize {
+        if node == FALSE { return FALSE; }
+        if node == TRUE { return if chosen { FALSE } else { TRUE }; }
+        if let Some(&result) = done.get(&node) { return result; }
+
+        let ZddNode { row: var, lo, hi } = self.nodes[node];
+        let result = if var == row {
+            // the row never appears again below this node
+            if chosen { self.make(var, FALSE, hi) } else { lo }
+        } else {
+            let lo = self.restrict_node(lo, row, chosen, done);
+            let hi = self.restrict_node(hi, row, chosen, done);
+            self.make(var, lo, hi)
+        };
+
+        done.insert(node, result);
+        result
+    }
+}
+
+/// A lazy iterator over the solutions of a [`Zdd`].
+pub struct ZddIter<'a> {
+    zdd: &'a Zdd,
+    stack: Vec<(usize, usize)>,
+    path: Vec<usize>,
+}
+impl Iterator for ZddIter<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        while let Some((node, len)) = self.stack.pop() {
+            self.path.truncate(len);
+            match node {
+                FALSE => continue,
+                TRUE => return Some(self.path.clone()),
+                _ => {
+                    let ZddNode { row, lo, hi } = self.zdd.nodes[node];
+                    // the hi branch is explored first
+                    self.stack.push((lo, len));
+                    self.stack.push((hi, len + 1));
+                    self.path.push(row);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A partial path as (estimated total, sequence number, node, cost so far, rows so far).
+type PartialPath = (u64, u64, usize, u64, Vec<usize>);
+
+/// An iterator over the solutions of a [`Zdd`] by nondecreasing total cost, with their cost.
+///
+/// This is a best-first search over partial paths, each ranked by its cost so far plus the exact
+/// cost of the cheapest way to finish it. As that estimate is exact, a complete path is only taken
+/// off the queue once no cheaper solution is left.
+pub struct ZddCostIter {
+    zdd: Zdd,
+    costs: Vec<u64>,
+    to_go: Vec<Option<u64>>,
+    heap: BinaryHeap<Reverse<PartialPath>>,
+    seq: u64,
+}
+impl ZddCostIter {
+    /// Queue a partial path if it can still reach a solution.
+    fn push(&mut self, node: usize, cost: u64, path: Vec<usize>) {
+        if let Some(to_go) = self.to_go[node] {
+            self.heap.push(Reverse((cost.saturating_add(to_go), self.seq, node, cost, path)));
+            self.seq += 1;
+        }
+    }
+}
+impl Iterator for ZddCostIter {
+    type Item = (u64, Vec<usize>);
+
+    fn next(&mut self) -> Option<(u64, Vec<usize>)> {
+        while let Some(Reverse((_, _, node, cost, mut path))) = self.heap.pop() {
+            if node == TRUE { return Some((cost, path)); }
+
+            let ZddNode { row, lo, hi } = self.zdd.nodes[node];
+            self.push(lo, cost, path.clone());
+            path.push(row);
+            self.push(hi, cost.saturating_add(self.costs[row]), path);
+        }
+        None
+    }
+}
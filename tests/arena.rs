@@ -0,0 +1,38 @@
+mod common;
+
+use std::time::Instant;
+use dlx::{Node, SolutionStorage};
+use common::permutations;
+
+#[test]
+fn arena_holds_every_solution() {
+    let input = permutations(5);
+    let all = Node::solve_all(&input);
+    let set = Node::solve_all_set(&input, SolutionStorage::Indices);
+    assert_eq!(all.len(), 120);
+
+    let (data, offsets) = set.flat().unwrap();
+    assert_eq!(offsets.len(), 121);
+    assert_eq!(data.len(), 5 * 120);
+    for (i, solution) in all.iter().enumerate() {
+        assert_eq!(&set.indices(i), solution);
+    }
+}
+
+/// The arena makes two allocations that grow, where the nested vectors make one for every solution.
+#[test]
+#[ignore = "benchmark, run with --release --ignored --nocapture"]
+fn permutations_benchmark() {
+    let input = permutations(9);
+    for _ in 0..3 {
+        let start = Instant::now();
+        let nested = Node::solve_all(&input);
+        let nested_time = start.elapsed();
+        let start = Instant::now();
+        let set = Node::solve_all_set(&input, SolutionStorage::Indices);
+        let arena_time = start.elapsed();
+
+        assert_eq!(set.len(), nested.len());
+        println!("{} solutions: {nested_time:?} as vectors, {arena_time:?} in the arena", set.len());
+    }
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use dlx::{Backend, Node, SolveOptions};
+use std::panic;
+use common::{dominoes, random};
+
+fn on(backend: Backend) -> SolveOptions {
+    SolveOptions { backend, ..SolveOptions::default() }
+}
+
+#[test]
+fn backends_agree() {
+    for seed in 0..300 {
+        let width = 1 + seed as usize % 128;
+        let input = random(seed, 5 + seed as usize % 40, width, 5 + seed % 30);
+        let (bitset, dancing) = (on(Backend::Bitset), on(Backend::DancingLinks));
+        assert_eq!(Node::solve_all_with_options(&input, &bitset), Node::solve_all_with_options(&input, &dancing), "seed {seed}");
+        assert_eq!(Node::solve_once_with_options(&input, &bitset), Node::solve_once_with_options(&input, &dancing), "seed {seed}");
+
+        let secondary = |options: &SolveOptions| SolveOptions { primary_columns: Some(width / 2), ..options.clone() };
+        assert_eq!(Node::solve_all_with_options(&input, &secondary(&bitset)), Node::solve_all_with_options(&input, &secondary(&dancing)), "seed {seed}");
+        let limited = |options: &SolveOptions| SolveOptions { node_limit: Some(7), ..options.clone() };
+        assert_eq!(Node::solve_all_outcome(&input, &limited(&bitset)), Node::solve_all_outcome(&input, &limited(&dancing)), "seed {seed}");
+    }
+    let input = dominoes(4, 6);
+    assert_eq!(Node::solve_all_with_options(&input, &on(Backend::Bitset)), Node::solve_all_with_options(&input, &on(Backend::DancingLinks)));
+}
+
+#[test]
+fn auto_picks_either_transparently() {
+    for (width, seed) in [(128, 1), (129, 2)] {
+        let input = random(seed, 30, width, 3);
+        assert_eq!(Node::solve_all_with_options(&input, &on(Backend::Auto)), Node::solve_all_with_options(&input, &on(Backend::DancingLinks)), "{width} columns");
+    }
+}
+
+#[test]
+fn bitsets_hold_at_most_128_columns() {
+    let input = random(3, 10, 129, 10);
+    assert!(panic::catch_unwind(|| Node::solve_all_with_options(&input, &on(Backend::Bitset))).is_err());
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use dlx::{DlxError, Node};
+use std::panic;
+use common::{dominoes, matrix};
+
+/// Get the message the function panics with.
+fn panic_message(f: impl FnOnce() + panic::UnwindSafe) -> String {
+    let payload = panic::catch_unwind(f).unwrap_err();
+    payload.downcast_ref::<String>().cloned().unwrap_or_default()
+}
+
+#[test]
+fn errors_say_what_is_wrong() {
+    let cases: [(&[Vec<bool>], DlxError, &str); 3] = [
+        (&[], DlxError::EmptyMatrix, "the matrix has no rows"),
+        (&[vec![], vec![]], DlxError::NoColumns, "the matrix has no columns"),
+        (&[vec![true], vec![true, false]], DlxError::RaggedRow { row: 1, expected: 1, got: 2 }, "row 1 has 2 columns instead of 1"),
+    ];
+    for (input, error, message) in cases {
+        assert_eq!(Node::try_build(input).err(), Some(error));
+        assert_eq!(Node::try_solve_all(input), Err(error));
+        assert_eq!(Node::try_solve_once(input), Err(error));
+        assert_eq!(error.to_string(), message);
+    }
+}
+
+#[test]
+fn good_matrices_solve_the_same() {
+    let input = dominoes(2, 3);
+    assert_eq!(Node::try_solve_all(&input), Ok(Node::solve_all(&input)));
+    assert_eq!(Node::try_solve_once(&input), Ok(Node::solve_once(&input)));
+    assert_eq!(Node::try_solve_once(&matrix(&["10"])), Ok(None));
+}
+
+#[test]
+fn panics_are_descriptive() {
+    assert_eq!(panic_message(|| { Node::solve_all(&[vec![true], vec![]]); }), "invalid matrix: row 1 has 0 columns instead of 1");
+    assert_eq!(panic_message(|| { Node::solve_once(&[]); }), "invalid matrix: the matrix has no rows");
+    assert_eq!(panic_message(|| { Node::count_solutions(&[vec![]]); }), "invalid matrix: the matrix has no columns");
+    assert_eq!(panic_message(|| { Node::solve_max_cover(&[]); }), "invalid matrix: the matrix has no rows");
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use std::panic;
+use dlx::{Node, RowSet, SolutionSet, SolutionStorage};
+use common::{dominoes, matrix, random, sorted};
+
+#[test]
+fn bitsets_hold_the_solutions() {
+    let input = dominoes(4, 3);
+    let bitsets: Vec<Vec<usize>> = Node::solve_all_bitsets(&input).iter().map(RowSet::to_indices).collect();
+    assert_eq!(bitsets.len(), 11);
+    assert_eq!(sorted(bitsets), sorted(Node::solve_all(&input)));
+}
+
+#[test]
+fn intersections_match_the_indices() {
+    let input = matrix(&["1100", "0011", "1000", "0100", "0010", "0001", "1010", "0101"]);
+    let solutions = sorted(Node::solve_all(&input));
+    assert!(solutions.len() > 2);
+
+    for a in &solutions {
+        for b in &solutions {
+            let common: Vec<usize> = a.iter().copied().filter(|row| b.contains(row)).collect();
+            let (a, b) = (RowSet::from_indices(a, input.len()), RowSet::from_indices(b, input.len()));
+            assert_eq!(a.intersection(&b).to_indices(), common);
+        }
+    }
+}
+
+#[test]
+fn sets_span_words() {
+    let set = RowSet::from_indices(&[129, 0, 64, 63], 130);
+    assert_eq!(set.to_indices(), vec![0, 63, 64, 129]);
+    assert_eq!(set.len(), 4);
+    assert_eq!(set.words().len(), 3);
+    assert!(set.contains(64) && !set.contains(65) && !set.contains(1000));
+}
+
+#[test]
+fn solution_sets_convert_between_storages() {
+    let input = dominoes(3, 4);
+    let all = Node::solve_all(&input);
+    let set = Node::solve_all_set(&input, SolutionStorage::Bitsets);
+    assert_eq!(set.storage(), SolutionStorage::Bitsets);
+    assert_eq!(set.len(), all.len());
+
+    let set = set.convert(SolutionStorage::Indices);
+    assert_eq!(set.storage(), SolutionStorage::Indices);
+    assert_eq!(sorted(set.into_indices()), sorted(all.clone()));
+
+    let set = SolutionSet::from_indices(all.clone(), input.len());
+    for (i, solution) in all.iter().enumerate() {
+        assert_eq!(set.bitset(i), RowSet::from_indices(solution, input.len()));
+    }
+}
+
+#[test]
+fn packed_words_round_trip() {
+    for seed in 0..8 {
+        // 70 to 140 rows, so solutions take two or three words
+        let input = random(seed, 70 + seed as usize * 10, 12, 35);
+        let all = Node::solve_all(&input);
+        let bits = Node::solve_all_bits(&input);
+        assert_eq!(bits.len(), all.len(), "seed {seed}");
+        for (words, solution) in bits.iter().zip(&all) {
+            assert_eq!(words.len(), input.len().div_ceil(64), "seed {seed}");
+            let mut rows = solution.clone();
+            rows.sort_unstable();
+            assert_eq!(Node::bits_to_rows(words), rows, "seed {seed}");
+            assert_eq!(&Node::rows_to_bits(solution, input.len()), words, "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn words_hold_rows_in_order() {
+    assert_eq!(Node::rows_to_bits(&[0, 64, 65], 130), vec![1, 3, 0]);
+    assert_eq!(Node::bits_to_rows(&[1 << 63, 5]), vec![63, 64, 66]);
+    assert!(Node::bits_to_rows(&[]).is_empty());
+    assert_eq!(RowSet::from_words(vec![0b10011], 5).to_indices(), vec![0, 1, 4]);
+}
+
+#[test]
+fn rows_out_of_range_panic() {
+    assert!(panic::catch_unwind(|| Node::rows_to_bits(&[5], 5)).is_err());
+    assert!(panic::catch_unwind(|| RowSet::from_words(vec![1 << 5], 5)).is_err());
+    assert!(panic::catch_unwind(|| RowSet::from_words(vec![1, 1], 5)).is_err());
+}
@@ -0,0 +1,29 @@
+use dlx::generation::blocks::{Block2D, BlockError, CountMode, Game2D, InvalidBlock};
+
+#[test]
+fn bad_strings_are_errors() {
+    assert_eq!(Block2D::try_from_string(""), Err(BlockError::Empty));
+    assert_eq!(Block2D::try_from_string("\n\n  \n"), Err(BlockError::Empty));
+    assert_eq!(Block2D::try_from_string("...\n..."), Err(BlockError::NoCells));
+    assert_eq!(Block2D::try_from_string("#\n##"), Err(BlockError::Ragged { line: 2 }));
+    assert_eq!(Block2D::try_from_string("\n##\n##\n#"), Err(BlockError::Ragged { line: 3 }));
+}
+
+#[test]
+fn games_name_the_bad_block() {
+    assert_eq!(Game2D::from_strings(2, 2, vec!["##", "", "#"]).err(), Some(InvalidBlock { block: 1, error: BlockError::Empty }));
+    let error = Game2D::from_strings(2, 2, vec!["##", "#", "..."]).err().unwrap();
+    assert_eq!(error.to_string(), "block 2: the block has no filled squares");
+    let error = Game2D::from_strings(2, 2, vec!["##", "#\n##"]).err().unwrap();
+    assert_eq!(error, InvalidBlock { block: 1, error: BlockError::Ragged { line: 2 } });
+}
+
+#[test]
+fn good_strings_still_work() {
+    let block = Block2D::try_from_string("  #.\n  ##\n").unwrap();
+    assert_eq!((block.w, block.h), (2, 2));
+    assert_eq!(block.data, vec![vec![true, false], vec![true, true]]);
+
+    let mut game = Game2D::from_strings(2, 2, vec!["##", "##"]).unwrap();
+    assert_eq!(game.count_solutions(CountMode::Raw), 4);
+}
@@ -0,0 +1,28 @@
+use dlx::generation::courses::Schedule;
+
+#[test]
+fn blocking_the_shared_slot_names_both_courses() {
+    // either course can be scheduled, and both only in slot 1
+    let availability = vec![vec![false, true, false], vec![false, true, false], vec![true, false, true]];
+    let mut schedule = Schedule::from_availability(&availability, None);
+    schedule.require_count(&[0, 1], 1).unwrap();
+    assert!(schedule.solve_best().is_some());
+
+    assert_eq!(schedule.block_slots(&[1]), vec![0, 1]);
+    assert_eq!(schedule.solve_best(), None);
+}
+
+#[test]
+fn blocked_patterns_are_left_out() {
+    let availability = vec![vec![true, true, false], vec![false, true, true]];
+    let mut schedule = Schedule::from_availability(&availability, None);
+    assert_eq!(schedule.solve_best_k(10).len(), 3);
+
+    assert_eq!(schedule.block_slots(&[0]), Vec::<usize>::new());
+    assert_eq!(schedule.solve_best_k(10).len(), 1);
+    // both courses keep slot 1, so neither is named, but they can't both have it
+    assert_eq!(schedule.block_slots(&[0, 2]), Vec::<usize>::new());
+    assert_eq!(schedule.solve_best(), None);
+    assert_eq!(schedule.block_slots(&[1]), vec![0, 1]);
+    assert_eq!(schedule.solve_best(), None);
+}
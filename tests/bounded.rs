@@ -0,0 +1,62 @@
+mod common;
+
+use dlx::Node;
+use common::{matrix, random, sorted};
+
+/// Find the sets of rows covering every column within its bounds by trying them all, rows without
+/// columns aside.
+fn brute_force(input: &[Vec<bool>], bounds: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    (0u32..1 << input.len()).filter_map(|set| {
+        let chosen: Vec<usize> = (0..input.len()).filter(|row| set >> row & 1 == 1).collect();
+        if chosen.iter().any(|&row| !input[row].contains(&true)) { return None; }
+        let within = (0..input[0].len()).all(|x| {
+            let count = chosen.iter().filter(|&&row| input[row][x]).count();
+            let (min, max) = bounds.get(x).copied().unwrap_or((1, 1));
+            (min..=max).contains(&count)
+        });
+        within.then_some(chosen)
+    }).collect()
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    for seed in 0..500 {
+        let width = 1 + seed as usize % 5;
+        let input = random(seed, 1 + seed as usize % 11, width, 40);
+        let bounds: Vec<(usize, usize)> = (0..seed as usize % (width + 1)).map(|x| {
+            let min = (seed as usize + x) % 3;
+            (min, min + (seed as usize / 3 + x) % 3)
+        }).collect();
+        let solutions = Node::solve_all_bounded(&input, &bounds);
+        let mut distinct = sorted(solutions.clone());
+        distinct.dedup();
+        assert_eq!(distinct.len(), solutions.len(), "seed {seed} has duplicates");
+        assert_eq!(sorted(solutions), sorted(brute_force(&input, &bounds)), "seed {seed}");
+    }
+}
+
+#[test]
+fn exact_and_secondary_columns() {
+    let input = matrix(&["110", "011", "100", "101", "010"]);
+    assert_eq!(sorted(Node::solve_all_bounded(&input, &[])), sorted(Node::solve_all(&input)));
+    assert_eq!(sorted(Node::solve_all_bounded(&input, &[(1, 1), (1, 1), (0, 1)])), sorted(Node::solve_all_with_secondary(&input, 2)));
+}
+
+#[test]
+fn nurses_work_two_or_three_shifts() {
+    // a row per shift and pair of nurses on it, the columns being the three nurses and the four shifts
+    let pairs = [[0, 1], [0, 2], [1, 2]];
+    let input: Vec<Vec<bool>> = (0..4).flat_map(|shift| pairs.iter().map(move |pair| {
+        (0..7).map(|x| pair.contains(&x) || x == 3 + shift).collect()
+    })).collect();
+    let bounds = [(2, 3), (2, 3), (2, 3)];
+    let solutions = Node::solve_all_bounded(&input, &bounds);
+    // eight shifts worked in all, so one nurse works two and the others three
+    assert_eq!(solutions.len(), brute_force(&input, &bounds).len());
+    assert!(!solutions.is_empty());
+    for solution in &solutions {
+        let mut shifts: Vec<usize> = (0..3).map(|nurse| solution.iter().filter(|&&row| input[row][nurse]).count()).collect();
+        shifts.sort();
+        assert_eq!(shifts, [2, 3, 3]);
+    }
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions};
+use common::{dominoes, random, row_of};
+
+fn bucketed() -> SolveOptions {
+    SolveOptions { column_strategy: ColumnStrategy::Bucketed, ..SolveOptions::default() }
+}
+
+/// A matrix of `width` columns with a planted solution of rows four columns wide, among random rows
+/// as wide, so columns have all sorts of sizes.
+fn planted(width: usize, extra: usize) -> Vec<Vec<bool>> {
+    let mut rows: Vec<Vec<bool>> = (0..width / 4).map(|i| row_of(width, &[4 * i, 4 * i + 1, 4 * i + 2, 4 * i + 3])).collect();
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    for _ in 0..extra {
+        let mut columns = Vec::new();
+        while columns.len() < 4 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let column = state as usize % width;
+            if !columns.contains(&column) { columns.push(column); }
+        }
+        rows.push(row_of(width, &columns));
+    }
+    rows
+}
+
+#[test]
+fn same_search_as_scanning() {
+    for seed in 0..300 {
+        let input = random(seed, 14, 9, 30);
+        let (solutions, stats) = Node::solve_all_with_options(&input, &SolveOptions::default());
+        let (bucketed_solutions, bucketed_stats) = Node::solve_all_with_options(&input, &bucketed());
+        assert_eq!(bucketed_solutions, solutions, "seed {seed}");
+        assert_eq!(bucketed_stats.nodes, stats.nodes);
+
+        let options = SolveOptions { memo_capacity: Some(100), ..bucketed() };
+        assert_eq!(Node::count_solutions_with_options(&input, &options).0, solutions.len() as u64);
+    }
+
+    let input = dominoes(4, 5);
+    assert_eq!(Node::solve_all_with_options(&input, &bucketed()).0, Node::solve_all(&input));
+    let options = SolveOptions { primary_columns: Some(12), early_contradiction: true, ..bucketed() };
+    let scanning = SolveOptions { column_strategy: ColumnStrategy::Smallest, ..options.clone() };
+    assert_eq!(Node::solve_all_with_options(&input, &options).0, Node::solve_all_with_options(&input, &scanning).0);
+}
+
+#[test]
+fn scans_fewer_headers_on_wide_matrices() {
+    let input = planted(2000, 10000);
+    let (solution, stats) = Node::solve_n_outcome(&input, 1, &SolveOptions::default());
+    let (bucketed_solution, bucketed_stats) = Node::solve_n_outcome(&input, 1, &bucketed());
+    assert_eq!(bucketed_solution, solution);
+    assert_eq!(bucketed_stats.nodes, stats.nodes);
+    // scanning looks at every active column, 1000 of them on average, buckets only at the smallest ones
+    assert_eq!(stats.headers_scanned / stats.nodes, 1000);
+    assert!(bucketed_stats.headers_scanned / bucketed_stats.nodes < 100);
+}
+
+#[test]
+fn ties_take_the_leftmost_column_at_once() {
+    // every column has one row, so all of them tie in a single bucket
+    let input: Vec<Vec<bool>> = (0..2000).map(|row| row_of(2000, &[row])).collect();
+    let (solution, stats) = Node::solve_n_outcome(&input, 1, &SolveOptions::default());
+    let (bucketed_solution, bucketed_stats) = Node::solve_n_outcome(&input, 1, &bucketed());
+    assert_eq!(bucketed_solution, solution);
+    assert_eq!(stats.headers_scanned, 2000 * 2001 / 2);
+    // the empty bucket, the bucket of the columns, and its leftmost column
+    assert_eq!(bucketed_stats.headers_scanned, 3 * (bucketed_stats.nodes - 1));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use dlx::{Backend, Node, SolveOptions, SolveOutcome};
+use common::dominoes;
+
+#[test]
+fn zero_budget_finds_nothing() {
+    let input = dominoes(4, 4);
+    for backend in [Backend::Bitset, Backend::DancingLinks] {
+        let options = SolveOptions { node_limit: Some(0), backend, ..SolveOptions::default() };
+        let (outcome, stats) = Node::solve_all_outcome(&input, &options);
+        assert_eq!(outcome, SolveOutcome::Interrupted(Vec::new()));
+        assert_eq!(stats.nodes, 0);
+        assert_eq!(Node::solve_n_outcome(&input, 3, &options).0, SolveOutcome::Interrupted(Vec::new()));
+    }
+    let options = SolveOptions { node_limit: Some(0), ..SolveOptions::default() };
+    assert_eq!(Node::count_solutions_outcome(&input, &options).0, SolveOutcome::Interrupted(0));
+}
+
+#[test]
+fn generous_budget_matches_unbounded() {
+    let input = dominoes(4, 4);
+    let all = Node::solve_all(&input);
+    for backend in [Backend::Bitset, Backend::DancingLinks] {
+        let options = SolveOptions { node_limit: Some(1 << 40), backend, ..SolveOptions::default() };
+        assert_eq!(Node::solve_all_outcome(&input, &options).0, SolveOutcome::Complete(all.clone()));
+        assert_eq!(Node::solve_n_outcome(&input, 3, &options).0, SolveOutcome::Complete(all[..3].to_vec()));
+        assert_eq!(Node::solve_n_outcome(&input, 1000, &options).0, SolveOutcome::Complete(all.clone()));
+    }
+    let options = SolveOptions { node_limit: Some(1 << 40), ..SolveOptions::default() };
+    assert_eq!(Node::count_solutions_outcome(&input, &options).0, SolveOutcome::Complete(all.len() as u64));
+}
+
+#[test]
+fn small_budgets_find_a_prefix() {
+    let input = dominoes(4, 4);
+    let all = Node::solve_all(&input);
+    for limit in 1..200 {
+        let options = SolveOptions { node_limit: Some(limit), ..SolveOptions::default() };
+        let (outcome, stats) = Node::solve_n_outcome(&input, 10, &options);
+        assert!(stats.nodes <= limit);
+        assert!(all.starts_with(outcome.solutions()));
+        if !outcome.is_complete() { assert!(outcome.solutions().len() < 10); }
+    }
+}
+
+#[test]
+fn budget_stops_counting() {
+    let input = dominoes(6, 6);
+    let options = SolveOptions { node_limit: Some(10), ..SolveOptions::default() };
+    let (outcome, stats) = Node::count_solutions_outcome(&input, &options);
+    assert!(!outcome.is_complete());
+    assert!(outcome.into_inner() < 6728);
+    assert!(stats.nodes <= 10);
+    assert!(Node::count_solutions_with_options(&input, &options).0 < 6728);
+
+    // cut short at any point with the memo too
+    let options = SolveOptions { memo_capacity: Some(1 << 16), ..SolveOptions::default() };
+    let nodes = Node::count_solutions_outcome(&input, &options).1.nodes;
+    for limit in (1..nodes).step_by(7) {
+        let options = SolveOptions { node_limit: Some(limit), ..options.clone() };
+        let (outcome, _) = Node::count_solutions_outcome(&input, &options);
+        assert!(!outcome.is_complete());
+        assert!(outcome.into_inner() < 6728);
+    }
+}
+
+#[test]
+fn budget_stops_building_diagrams() {
+    let input = dominoes(4, 4);
+    let options = SolveOptions { node_limit: Some(20), memo_capacity: Some(usize::MAX), ..SolveOptions::default() };
+    let (outcome, stats) = Node::solve_to_zdd_outcome(&input, &options);
+    assert!(!outcome.is_complete());
+    assert!(stats.nodes <= 20);
+    let zdd = outcome.into_inner();
+    assert!(zdd.count() < 36);
+    let all = common::sorted(Node::solve_all(&input));
+    for mut solution in zdd.iter() {
+        solution.sort_unstable();
+        assert!(all.contains(&solution));
+    }
+}
+
+#[test]
+fn budget_composes_with_cancellation() {
+    let input = dominoes(4, 4);
+    let cancel = Arc::new(AtomicBool::new(true));
+    let options = SolveOptions { cancel: Some(cancel), node_limit: Some(1000), ..SolveOptions::default() };
+    assert!(!Node::solve_n_outcome(&input, 2, &options).0.is_complete());
+    assert_eq!(Node::count_solutions_outcome(&input, &options).0, SolveOutcome::Interrupted(0));
+}
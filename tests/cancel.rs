@@ -0,0 +1,62 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use dlx::{Node, ProgressCallback, SolveOptions, SolveOutcome};
+use common::{dominoes, random};
+
+#[test]
+fn deadline_stops_early() {
+    let input = dominoes(8, 8);
+    let start = Instant::now();
+    let options = SolveOptions { deadline: Some(start + Duration::from_millis(50)), ..SolveOptions::default() };
+    let (outcome, _) = Node::solve_all_outcome(&input, &options);
+    assert!(!outcome.is_complete());
+    assert!(!outcome.solutions().is_empty());
+    assert!(start.elapsed() < Duration::from_secs(2));
+
+    let options = SolveOptions { deadline: Some(Instant::now()), ..SolveOptions::default() };
+    assert_eq!(Node::solve_all_outcome(&dominoes(2, 2), &options).0, SolveOutcome::Interrupted(Vec::new()));
+}
+
+#[test]
+fn cancel_flag_stops_early() {
+    let input = dominoes(8, 8);
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = cancel.clone();
+    // set the flag from inside the search, after a few hundred nodes
+    let progress = ProgressCallback::new(300, move |_| flag.store(true, Ordering::Relaxed));
+    let options = SolveOptions { cancel: Some(cancel), progress: Some(progress), ..SolveOptions::default() };
+    let (outcome, stats) = Node::solve_all_outcome(&input, &options);
+    assert!(!outcome.is_complete());
+    assert!(stats.nodes <= 301);
+    assert_eq!(outcome.solutions(), Node::solve_n(&input, outcome.solutions().len()));
+}
+
+#[test]
+fn never_set_flag_changes_nothing() {
+    for seed in 0..50 {
+        let input = random(seed, 12, 8, 30);
+        let options = SolveOptions {
+            deadline: Some(Instant::now() + Duration::from_secs(60)),
+            cancel: Some(Arc::new(AtomicBool::new(false))),
+            ..SolveOptions::default()
+        };
+        assert_eq!(Node::solve_all_outcome(&input, &options).0, SolveOutcome::Complete(Node::solve_all(&input)));
+        assert_eq!(Node::count_solutions_outcome(&input, &options).0, SolveOutcome::Complete(Node::count_solutions(&input)));
+    }
+}
+
+#[test]
+fn counting_can_be_interrupted() {
+    let input = dominoes(8, 8);
+    let options = SolveOptions { cancel: Some(Arc::new(AtomicBool::new(true))), ..SolveOptions::default() };
+    assert_eq!(Node::count_solutions_outcome(&input, &options).0, SolveOutcome::Interrupted(0));
+
+    let start = Instant::now();
+    let options = SolveOptions { deadline: Some(start + Duration::from_millis(50)), ..SolveOptions::default() };
+    let (outcome, _) = Node::count_solutions_outcome(&input, &options);
+    assert!(!outcome.is_complete());
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions, Solver};
+use dlx::generation::blocks::Game2D;
+use common::{random, sorted};
+
+fn canonical() -> SolveOptions {
+    SolveOptions { canonical_order: true, ..SolveOptions::default() }
+}
+
+#[test]
+fn pins_a_game() {
+    let mut game = Game2D::from_strings(3, 2, vec!["##", "#\n#", "##"]).unwrap();
+    let matrix = game.get_matrix();
+    let (solutions, _) = Node::solve_all_with_options(&matrix, &canonical());
+    assert_eq!(solutions, [
+        [0, 9, 16], [0, 12, 20], [1, 7, 17], [1, 13, 18], [2, 9, 14], [2, 10, 20],
+        [3, 7, 15], [3, 11, 18], [4, 8, 20], [4, 9, 19], [4, 11, 17], [4, 13, 15],
+        [5, 7, 20], [5, 9, 18], [6, 7, 19], [6, 8, 18], [6, 10, 16], [6, 12, 14],
+    ]);
+    assert_eq!(solutions, sorted(Node::solve_all(&matrix)));
+}
+
+#[test]
+fn order_only_depends_on_the_solutions() {
+    for seed in 0..50 {
+        let input = random(seed, 30, 10, 25);
+        let expected = sorted(Node::solve_all(&input));
+        for column_strategy in [ColumnStrategy::First, ColumnStrategy::Random { seed: 3 }] {
+            let options = SolveOptions { column_strategy, row_seed: Some(seed), ..canonical() };
+            assert_eq!(Node::solve_all_with_options(&input, &options).0, expected, "seed {seed}, {column_strategy:?}");
+        }
+        assert_eq!(Solver::with_options(&input, canonical()).solve_all(), expected, "seed {seed}");
+    }
+}
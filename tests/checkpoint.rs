@@ -0,0 +1,47 @@
+mod common;
+
+use dlx::{Checkpoint, Node, SolutionIter};
+use common::{dominoes, random};
+
+/// Compiles only for types that can be saved and loaded.
+fn serializable<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+
+#[test]
+fn resuming_continues_the_enumeration() {
+    serializable::<Checkpoint>();
+    let input = dominoes(4, 4);
+    let all = Node::solve_all(&input);
+    assert_eq!(SolutionIter::new(&input).collect::<Vec<_>>(), all);
+    assert_eq!(SolutionIter::new(&input).checkpoint(), Checkpoint::default());
+
+    for taken in 0..=all.len() + 1 {
+        let mut iter = SolutionIter::new(&input);
+        let mut solutions: Vec<Vec<usize>> = iter.by_ref().take(taken).collect();
+        let checkpoint = iter.checkpoint();
+        drop(iter);
+        solutions.extend(SolutionIter::resume(&input, &checkpoint));
+        assert_eq!(solutions, all, "{taken} taken");
+    }
+}
+
+#[test]
+fn resuming_after_every_solution() {
+    for seed in 0..300 {
+        let input = random(seed, 12, 6, 30);
+        let all = Node::solve_all(&input);
+        assert_eq!(SolutionIter::new(&input).collect::<Vec<_>>(), all, "seed {seed}");
+
+        let mut iter = SolutionIter::new(&input);
+        let mut solutions = Vec::new();
+        loop {
+            iter = SolutionIter::resume(&input, &iter.checkpoint());
+            match iter.next() {
+                Some(solution) => solutions.push(solution),
+                None => break,
+            }
+        }
+        assert_eq!(solutions, all, "seed {seed}");
+        // a finished search stays finished
+        assert!(SolutionIter::resume(&input, &iter.checkpoint()).next().is_none(), "seed {seed}");
+    }
+}
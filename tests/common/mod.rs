@@ -0,0 +1,58 @@
+//! Matrices shared by the tests.
+
+#![allow(dead_code)]
+
+/// The twelve pentominoes, for `Game2D::from_strings`.
+pub const PENTOMINOES: [&str; 12] = [
+    "#####", "####\n#...", "####\n.#..", "###.\n..##", "###\n#..\n#..", "###\n.#.\n.#.",
+    "##.\n.##\n..#", "###\n#.#", "##.\n.##\n.#.", "##\n##\n#.", "#..\n###\n..#", ".#.\n###\n.#.",
+];
+
+/// Parse a matrix written as rows of `0` and `1`.
+pub fn matrix(rows: &[&str]) -> Vec<Vec<bool>> {
+    rows.iter().map(|row| row.chars().map(|c| c == '1').collect()).collect()
+}
+
+/// The placements of dominoes on a `width` by `height` board, one column per cell.
+pub fn dominoes(width: usize, height: usize) -> Vec<Vec<bool>> {
+    let mut rows = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let cell = y * width + x;
+            if x + 1 < width { rows.push(row_of(width * height, &[cell, cell + 1])); }
+            if y + 1 < height { rows.push(row_of(width * height, &[cell, cell + width])); }
+        }
+    }
+    rows
+}
+
+/// The permutations of `n` elements, as choices of one column of each half for every row.
+pub fn permutations(n: usize) -> Vec<Vec<bool>> {
+    (0..n * n).map(|i| row_of(2 * n, &[i / n, n + i % n])).collect()
+}
+
+/// A row of `width` columns with ones in `columns`.
+pub fn row_of(width: usize, columns: &[usize]) -> Vec<bool> {
+    let mut row = vec![false; width];
+    for &x in columns { row[x] = true; }
+    row
+}
+
+/// A matrix with every entry set at random with a chance of `percent` in 100, the same one for the
+/// same seed.
+pub fn random(seed: u64, rows: usize, columns: usize, percent: u64) -> Vec<Vec<bool>> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..rows).map(|_| (0..columns).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 100 < percent
+    }).collect()).collect()
+}
+
+/// Sort the rows of every solution and the solutions, to compare sets of solutions.
+pub fn sorted(mut solutions: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    for solution in &mut solutions { solution.sort_unstable(); }
+    solutions.sort();
+    solutions
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, permutations, random, sorted};
+
+/// Put matrices side by side, each with its own columns, so their rows never share a column.
+fn block_diagonal(blocks: &[Vec<Vec<bool>>]) -> Vec<Vec<bool>> {
+    let width: usize = blocks.iter().map(|block| block[0].len()).sum();
+    let (mut rows, mut offset) = (Vec::new(), 0);
+    for block in blocks {
+        for row in block {
+            let mut wide = vec![false; width];
+            wide[offset..offset + row.len()].copy_from_slice(row);
+            rows.push(wide);
+        }
+        offset += block[0].len();
+    }
+    rows
+}
+
+#[test]
+fn blocks_split_into_components() {
+    let (tiles, orders) = (dominoes(2, 3), permutations(3));
+    let input = block_diagonal(&[tiles.clone(), orders.clone()]);
+    let components = Node::split_components(&input);
+    assert_eq!(components, vec![
+        ((0..6).collect(), (0..tiles.len()).collect()),
+        ((6..12).collect(), (tiles.len()..tiles.len() + orders.len()).collect()),
+    ]);
+
+    let solutions = Node::solve_all_decomposed(&input);
+    assert_eq!(solutions.len() as u64, Node::count_solutions(&tiles) * Node::count_solutions(&orders));
+    assert_eq!(sorted(solutions), sorted(Node::solve_all(&input)));
+}
+
+#[test]
+fn unsatisfiable_component_has_no_solutions() {
+    let input = block_diagonal(&[matrix(&["10", "10"]), permutations(3)]);
+    assert!(Node::solve_all_decomposed(&input).is_empty());
+}
+
+#[test]
+fn decomposed_matches_solve_all() {
+    for seed in 0..300 {
+        let input = random(seed, 10, 8, 18);
+        let components = Node::split_components(&input);
+        let mut cols: Vec<usize> = components.iter().flat_map(|(cols, _)| cols.iter().copied()).collect();
+        cols.sort_unstable();
+        assert_eq!(cols, (0..8).collect::<Vec<_>>(), "seed {seed}");
+        for (cols, rows) in &components {
+            assert!(rows.iter().all(|&row| (0..8).all(|col| !input[row][col] || cols.contains(&col))), "seed {seed}");
+        }
+        assert_eq!(sorted(Node::solve_all_decomposed(&input)), sorted(Node::solve_all(&input)), "seed {seed}");
+    }
+}
@@ -0,0 +1,73 @@
+use dlx::generation::compose::{ComposeError, column_permutation, hstack, identity_block, pad_columns};
+use dlx::generation::courses::{self, Schedule};
+use dlx::generation::sudoku;
+
+/// The sudoku matrix encoded by hand: a column for every cell, then for every digit of every row,
+/// column and box, in that order.
+fn hand_rolled_sudoku() -> Vec<Vec<bool>> {
+    let mut matrix = Vec::new();
+    for row in 0..9 {
+        for col in 0..9 {
+            for digit in 0..9 {
+                let mut entry = vec![false; 4 * 81];
+                entry[row * 9 + col] = true;
+                entry[81 + row * 9 + digit] = true;
+                entry[2 * 81 + col * 9 + digit] = true;
+                entry[3 * 81 + (row / 3 * 3 + col / 3) * 9 + digit] = true;
+                matrix.push(entry);
+            }
+        }
+    }
+    matrix
+}
+
+#[test]
+fn composed_sudoku_matches_the_hand_rolled_one() {
+    assert_eq!(sudoku::get_matrix(), hand_rolled_sudoku());
+}
+
+#[test]
+fn composed_courses_match_the_hand_rolled_ones() {
+    let availability = vec![vec![true, false, true], vec![false, true, true]];
+    // a row per course and slot it can take, then a free row per slot
+    let expected = vec![
+        vec![true, false, true, false, false],
+        vec![true, false, false, false, true],
+        vec![false, true, false, true, false],
+        vec![false, true, false, false, true],
+        vec![false, false, true, false, false],
+        vec![false, false, false, true, false],
+        vec![false, false, false, false, true],
+    ];
+    assert_eq!(Schedule::from_availability(&availability, None).get_matrix().0, expected);
+}
+
+#[test]
+fn bindings_share_the_course_matrix_of_schedules() {
+    // the wasm and Node.js bindings both hand out this matrix
+    let availability = vec![vec![true, false, true], vec![false, true, true], vec![false, false, false]];
+    let schedule = Schedule::from_availability(&availability, None);
+    assert_eq!(courses::matrix_from_courses(&availability), Ok(schedule.get_matrix().0));
+    // the one examples/napi checks
+    assert_eq!(courses::matrix_from_courses(&[vec![true]]), Ok(vec![vec![true, true], vec![false, true]]));
+    assert_eq!(courses::matrix_from_courses(&[vec![true], vec![]]), Err(ComposeError::RaggedRow { row: 1, expected: 1, got: 0 }));
+}
+
+#[test]
+fn stacks_check_their_parts() {
+    assert_eq!(hstack(&[]), Ok(Vec::new()));
+    assert_eq!(hstack(&[identity_block(2), vec![vec![true]]]), Err(ComposeError::RowCount { part: 1, expected: 2, got: 1 }));
+    assert_eq!(hstack(&[vec![vec![true], vec![]]]), Err(ComposeError::RaggedRow { row: 1, expected: 1, got: 0 }));
+    assert_eq!(hstack(&[identity_block(2), identity_block(2)]), Ok(vec![vec![true, false, true, false], vec![false, true, false, true]]));
+}
+
+#[test]
+fn permutations_and_padding() {
+    let permuted = column_permutation(&identity_block(3), &[2, 0, 1]).unwrap();
+    assert_eq!(permuted, vec![vec![false, true, false], vec![false, false, true], vec![true, false, false]]);
+    assert_eq!(column_permutation(&identity_block(3), &[2, 2, 1]), Err(ComposeError::NotPermutation { width: 3 }));
+    assert_eq!(column_permutation(&identity_block(3), &[2, 1]), Err(ComposeError::NotPermutation { width: 3 }));
+
+    assert_eq!(pad_columns(&identity_block(1), 1, 2), Ok(vec![vec![false, true, false, false]]));
+    assert!(pad_columns(&[vec![true], vec![]], 1, 1).is_err());
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use dlx::Node;
+use common::{matrix, random, sorted};
+
+#[test]
+fn covers_come_by_cost() {
+    // the covers are rows 0 and 1 for 3, rows 3 and 4 for 5, rows 5 and 6 for 5, and row 2 for 9
+    let input = matrix(&["1100", "0011", "1111", "1000", "0111", "1110", "0001"]);
+    let costs = [1, 2, 9, 2, 3, 4, 1];
+    let mut covers = Node::solve_ordered_by_cost(&input, &costs);
+    let found: Vec<(u64, Vec<usize>)> = covers.by_ref().map(|(cost, mut rows)| {
+        rows.sort_unstable();
+        (cost, rows)
+    }).collect();
+
+    assert_eq!(found.iter().map(|(cost, _)| *cost).collect::<Vec<_>>(), vec![3, 5, 5, 9]);
+    assert_eq!(found[0].1, vec![0, 1]);
+    assert_eq!(found[3].1, vec![2]);
+    assert_eq!(covers.next(), None);
+}
+
+#[test]
+fn every_cover_comes_once_and_in_order() {
+    for seed in 0..50 {
+        let input = random(seed, 16, 8, 25);
+        let costs: Vec<u64> = (0..16).map(|row| (row * 7 + seed) % 5).collect();
+        let found: Vec<(u64, Vec<usize>)> = Node::solve_ordered_by_cost(&input, &costs).collect();
+
+        assert!(found.windows(2).all(|pair| pair[0].0 <= pair[1].0), "seed {seed}");
+        for (cost, rows) in &found {
+            assert_eq!(*cost, rows.iter().map(|&row| costs[row]).sum::<u64>());
+        }
+        let covers = found.into_iter().map(|(_, rows)| rows).collect();
+        assert_eq!(sorted(covers), sorted(Node::solve_all(&input)), "seed {seed}");
+    }
+}
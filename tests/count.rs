@@ -0,0 +1,36 @@
+mod common;
+
+use dlx::Node;
+use std::time::Instant;
+use common::{dominoes, random};
+
+#[test]
+fn counts_match_the_solutions() {
+    for seed in 0..300 {
+        let input = random(seed, 12, 8, 30);
+        assert_eq!(Node::count_solutions(&input), Node::solve_all(&input).len() as u64, "seed {seed}");
+    }
+}
+
+#[test]
+fn counts_domino_tilings() {
+    let counts: Vec<u64> = (2..=6).map(|n| Node::count_solutions(&dominoes(n, n))).collect();
+    assert_eq!(counts, [2, 0, 36, 0, 6728]);
+}
+
+/// Counting keeps nothing for a solution, where listing them copies every one.
+#[test]
+#[ignore = "benchmark, run with --release --ignored --nocapture"]
+fn domino_benchmark() {
+    let input = dominoes(6, 8);
+    let start = Instant::now();
+    let count = Node::count_solutions(&input);
+    let count_time = start.elapsed();
+    let start = Instant::now();
+    let listed = Node::solve_all(&input).len();
+    let list_time = start.elapsed();
+
+    assert_eq!(count, listed as u64);
+    println!("{count} solutions: {count_time:?} counted, {list_time:?} listed");
+    assert_eq!(Node::count_solutions(&dominoes(8, 8)), 12_988_816);
+}
@@ -0,0 +1,88 @@
+use dlx::Node;
+use dlx::generation::blocks::{Block2D, CountMode, Game2D};
+use std::collections::HashSet;
+
+type Grid = Vec<Vec<Option<usize>>>;
+
+/// The grid turned a quarter clockwise.
+fn rotated(grid: &Grid) -> Grid {
+    (0..grid[0].len()).map(|x| (0..grid.len()).rev().map(|y| grid[y][x]).collect()).collect()
+}
+
+/// The grid mirrored left to right.
+fn mirrored(grid: &Grid) -> Grid {
+    grid.iter().map(|row| row.iter().rev().copied().collect()).collect()
+}
+
+/// The images of a grid under the symmetries of its board.
+fn images(grid: &Grid) -> Vec<Grid> {
+    let mut images = Vec::new();
+    let mut image = grid.clone();
+    for _ in 0..4 {
+        images.push(mirrored(&image));
+        images.push(image.clone());
+        image = rotated(&image);
+    }
+    images.retain(|image| image.len() == grid.len() && image[0].len() == grid[0].len());
+    images
+}
+
+/// Relabel a grid with the shape of every block, numbering the blocks in reading order.
+fn by_shape(grid: &Grid, shapes: &[usize]) -> Vec<Vec<Option<(usize, usize)>>> {
+    let mut order = Vec::new();
+    grid.iter().map(|row| row.iter().map(|cell| cell.map(|block| {
+        if !order.contains(&block) { order.push(block); }
+        (shapes[block], order.iter().position(|&seen| seen == block).unwrap())
+    })).collect()).collect()
+}
+
+/// Count the raw tilings of a game in each mode by filtering the whole enumeration.
+fn filtered(w: usize, h: usize, blocks: &[&str]) -> [u64; 3] {
+    let mut game = Game2D::from_strings(w, h, blocks.to_vec()).unwrap();
+    let canonical: Vec<_> = blocks.iter().map(|block| Block2D::from_string(block).canonical()).collect();
+    let shapes: Vec<usize> = canonical.iter().map(|shape| canonical.iter().position(|other| other == shape).unwrap()).collect();
+
+    let solutions = Node::solve_all(&game.get_matrix());
+    let (mut boards, mut pieces) = (HashSet::new(), HashSet::new());
+    for rows in &solutions {
+        let images = images(&game.solution_grid(rows));
+        boards.insert(images.iter().min().unwrap().clone());
+        pieces.insert(images.iter().map(|image| by_shape(image, &shapes)).min().unwrap());
+    }
+    [solutions.len() as u64, boards.len() as u64, pieces.len() as u64]
+}
+
+/// Count the tilings of a game in each mode.
+fn counted(w: usize, h: usize, blocks: &[&str]) -> [u64; 3] {
+    let mut game = Game2D::from_strings(w, h, blocks.to_vec()).unwrap();
+    [CountMode::Raw, CountMode::UpToBoardSymmetry, CountMode::UpToBoardSymmetryAndIdenticalPieces].map(|mode| game.count_solutions(mode))
+}
+
+#[test]
+fn dominoes_on_a_small_board() {
+    // three tilings, each with the dominoes in any order; mirroring pairs the two with a vertical domino on a side
+    let blocks = ["##", "##", "##"];
+    assert_eq!(counted(3, 2, &blocks), [18, 6, 2]);
+    assert_eq!(counted(3, 2, &blocks), filtered(3, 2, &blocks));
+}
+
+#[test]
+fn square_board_divides_by_its_symmetries() {
+    // both tilings of the square turn into each other, and so do both orders of the dominoes
+    assert_eq!(counted(2, 2, &["##", "##"]), [4, 1, 1]);
+    // only mirroring along its bars leaves a tiling as it is, so the eight symmetries give four images
+    assert_eq!(counted(4, 4, &["####"; 4]), [48, 48 / 4, 1]);
+}
+
+#[test]
+fn modes_agree_with_filtering() {
+    let games: [(usize, usize, &[&str]); 4] = [
+        (2, 2, &["##", "##"]),
+        (4, 2, &["##", "##", "##", "##"]),
+        (4, 4, &["###\n#..", "###\n#..", "###\n#..", "###\n#.."]),
+        (3, 3, &["###\n#..", "###\n..#", "#"]),
+    ];
+    for (w, h, blocks) in games {
+        assert_eq!(counted(w, h, blocks), filtered(w, h, blocks), "{w}x{h}");
+    }
+}
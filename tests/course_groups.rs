@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+use dlx::generation::courses::{GroupError, Schedule};
+
+/// Five courses over four slots.
+fn availability() -> Vec<Vec<bool>> {
+    vec![
+        vec![true, false, false, false],
+        vec![true, true, false, false],
+        vec![false, false, true, false],
+        vec![false, false, true, true],
+        vec![false, true, false, false],
+    ]
+}
+
+/// Check if courses can each get a slot of their own.
+fn feasible(availability: &[Vec<bool>], courses: &[usize], taken: &mut Vec<usize>) -> bool {
+    let Some((&course, rest)) = courses.split_first() else { return true };
+    (0..availability[course].len()).any(|slot| {
+        if !availability[course][slot] || taken.contains(&slot) { return false; }
+        taken.push(slot);
+        let found = feasible(availability, rest, taken);
+        taken.pop();
+        found
+    })
+}
+
+#[test]
+fn three_of_five_are_the_feasible_triples() {
+    let availability = availability();
+    let mut schedule = Schedule::from_availability(&availability, None);
+    schedule.require_count(&[0, 1, 2, 3, 4], 3).unwrap();
+
+    let schedules = schedule.solve_best_k(1000);
+    assert!(schedules.iter().all(|(_, assignments)| assignments.len() == 3));
+    let triples: BTreeSet<BTreeSet<usize>> = schedules.iter()
+        .map(|(_, assignments)| assignments.iter().map(|assignment| assignment.course).collect())
+        .collect();
+
+    let mut expected = BTreeSet::new();
+    for a in 0..5 {
+        for b in a + 1..5 {
+            for c in b + 1..5 {
+                if feasible(&availability, &[a, b, c], &mut Vec::new()) { expected.insert(BTreeSet::from([a, b, c])); }
+            }
+        }
+    }
+    assert!(!expected.is_empty() && expected.len() < 10);
+    assert_eq!(triples, expected);
+}
+
+#[test]
+fn groups_are_checked_up_front() {
+    let mut schedule = Schedule::from_availability(&availability(), None);
+    assert_eq!(schedule.require_count(&[0, 1, 2, 3, 4], 6), Err(GroupError::TooFewCourses { courses: 5, count: 6 }));
+    assert_eq!(schedule.require_count(&[0, 5], 1), Err(GroupError::Course(5)));
+    assert_eq!(schedule.require_count(&[1, 1], 1), Err(GroupError::Course(1)));
+
+    schedule.require_count(&[0, 1], 1).unwrap();
+    assert_eq!(schedule.require_count(&[1, 2], 1), Err(GroupError::Course(1)));
+    assert!(schedule.solve_best().is_some());
+}
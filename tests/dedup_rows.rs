@@ -0,0 +1,36 @@
+mod common;
+
+use dlx::{Node, SolveOptions};
+use common::{matrix, random, sorted};
+
+#[test]
+fn copies_map_back_to_the_originals() {
+    let input = matrix(&["110", "001", "110", "100", "011", "001", "110"]);
+    let (rows, originals) = Node::dedup_rows(&input);
+    // kept in the order of their first copies
+    assert_eq!(rows, matrix(&["110", "001", "100", "011"]));
+    assert_eq!(originals, [vec![0, 2, 6], vec![1, 5], vec![3], vec![4]]);
+}
+
+#[test]
+fn solutions_drop_to_the_distinct_ones() {
+    let input = matrix(&["110", "001", "110", "100", "011", "001", "110"]);
+    // three copies of one row and two of the other, or the other pair
+    assert_eq!(Node::solve_all(&input).len(), 3 * 2 + 1);
+    let options = SolveOptions { dedup_rows: true, ..SolveOptions::default() };
+    assert_eq!(sorted(Node::solve_all_with_options(&input, &options).0), [vec![0, 1], vec![3, 4]]);
+}
+
+#[test]
+fn doubled_matrices_solve_like_the_original() {
+    let options = SolveOptions { dedup_rows: true, ..SolveOptions::default() };
+    for seed in 0..100 {
+        let input = random(seed, 8, 6, 35);
+        let doubled: Vec<Vec<bool>> = input.iter().chain(&input).cloned().collect();
+        let (rows, _) = Node::dedup_rows(&input);
+        // the first copy of every row is kept, so solutions use the same indices
+        let distinct = sorted(Node::solve_all_with_options(&input, &options).0);
+        assert_eq!(sorted(Node::solve_all_with_options(&doubled, &options).0), distinct, "seed {seed}");
+        assert_eq!(distinct.len(), Node::solve_all(&rows).len(), "seed {seed}");
+    }
+}
@@ -0,0 +1,46 @@
+use dlx::{Node, Solver};
+use std::thread;
+
+/// The identity matrix, solved by choosing every row: one level of search per row.
+fn identity(n: usize) -> Vec<Vec<bool>> {
+    (0..n).map(|row| (0..n).map(|col| col == row).collect()).collect()
+}
+
+#[test]
+fn deep_searches_fit_a_small_stack() {
+    let input = identity(2000);
+    // a frame per level would need far more than this
+    let worker = thread::Builder::new().stack_size(64 * 1024).spawn(move || {
+        let once = Node::solve_once(&input).unwrap();
+        let all = Node::solve_all(&input);
+        let count = Solver::new(&input).solve_all().len();
+        (once, all, count)
+    }).unwrap();
+
+    let (once, all, count) = worker.join().unwrap();
+    assert_eq!(once.len(), 2000);
+    assert_eq!(all, [once]);
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn every_search_fits_a_small_stack() {
+    let input = identity(2000);
+    let worker = thread::Builder::new().stack_size(64 * 1024).spawn(move || {
+        let count = Node::count_solutions(&input);
+        let zdd = Node::solve_to_zdd(&input).count();
+        let (mut cover, covered) = Node::solve_max_cover(&input);
+        let mut fewest = Node::solve_fewest_rows(&input).unwrap();
+        cover.sort_unstable();
+        fewest.sort_unstable();
+        let lex_min = Node::solve_lex_min(&input).unwrap();
+        (count, zdd, cover, covered, fewest, lex_min)
+    }).unwrap();
+
+    let (count, zdd, cover, covered, fewest, lex_min) = worker.join().unwrap();
+    let rows: Vec<usize> = (0..2000).collect();
+    assert_eq!((count, zdd), (1, 1));
+    assert_eq!((cover, covered), (rows.clone(), 2000));
+    assert_eq!(fewest, rows);
+    assert_eq!(lex_min, rows);
+}
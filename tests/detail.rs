@@ -0,0 +1,40 @@
+mod common;
+
+use dlx::{Node, SolveOptions};
+use dlx::generation::blocks::Game2D;
+use common::{PENTOMINOES, matrix};
+
+#[test]
+fn every_cell_maps_to_its_placement() {
+    let mut game = Game2D::from_strings(20, 3, PENTOMINOES.to_vec()).unwrap();
+    let input = game.get_matrix();
+    let placements = game.get_placements();
+    let details = Node::solve_all_detailed(&input);
+    assert_eq!(details.len(), 8);
+
+    for detail in &details {
+        assert_eq!(Node::verify_solution(&input, &detail.rows), Ok(()));
+        assert_eq!(detail.covered_by.len(), input[0].len());
+        for (col, row) in detail.covered_by.iter().enumerate() {
+            let row = row.unwrap();
+            assert!(detail.rows.contains(&row) && input[row][col]);
+        }
+        // a column for every block, then one for every cell
+        for block in 0..12 { assert_eq!(placements[detail.covered_by[block].unwrap()].block, block); }
+        for (y, x) in (0..3).flat_map(|y| (0..20).map(move |x| (y, x))) {
+            let row = detail.covered_by[12 + y * 20 + x].unwrap();
+            assert!(placements[row].cells().contains(&(x, y)), "({x}, {y})");
+        }
+    }
+}
+
+#[test]
+fn uncovered_secondary_columns_have_no_row() {
+    let input = matrix(&["101", "010", "110"]);
+    let options = SolveOptions { primary_columns: Some(2), ..SolveOptions::default() };
+    let mut details: Vec<_> = Node::solve_all_detailed_with_options(&input, &options).0.into_iter()
+        .map(|detail| (detail.rows, detail.covered_by))
+        .collect();
+    details.sort();
+    assert_eq!(details, [(vec![0, 1], vec![Some(0), Some(1), Some(0)]), (vec![2], vec![Some(2), Some(2), None])]);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use std::collections::BTreeSet;
+use dlx::Node;
+use common::{matrix, random, sorted};
+
+/// Get the number of variables and clauses from the header of a formula.
+fn header(cnf: &str) -> (usize, usize) {
+    let line = cnf.lines().find(|line| !line.starts_with('c')).unwrap();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    assert_eq!(words[..2], ["p", "cnf"]);
+    (words[2].parse().unwrap(), words[3].parse().unwrap())
+}
+
+/// Find every model of a formula by trying every assignment.
+fn models(cnf: &str) -> Vec<Vec<bool>> {
+    let (variables, count) = header(cnf);
+    let clauses: Vec<Vec<i64>> = cnf.lines().filter(|line| !line.starts_with(['c', 'p'])).map(|line| {
+        let mut literals: Vec<i64> = line.split_whitespace().map(|literal| literal.parse().unwrap()).collect();
+        assert_eq!(literals.pop(), Some(0), "{line}");
+        literals
+    }).collect();
+    assert_eq!(clauses.len(), count);
+
+    let mut found = Vec::new();
+    for mask in 0u64..1 << variables {
+        let assignment: Vec<bool> = (0..variables).map(|i| mask >> i & 1 == 1).collect();
+        let holds = |literal: &i64| assignment[literal.unsigned_abs() as usize - 1] == (*literal > 0);
+        if clauses.iter().all(|clause| clause.iter().any(holds)) { found.push(assignment); }
+    }
+    found
+}
+
+#[test]
+fn models_are_the_solutions() {
+    let mut counted = 0;
+    for seed in 0..200 {
+        let rows = 4 + seed as usize % 7;
+        let input = random(seed, rows, 3, if seed.is_multiple_of(2) { 30 } else { 80 });
+        let cnf = Node::to_dimacs_cnf(&input);
+        // keep the brute force quick
+        let (variables, _) = header(&cnf);
+        if variables > 16 { continue; }
+        if variables > rows { counted += 1; }
+
+        let models = models(&cnf);
+        let solutions: BTreeSet<Vec<usize>> = models.iter().map(|model| Node::solution_from_assignment(&input, model)).collect();
+        // the counters of a wide column are fixed by the rows chosen
+        assert_eq!(solutions.len(), models.len(), "seed {seed}");
+        assert_eq!(solutions, sorted(Node::solve_all(&input)).into_iter().collect(), "seed {seed}\n{cnf}");
+    }
+    assert!(counted > 0);
+}
+
+#[test]
+fn column_without_rows_is_an_empty_clause() {
+    let cnf = Node::to_dimacs_cnf(&matrix(&["10", "10"]));
+    assert!(cnf.lines().any(|line| line == "0"), "{cnf}");
+    assert!(models(&cnf).is_empty());
+}
@@ -0,0 +1,41 @@
+use std::collections::BTreeSet;
+use dlx::Node;
+use dlx::generation::blocks::Game2D;
+
+#[test]
+fn two_shapes_but_not_one() {
+    // the square leaves a column of two cells only the domino fits
+    let mut game = Game2D::from_strings(3, 2, vec!["##\n##", "##", "##\n##"]).unwrap();
+    assert!(game.max_distinct_pieces(1).is_empty());
+    let tilings = game.max_distinct_pieces(2);
+    // the square goes left or right, and either square can be it
+    assert_eq!(tilings.len(), 2 * 2);
+    assert!(tilings.iter().all(|tiling| tiling.shapes == vec![0, 1]));
+}
+
+#[test]
+fn repeats_of_a_shape_count_once() {
+    let mut game = Game2D::from_strings(4, 2, vec!["##", "#\n#", "##", "##"]).unwrap();
+    let tilings = game.max_distinct_pieces(1);
+    // the 5 domino tilings of the board, each with the 4! ways to pick which block goes where
+    assert_eq!(tilings.len(), 5 * 24);
+    assert!(tilings.iter().all(|tiling| tiling.shapes == vec![0]));
+}
+
+#[test]
+fn unbounded_matches_the_ordinary_solve() {
+    let mut game = Game2D::from_strings(4, 2, vec!["##\n##", "##", "#\n#"]).unwrap();
+    let tilings: BTreeSet<Vec<usize>> = game.max_distinct_pieces(usize::MAX).into_iter().map(|tiling| {
+        let mut rows = tiling.rows;
+        rows.sort_unstable();
+        rows
+    }).collect();
+
+    let matrix = game.get_matrix();
+    let solutions: BTreeSet<Vec<usize>> = Node::solve_all(&matrix).into_iter().map(|mut rows| {
+        rows.sort_unstable();
+        rows
+    }).collect();
+    assert!(!solutions.is_empty());
+    assert_eq!(tilings, solutions);
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use dlx::{Node, RowSet};
+use common::dominoes;
+
+fn distance(a: &[usize], b: &[usize], rows: usize) -> usize {
+    RowSet::from_indices(a, rows).symmetric_difference(&RowSet::from_indices(b, rows)).len()
+}
+
+#[test]
+fn diverse_solutions_are_far_apart() {
+    let input = dominoes(4, 4);
+    let diverse = Node::solve_diverse(&input, 5, 6);
+    assert_eq!(diverse.len(), 5);
+    for (i, a) in diverse.iter().enumerate() {
+        for b in &diverse[i + 1..] {
+            assert!(distance(a, b, input.len()) >= 6, "{a:?} and {b:?}");
+        }
+    }
+}
+
+#[test]
+fn diverse_solutions_run_out() {
+    let input = dominoes(4, 4);
+    assert_eq!(Node::solve_diverse(&input, 100, 0), Node::solve_all(&input));
+    assert!(Node::solve_diverse(&input, 100, 9).len() < 36);
+    assert!(Node::solve_diverse(&input, 0, 0).is_empty());
+}
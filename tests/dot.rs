@@ -0,0 +1,40 @@
+mod common;
+
+use dlx::{Node, SolutionIter};
+use common::{matrix, random};
+
+/// Count how often a piece of text appears in a graph.
+fn count(dot: &str, text: &str) -> usize {
+    dot.matches(text).count()
+}
+
+#[test]
+fn graph_has_every_node_and_link() {
+    let input = matrix(&["101", "010", "110", "001"]);
+    let dot = Node::to_dot(&input);
+    assert!(dot.starts_with("digraph dlx {") && dot.ends_with("}\n"), "{dot}");
+    assert_eq!(count(&dot, "\\nsize "), 3);
+    assert!(dot.contains("column 0\\nsize 2"));
+    assert_eq!(count(&dot, "[label=\"row "), 6);
+    assert_eq!(count(&dot, "subgraph cluster_row"), 4);
+    // right links from the root, the 3 headers and the 4 nodes in rows of two; down links from the
+    // headers and the 6 nodes
+    assert_eq!(count(&dot, "[label=\"R\""), 8);
+    assert_eq!(count(&dot, "[label=\"D\""), 9);
+
+    let dot = Node::to_dot(&random(3, 15, 10, 30));
+    let entries = random(3, 15, 10, 30).iter().flatten().filter(|&&entry| entry).count();
+    assert_eq!(count(&dot, "[label=\"row "), entries);
+}
+
+#[test]
+fn covered_columns_disappear_mid_search() {
+    let input = matrix(&["101", "010", "110", "001"]);
+    let mut iter = SolutionIter::new(&input);
+    assert_eq!(iter.to_dot(), Node::to_dot(&input));
+    iter.next().unwrap();
+    // every column is covered at a solution, leaving the root alone
+    let dot = iter.to_dot();
+    assert_eq!(count(&dot, "\\nsize "), 0);
+    assert_eq!(count(&dot, "[label=\"R\""), 1);
+}
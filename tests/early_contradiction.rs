@@ -0,0 +1,32 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions};
+use common::{matrix, random};
+
+#[test]
+fn solutions_are_unchanged() {
+    for seed in 0..300 {
+        let input = random(seed, 14, 9, 30);
+        for column_strategy in [ColumnStrategy::Smallest, ColumnStrategy::Bucketed, ColumnStrategy::Lookahead { candidates: 3 }] {
+            let plain = SolveOptions { column_strategy, ..SolveOptions::default() };
+            let early = SolveOptions { early_contradiction: true, ..plain.clone() };
+            let (expected, without) = Node::solve_all_with_options(&input, &plain);
+            let (solutions, with) = Node::solve_all_with_options(&input, &early);
+            assert_eq!(solutions, expected, "seed {seed}, {column_strategy:?}");
+            assert!(with.nodes <= without.nodes, "seed {seed}, {column_strategy:?}");
+            assert_eq!(Node::solve_once_with_options(&input, &early).0, expected.first().cloned(), "seed {seed}");
+            assert_eq!(Node::count_solutions_with_options(&input, &early).0, expected.len() as u64, "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn poison_rows_backtrack_at_once() {
+    // the first row of column 0 takes the only rows of column 3 away with column 1
+    let input = matrix(&["1100", "1000", "0111", "0010", "0101"]);
+    let (expected, without) = Node::solve_all_with_options(&input, &SolveOptions::default());
+    let (solutions, with) = Node::solve_all_with_options(&input, &SolveOptions { early_contradiction: true, ..SolveOptions::default() });
+    assert_eq!(solutions, expected);
+    assert_eq!(solutions.len(), 2);
+    assert!(with.nodes < without.nodes, "{} nodes with the check, {} without", with.nodes, without.nodes);
+}
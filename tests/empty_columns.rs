@@ -0,0 +1,50 @@
+mod common;
+
+use dlx::{ColumnStrategy, Dlx, Node, SolveOptions};
+use common::{random, sorted};
+
+/// Count the exact covers of a matrix by trying every set of rows.
+fn brute_force(input: &[Vec<bool>]) -> usize {
+    let width = input[0].len();
+    (0u32..1 << input.len()).filter(|&set| {
+        let chosen: Vec<&Vec<bool>> = (0..input.len()).filter(|row| set >> row & 1 == 1).map(|row| &input[row]).collect();
+        // an empty row adds nothing, so it is never part of a solution
+        chosen.iter().all(|row| row.contains(&true)) && (0..width).all(|x| chosen.iter().filter(|row| row[x]).count() == 1)
+    }).count()
+}
+
+#[test]
+fn uncoverable_columns_leave_no_solution() {
+    let input = vec![vec![true, false]];
+    assert!(Node::solve_all(&input).is_empty());
+    assert_eq!(Node::solve_once(&input), None);
+    assert_eq!(Node::count_solutions(&input), 0);
+    assert!(Dlx::new(&input).solve_all().is_empty());
+}
+
+#[test]
+fn every_search_agrees_with_brute_force() {
+    let strategies = |seed| [
+        ColumnStrategy::Smallest,
+        ColumnStrategy::Bucketed,
+        ColumnStrategy::First,
+        ColumnStrategy::Lookahead { candidates: 3 },
+        ColumnStrategy::Random { seed },
+    ];
+    for seed in 0..200 {
+        // sparse enough that some columns have no rows
+        let input = random(seed, 10, 8, 25);
+        let all = Node::solve_all(&input);
+        assert_eq!(all.len(), brute_force(&input), "seed {seed}");
+        assert_eq!(Node::solve_to_zdd(&input).count(), all.len() as u64, "seed {seed}");
+        assert_eq!(Dlx::new(&input).solve_all(), all, "seed {seed}");
+
+        for column_strategy in strategies(seed) {
+            for early_contradiction in [false, true] {
+                let options = SolveOptions { column_strategy, early_contradiction, memo_capacity: Some(100), ..SolveOptions::default() };
+                assert_eq!(sorted(Node::solve_all_with_options(&input, &options).0), sorted(all.clone()), "seed {seed}, {options:?}");
+                assert_eq!(Node::count_solutions_with_options(&input, &options).0, all.len() as u64, "seed {seed}, {options:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, permutations, random};
+
+#[test]
+fn estimate_lands_near_the_tree_size() {
+    for input in [dominoes(4, 4), permutations(5), dominoes(3, 6)] {
+        let nodes = Node::solve_all_with_stats(&input).1.nodes as f64;
+        let estimate = Node::estimate_tree_size(&input, 20_000, 1);
+        assert!(estimate > nodes / 1.5 && estimate < nodes * 1.5, "{estimate} against {nodes} nodes");
+    }
+    for seed in 0..100 {
+        let input = random(seed, 10, 6, 35);
+        let nodes = Node::solve_all_with_stats(&input).1.nodes as f64;
+        let estimate = Node::estimate_tree_size(&input, 5_000, seed);
+        assert!(estimate > nodes / 2.0 && estimate < nodes * 2.0, "seed {seed}: {estimate} against {nodes} nodes");
+    }
+}
+
+#[test]
+fn estimate_depends_only_on_the_seed() {
+    let input = dominoes(4, 4);
+    assert_eq!(Node::estimate_tree_size(&input, 100, 7), Node::estimate_tree_size(&input, 100, 7));
+}
+
+#[test]
+fn tree_without_branches_is_exact() {
+    let input = matrix(&["10", "01"]);
+    assert_eq!(Node::estimate_tree_size(&input, 5, 0), 3.0);
+    assert_eq!(Node::estimate_tree_size(&input, 0, 0), 0.0);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::compose::{ComposeError, Exclusions, forbid_pairs};
+use common::{matrix, random, sorted};
+
+/// Solve a compiled matrix, giving the solutions as rows of the original one.
+fn solve(exclusions: &Exclusions) -> Vec<Vec<usize>> {
+    sorted(Node::solve_all(&exclusions.matrix).iter().map(|solution| exclusions.decode(solution)).collect())
+}
+
+/// The solutions of a matrix without both rows of any pair.
+fn allowed(input: &[Vec<bool>], pairs: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let solutions = sorted(Node::solve_all(input));
+    solutions.into_iter().filter(|solution| !pairs.iter().any(|(a, b)| solution.contains(a) && solution.contains(b))).collect()
+}
+
+#[test]
+fn forbidding_the_only_cover_leaves_none() {
+    let input = matrix(&["1100", "0011"]);
+    assert!(solve(&forbid_pairs(&input, &[(0, 1)]).unwrap()).is_empty());
+
+    // without the pair, the matrix is the same and so are its solutions
+    let unchanged = forbid_pairs(&input, &[]).unwrap();
+    assert_eq!(unchanged.matrix, input);
+    assert!(unchanged.secondary.is_empty());
+    assert_eq!(solve(&unchanged), [vec![0, 1]]);
+}
+
+#[test]
+fn forbidden_pairs_filter_the_solutions() {
+    let input = matrix(&["1100", "0011", "1000", "0100", "0010", "0001"]);
+    let pairs = [(0, 1), (2, 5)];
+    let solutions = solve(&forbid_pairs(&input, &pairs).unwrap());
+    assert_eq!(solutions, allowed(&input, &pairs));
+    assert!(solutions.len() < Node::solve_all(&input).len());
+
+    for seed in 0..200 {
+        let input = random(seed, 10, 6, 35);
+        let pairs: Vec<(usize, usize)> = (0..6).map(|i| {
+            let a = (seed as usize * 7 + i * 3) % 10;
+            (a, (a + 1 + (seed as usize + i) % 9) % 10)
+        }).collect();
+        assert_eq!(solve(&forbid_pairs(&input, &pairs).unwrap()), allowed(&input, &pairs), "seed {seed}");
+    }
+}
+
+#[test]
+fn groups_share_a_column() {
+    let input = matrix(&["1100", "0011", "1000", "0100", "0010", "0001"]);
+    // every pair of the last four rows is forbidden, which one column does
+    let pairs: Vec<(usize, usize)> = (2..6).flat_map(|a| (a + 1..6).map(move |b| (a, b))).collect();
+    let exclusions = forbid_pairs(&input, &pairs).unwrap();
+    assert_eq!(exclusions.secondary, [4]);
+    assert_eq!(solve(&exclusions), allowed(&input, &pairs));
+}
+
+#[test]
+fn rejects_bad_pairs() {
+    let input = matrix(&["10", "01"]);
+    assert_eq!(forbid_pairs(&input, &[(1, 1)]), Err(ComposeError::SelfExclusion { row: 1 }));
+    assert_eq!(forbid_pairs(&input, &[(0, 2)]), Err(ComposeError::RowOutOfRange { row: 2, rows: 2 }));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use dlx::{Node, UnsatReason};
+use common::{dominoes, matrix, random};
+
+#[test]
+fn column_without_rows() {
+    assert_eq!(Node::explain_unsat(&matrix(&["101", "100"])), Some(UnsatReason::EmptyColumn { column: 1 }));
+}
+
+#[test]
+fn column_emptied_by_forced_rows() {
+    // column 1 forces row 2 and column 2 forces row 0, which takes column 0 from row 1, the only row
+    // of column 3
+    let input = matrix(&["1010", "1001", "0100"]);
+    assert_eq!(Node::explain_unsat(&input), Some(UnsatReason::ForcedOut { column: 3, forced_rows: vec![2, 0] }));
+}
+
+#[test]
+fn deeper_conflicts_exhaust_the_search() {
+    let reason = Node::explain_unsat(&matrix(&["110", "011", "101"]));
+    assert!(matches!(reason, Some(UnsatReason::SearchExhausted { nodes_visited }) if nodes_visited > 1), "{reason:?}");
+}
+
+#[test]
+fn only_unsatisfiable_matrices_are_explained() {
+    assert_eq!(Node::explain_unsat(&dominoes(4, 4)), None);
+    for seed in 0..300 {
+        let input = random(seed, 8, 6, 30);
+        assert_eq!(Node::explain_unsat(&input).is_none(), Node::solve_once(&input).is_some(), "seed {seed}");
+    }
+}
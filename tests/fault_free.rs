@@ -0,0 +1,43 @@
+use dlx::Node;
+use dlx::generation::blocks::{Game2D, Solution2D};
+
+/// A tiling with nothing but its grid, written as rows of block digits.
+fn tiling(rows: &[&str]) -> Solution2D {
+    let grid = rows.iter().map(|row| row.chars().map(|c| c.to_digit(10).map(|block| block as usize)).collect()).collect();
+    Solution2D { placements: Vec::new(), grid }
+}
+
+/// The tiling made of the given rows of a game's matrix.
+fn tiling_of(game: &Game2D, rows: &[usize]) -> Solution2D {
+    let placements = game.get_placements();
+    Solution2D { grid: game.solution_grid(rows), placements: rows.iter().map(|&row| placements[row].clone()).collect() }
+}
+
+#[test]
+fn fault_lines_cross_the_whole_board() {
+    assert!(tiling(&["0011", "2233"]).has_fault_line());
+    assert!(tiling(&["01", "01", "22"]).has_fault_line());
+    // every line between columns or rows cuts a domino of the pinwheel
+    assert!(!tiling(&["001", "2.1", "233"]).has_fault_line());
+    assert!(!tiling(&["0"]).has_fault_line());
+}
+
+#[test]
+fn dominoes_on_two_rows_always_have_a_fault() {
+    let mut game = Game2D::from_strings(6, 2, vec!["##"; 6]).unwrap();
+    assert!(game.solve_fault_free(10).is_empty());
+}
+
+#[test]
+fn pinwheels_are_fault_free() {
+    // four dominoes turning around the middle square, either way, in any order
+    let mut game = Game2D::from_strings(3, 3, vec!["##", "##", "##", "##", "#"]).unwrap();
+    let tilings = game.solve_fault_free(100);
+    assert_eq!(tilings.len(), 2 * 24);
+    assert!(tilings.iter().all(|tiling| game.validate_layout(tiling) && !tiling.has_fault_line()));
+
+    let all = Node::solve_all(&game.get_matrix());
+    let fault_free = all.iter().filter(|rows| !tiling_of(&game, rows).has_fault_line()).count();
+    assert_eq!(fault_free, tilings.len());
+    assert_eq!(game.solve_fault_free(5).len(), 5);
+}
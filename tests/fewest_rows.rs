@@ -0,0 +1,27 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, random};
+
+#[test]
+fn shortest_cover_beats_the_first_one() {
+    let input = matrix(&["1000", "0100", "0010", "0001", "1111"]);
+    assert_eq!(Node::solve_all(&input)[0].len(), 4);
+    assert_eq!(Node::solve_fewest_rows(&input), Some(vec![4]));
+    assert_eq!(Node::solve_fewest_rows(&dominoes(4, 4)).unwrap().len(), 8);
+}
+
+#[test]
+fn unsatisfiable_matrices_have_no_cover() {
+    assert_eq!(Node::solve_fewest_rows(&matrix(&["10", "00"])), None);
+    assert_eq!(Node::solve_fewest_rows(&matrix(&["00", "00"])), None);
+}
+
+#[test]
+fn fewest_rows_is_the_first_shortest_solution() {
+    for seed in 0..200 {
+        let input = random(seed, 16, 8, 30);
+        let expected = Node::solve_all(&input).into_iter().min_by_key(Vec::len);
+        assert_eq!(Node::solve_fewest_rows(&input), expected, "seed {seed}");
+    }
+}
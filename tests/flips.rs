@@ -0,0 +1,46 @@
+use dlx::Node;
+use dlx::generation::blocks::{Game2D, TransformOp};
+
+/// Count the flipped placements of a solution.
+fn flips(game: &mut Game2D, rows: &[usize]) -> u64 {
+    let placements = game.get_placements();
+    rows.iter().filter(|&&row| placements[row].op.flip).count() as u64
+}
+
+#[test]
+fn fewest_flips_avoids_the_first_solution() {
+    let mut game = Game2D::from_strings(3, 4, vec!["###\n#..", "###\n#..", "##\n##"]).unwrap();
+    let matrix = game.get_matrix();
+    let first = Node::solve_once(&matrix).unwrap();
+    assert_eq!(flips(&mut game, &first), 2);
+
+    let (cost, rows) = game.solve_min_flips().unwrap();
+    assert_eq!(cost, 0);
+    assert_eq!(flips(&mut game, &rows), 0);
+    assert!(Node::verify_solution(&matrix, &rows).is_ok());
+}
+
+#[test]
+fn fewest_flips_is_the_minimum() {
+    let games = [
+        (3, 3, vec!["###\n#..", "###\n..#", "#"]),
+        (4, 4, vec!["###\n#..", "###\n#..", "###\n#..", "###\n#.."]),
+        (2, 4, vec!["###\n#..", "###", "#"]),
+    ];
+    for (w, h, blocks) in games {
+        let mut game = Game2D::from_strings(w, h, blocks).unwrap();
+        let matrix = game.get_matrix();
+        let fewest = Node::solve_all(&matrix).iter().map(|rows| flips(&mut game, rows)).min();
+        assert_eq!(game.solve_min_flips().map(|(cost, _)| cost), fewest, "{w}x{h}");
+    }
+}
+
+#[test]
+fn transform_costs_are_pluggable() {
+    let mut game = Game2D::from_strings(2, 4, vec!["###\n#..", "###", "#"]).unwrap();
+    game.set_transform_cost(|op: &TransformOp| u64::from(op.rotations) + 10 * u64::from(op.flip));
+    let (cost, rows) = game.solve_min_flips().unwrap();
+    let placements = game.get_placements();
+    assert_eq!(cost, rows.iter().map(|&row| u64::from(placements[row].op.rotations) + 10 * u64::from(placements[row].op.flip)).sum::<u64>());
+    assert!(cost < 10);
+}
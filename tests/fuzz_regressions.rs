@@ -0,0 +1,36 @@
+//! Runs the checks of the fuzz targets over the inputs in `fuzz/regressions`, one directory per
+//! target. Copy a crash `cargo fuzz` finds under `fuzz/artifacts` there to keep it fixed.
+
+use std::fs;
+use std::panic;
+use std::path::Path;
+use arbitrary::{Arbitrary, Unstructured};
+use dlx::fuzz::{FuzzGame2D, FuzzMatrix};
+
+/// Get every input kept for a fuzz target, with its file name.
+fn inputs(target: &str) -> Vec<(String, Vec<u8>)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions").join(target);
+    let inputs: Vec<_> = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read(&path).unwrap()))
+        .collect();
+    assert!(!inputs.is_empty(), "{} has no inputs", dir.display());
+    inputs
+}
+
+#[test]
+fn solve_matrix() {
+    for (name, data) in inputs("solve_matrix") {
+        let matrix = FuzzMatrix::arbitrary_take_rest(Unstructured::new(&data)).unwrap_or_else(|error| panic!("{name}: {error}"));
+        // the check's own panic message is printed first, this one names the input
+        assert!(panic::catch_unwind(|| matrix.check()).is_ok(), "{name} fails its check");
+    }
+}
+
+#[test]
+fn solve_game2d() {
+    for (name, data) in inputs("solve_game2d") {
+        let game = FuzzGame2D::arbitrary_take_rest(Unstructured::new(&data)).unwrap_or_else(|error| panic!("{name}: {error}"));
+        assert!(panic::catch_unwind(|| game.check()).is_ok(), "{name} fails its check");
+    }
+}
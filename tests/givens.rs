@@ -0,0 +1,36 @@
+mod common;
+
+use dlx::{ConflictError, Node};
+use common::{matrix, random, sorted};
+
+#[test]
+fn no_givens_is_solve_all() {
+    for seed in 0..100 {
+        let input = random(seed, 10, 7, 30);
+        assert_eq!(Node::solve_all_with_givens(&input, &[]).unwrap(), Node::solve_all(&input), "seed {seed}");
+    }
+}
+
+#[test]
+fn solutions_include_the_givens() {
+    for seed in 0..200 {
+        let input = random(seed, 10, 7, 30);
+        let all = sorted(Node::solve_all(&input));
+        for given in (0..input.len()).filter(|&row| input[row].contains(&true)) {
+            let solutions = Node::solve_all_with_givens(&input, &[given]).unwrap();
+            assert!(solutions.iter().all(|solution| solution[0] == given), "seed {seed}, given {given}");
+            let expected: Vec<Vec<usize>> = all.iter().filter(|solution| solution.contains(&given)).cloned().collect();
+            assert_eq!(sorted(solutions), expected, "seed {seed}, given {given}");
+        }
+    }
+}
+
+#[test]
+fn overlapping_givens_are_an_error() {
+    let input = matrix(&["110", "011", "001", "100"]);
+    let error = Node::solve_all_with_givens(&input, &[0, 1]).unwrap_err();
+    assert_eq!(error, ConflictError { column: 1, rows: (0, 1) });
+    assert_eq!(error.to_string(), "rows 0 and 1 both cover column 1");
+    assert_eq!(Node::solve_all_with_givens(&input, &[0]).unwrap(), [vec![0, 2]]);
+    assert_eq!(Node::solve_all_with_givens(&input, &[3, 1]).unwrap(), [vec![3, 1]]);
+}
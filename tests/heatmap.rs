@@ -0,0 +1,28 @@
+use dlx::generation::blocks::{CountMode, Game2D};
+
+#[test]
+fn unique_solutions_are_forced_everywhere() {
+    // the ring can only go around the middle cell
+    let mut game = Game2D::from_strings(3, 3, vec!["###\n#.#\n###", "#"]).unwrap();
+    assert_eq!(game.count_solutions(CountMode::Raw), 1);
+    let heatmap = game.coverage_heatmap();
+    for (y, line) in heatmap.iter().enumerate() {
+        for (x, counts) in line.iter().enumerate() {
+            let block = if (x, y) == (1, 1) { 1 } else { 0 };
+            assert_eq!(counts.iter().collect::<Vec<_>>(), [(&block, &1)], "({x}, {y})");
+        }
+    }
+    let (ring, single) = (game.block_char(0), game.block_char(1));
+    assert_eq!(game.render_heatmap(), format!("{ring}{ring}{ring}\n{ring}{single}{ring}\n{ring}{ring}{ring}\n"));
+}
+
+#[test]
+fn cells_add_up_to_the_solutions() {
+    let mut game = Game2D::from_strings(3, 2, vec!["##"; 3]).unwrap();
+    let solutions = game.count_solutions(CountMode::Raw);
+    let heatmap = game.coverage_heatmap();
+    assert!(heatmap.iter().flatten().all(|counts| counts.values().sum::<u64>() == solutions));
+    // each domino covers each cell as often as the others do
+    assert!(heatmap.iter().flatten().all(|counts| counts.len() == 3 && counts.values().all(|&count| count * 3 == solutions)));
+    assert!(game.render_heatmap().lines().all(|line| line == "333"));
+}
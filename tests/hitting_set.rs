@@ -0,0 +1,47 @@
+mod common;
+
+use std::panic;
+use dlx::Node;
+use common::{random, sorted};
+
+#[test]
+fn transpose_swaps_rows_and_columns() {
+    let input = random(4, 6, 9, 40);
+    let transposed = Node::transpose(&input);
+    assert_eq!((transposed.len(), transposed[0].len()), (9, 6));
+    assert_eq!(Node::transpose(&transposed), input);
+    assert!(Node::transpose(&[]).is_empty());
+}
+
+#[test]
+fn hitting_sets_of_a_cycle() {
+    let sets = vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]];
+    assert_eq!(sorted(Node::solve_hitting_set(&sets, 4)), vec![vec![0, 2], vec![1, 3]]);
+    // element 4 is in no set, so it's never chosen
+    assert_eq!(sorted(Node::solve_hitting_set(&sets, 5)), vec![vec![0, 2], vec![1, 3]]);
+    // an odd cycle can't be hit once each
+    assert!(Node::solve_hitting_set(&[vec![0, 1], vec![1, 2], vec![2, 0]], 3).is_empty());
+}
+
+#[test]
+fn empty_and_repeated_sets() {
+    assert!(Node::solve_hitting_set(&[vec![0], vec![]], 1).is_empty());
+    assert_eq!(Node::solve_hitting_set(&[], 3), vec![Vec::<usize>::new()]);
+    assert_eq!(Node::solve_hitting_set(&[vec![0, 0, 1], vec![1]], 2), vec![vec![1]]);
+    assert!(panic::catch_unwind(|| Node::solve_hitting_set(&[vec![5]], 3)).is_err());
+}
+
+#[test]
+fn hitting_sets_are_covers_of_the_transpose() {
+    for seed in 0..20 {
+        let input = random(seed, 8, 10, 30);
+        let sets: Vec<Vec<usize>> = input.iter().map(|row| (0..10).filter(|&x| row[x]).collect()).collect();
+        let hitting = Node::solve_hitting_set(&sets, 10);
+        for elements in &hitting {
+            assert!(sets.iter().all(|set| set.iter().filter(|x| elements.contains(x)).count() == 1), "seed {seed}");
+        }
+        if sets.iter().all(|set| !set.is_empty()) {
+            assert_eq!(sorted(hitting), sorted(Node::solve_all(&Node::transpose(&input))), "seed {seed}");
+        }
+    }
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use dlx::{Node, RowId, SolveOptions, Solver};
+use common::{matrix, random, sorted};
+
+#[test]
+fn added_rows_come_and_go() {
+    let mut solver = Solver::new(&matrix(&["1100", "0011", "1000"]));
+    assert_eq!(sorted(solver.solve_all()), vec![vec![0, 1]]);
+
+    let pair = solver.add_row(&[1, 0]);
+    let single = solver.add_row(&[1]);
+    assert_eq!(pair, RowId { index: 3 });
+    assert_eq!(sorted(solver.solve_all()), vec![vec![0, 1], vec![1, 2, 4], vec![1, 3]]);
+    solver.remove_row(pair);
+    assert_eq!(sorted(solver.solve_all()), vec![vec![0, 1], vec![1, 2, 4]]);
+    // removing a row twice changes nothing the second time
+    solver.remove_row(single);
+    solver.remove_row(single);
+    assert_eq!(sorted(solver.solve_all()), vec![vec![0, 1]]);
+    assert_eq!(solver.count_solutions(), 1);
+}
+
+#[test]
+fn edits_match_rebuilding() {
+    for seed in 0..300 {
+        let mut input = random(seed, 10, 6, 35);
+        let mut choices = random(seed + 1000, 12, 8, 50).into_iter();
+        let options = SolveOptions { forbid_rows: vec![seed as usize % 10], ..SolveOptions::default() };
+        let mut solver = Solver::with_options(&input, options.clone());
+
+        for step in 0..12 {
+            let choice = choices.next().unwrap();
+            if choice[0] {
+                // add a row on the columns picked out by the rest of the choice
+                let cols: Vec<usize> = (0..6).filter(|&col| choice[col + 1] && choice[7]).collect();
+                assert_eq!(solver.add_row(&cols), RowId { index: input.len() });
+                input.push((0..6).map(|col| cols.contains(&col)).collect());
+            } else {
+                let index = (step * 7 + seed as usize) % input.len();
+                solver.remove_row(RowId { index });
+                input[index] = vec![false; 6];
+            }
+            let expected = sorted(Node::solve_all_with_options(&input, &options).0);
+            assert_eq!(sorted(solver.solve_all()), expected, "seed {seed} step {step}");
+            assert_eq!(solver.count_solutions(), expected.len() as u64, "seed {seed} step {step}");
+        }
+    }
+}
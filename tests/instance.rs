@@ -0,0 +1,45 @@
+mod common;
+
+use dlx::{Node, ParseError};
+use common::{matrix, random};
+
+#[test]
+fn round_trip() {
+    for seed in 0..100 {
+        let input = random(seed, 1 + seed as usize % 7, 1 + seed as usize % 5, 30);
+        assert_eq!(Node::parse_instance(&Node::write_instance(&input)).unwrap(), input);
+    }
+}
+
+#[test]
+fn loads_pentominoes() {
+    let input = Node::parse_instance(include_str!("data/pentominoes-20x3.txt")).unwrap();
+    assert_eq!((input.len(), input[0].len()), (1236, 72));
+    // two tilings of the 20 by 3 rectangle, each rotated and mirrored
+    assert_eq!(Node::count_solutions(&input), 8);
+}
+
+#[test]
+fn skips_comments_and_blank_lines() {
+    let text = "# comment\np dlx 2 3\n1 3\n\n  # indented comment\n-\n";
+    assert_eq!(Node::parse_instance(text).unwrap(), matrix(&["101", "000"]));
+}
+
+#[test]
+fn reports_errors_by_line() {
+    assert_eq!(Node::parse_instance("# only a comment\n"), Err(ParseError::MissingHeader));
+    assert_eq!(Node::parse_instance("p dlx 2\n"), Err(ParseError::BadHeader { line: 1 }));
+    assert_eq!(Node::parse_instance("\np cnf 2 2\n"), Err(ParseError::BadHeader { line: 2 }));
+    assert_eq!(Node::parse_instance("p dlx 2 2\n1\n3\n"), Err(ParseError::ColumnOutOfRange { line: 3, column: 3 }));
+    assert_eq!(Node::parse_instance("p dlx 2 2\n0\n"), Err(ParseError::ColumnOutOfRange { line: 2, column: 0 }));
+    assert_eq!(Node::parse_instance("p dlx 2 2\n1 x\n"), Err(ParseError::BadColumn { line: 2 }));
+    assert_eq!(Node::parse_instance("p dlx 2 2\n1\n"), Err(ParseError::RowCount { expected: 2, got: 1 }));
+    assert_eq!(Node::parse_instance("p dlx 1 2\n1\n2\n# comment\n1 2\n"), Err(ParseError::RowCount { expected: 1, got: 3 }));
+}
+
+#[test]
+fn huge_headers_are_errors() {
+    let huge = "99999999999999999";
+    assert_eq!(Node::parse_instance(&format!("p dlx {huge} 3\n1\n")), Err(ParseError::RowCount { expected: 99999999999999999, got: 1 }));
+    assert_eq!(Node::parse_instance(&format!("p dlx 1 {huge}\n-\n")), Err(ParseError::TooWide { line: 2 }));
+}
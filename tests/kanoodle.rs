@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+use dlx::Node;
+use dlx::generation::blocks::TransformOp;
+use dlx::generation::presets::{kanoodle, kanoodle_challenge};
+
+const fn op(flip: bool, rotations: u8) -> TransformOp {
+    TransformOp { flip, rotations }
+}
+
+/// A challenge with eight of the twelve pieces placed, and its answer.
+const CHALLENGE: [(&str, TransformOp, usize, usize); 8] = [
+    ("L", op(false, 0), 1, 0),
+    ("A", op(true, 1), 0, 0),
+    ("B", op(true, 3), 0, 2),
+    ("C", op(true, 2), 3, 0),
+    ("E", op(true, 0), 2, 3),
+    ("H", op(false, 1), 2, 1),
+    ("I", op(false, 2), 5, 1),
+    ("J", op(false, 0), 5, 4),
+];
+const ANSWER: &str = "\
+AALCCCCDDDD
+ALLLHICIGDF
+ABLHHIIIGFF
+BBHHEEGGGKK
+BBEEEJJJJKK
+";
+
+#[test]
+fn challenge_has_the_known_answer() {
+    let mut game = kanoodle_challenge(&CHALLENGE);
+    let matrix = game.get_matrix();
+    let solutions = Node::solve_all(&matrix);
+    assert!(!solutions.is_empty());
+    assert!(solutions.iter().any(|solution| game.render_solution(solution) == ANSWER));
+}
+
+#[test]
+fn empty_board_has_many_solutions() {
+    let mut game = kanoodle();
+    assert_eq!(game.blocks.len(), 12);
+    assert_eq!(game.blocks.iter().map(|block| block.data.iter().flatten().filter(|filled| **filled).count()).sum::<usize>(), 11 * 5);
+
+    let matrix = game.get_matrix();
+    let solutions = Node::solve_n(&matrix, 500);
+    assert_eq!(solutions.len(), 500);
+    let layouts: HashSet<String> = solutions.iter().map(|solution| game.render_solution(solution)).collect();
+    assert_eq!(layouts.len(), 500);
+}
@@ -0,0 +1,61 @@
+use dlx::{LabeledMatrix, Node};
+
+/// A domino on the board: its id, the cell of its top left square, and whether it stands upright.
+#[derive(Debug, PartialEq)]
+struct Placement {
+    piece: usize,
+    x: usize,
+    y: usize,
+    rotation: u8,
+}
+
+impl Placement {
+    fn cells(&self) -> [(usize, usize); 2] {
+        if self.rotation == 0 { [(self.x, self.y), (self.x + 1, self.y)] } else { [(self.x, self.y), (self.x, self.y + 1)] }
+    }
+}
+
+/// The dominoes on a board, labelled with their placements.
+fn dominoes(w: usize, h: usize) -> LabeledMatrix<Placement> {
+    let mut matrix = LabeledMatrix::new();
+    let mut piece = 0;
+    for y in 0..h {
+        for x in 0..w {
+            for rotation in [0, 1] {
+                let placement = Placement { piece, x, y, rotation };
+                if placement.cells().iter().any(|&(x, y)| x >= w || y >= h) { continue; }
+                let mut row = vec![false; w * h];
+                for (x, y) in placement.cells() { row[y * w + x] = true; }
+                matrix.add_row(placement, row);
+                piece += 1;
+            }
+        }
+    }
+    matrix
+}
+
+#[test]
+fn placements_tile_the_board() {
+    let (w, h) = (3, 2);
+    let matrix = dominoes(w, h);
+    let solutions = matrix.solve_all();
+    assert_eq!(solutions.len(), 3);
+    for solution in &solutions {
+        let mut board = vec![0; w * h];
+        for placement in solution {
+            for (x, y) in placement.cells() { board[y * w + x] += 1; }
+        }
+        assert!(board.iter().all(|&count| count == 1), "{solution:?}");
+    }
+    assert_eq!(matrix.solve_once().as_ref(), solutions.first());
+}
+
+#[test]
+fn labels_follow_the_rows() {
+    let matrix = dominoes(4, 3);
+    let indices = Node::solve_all(matrix.rows());
+    let labelled: Vec<Vec<usize>> = matrix.solve_all().iter().map(|solution| solution.iter().map(|placement| placement.piece).collect()).collect();
+    // pieces are numbered in the order their rows were added
+    assert_eq!(labelled, indices);
+    assert!(matrix.labels().iter().enumerate().all(|(i, placement)| placement.piece == i));
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, permutations, random};
+
+/// The smallest solution, rows sorted, by enumerating them all.
+fn smallest(input: &[Vec<bool>]) -> Option<Vec<usize>> {
+    Node::solve_all(input).into_iter().map(|mut solution| {
+        solution.sort_unstable();
+        solution
+    }).min()
+}
+
+#[test]
+fn smallest_over_all_solutions() {
+    for seed in 0..400 {
+        let input = random(seed, 14, 7, 30);
+        assert_eq!(Node::solve_lex_min(&input), smallest(&input), "seed {seed}");
+    }
+    for seed in 0..300 {
+        let input = random(seed, 24, 6, 35);
+        assert_eq!(Node::solve_lex_min(&input), smallest(&input), "seed {seed}");
+    }
+    let tiles = dominoes(6, 6);
+    assert_eq!(Node::solve_lex_min(&tiles), smallest(&tiles));
+}
+
+#[test]
+fn small_examples() {
+    let input = matrix(&["1000", "0111", "0100", "1011", "0011"]);
+    assert_eq!(Node::solve_lex_min(&input), Some(vec![0, 1]));
+    let input = matrix(&["1100", "0011", "1000", "0100", "0010", "0001"]);
+    assert_eq!(Node::solve_lex_min(&input), Some(vec![0, 1]));
+    assert_eq!(Node::solve_lex_min(&matrix(&["10", "00"])), None);
+    assert_eq!(Node::solve_lex_min(&permutations(7)), Some((0..7).map(|i| i * 7 + i).collect()));
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use dlx::{ColumnRef, LinkedMatrix, Node};
+use common::{dominoes, random};
+
+/// Algorithm X written over the public structure: branch on the smallest column, leftmost on ties,
+/// trying its rows top to bottom, handing every solution to `found`. Leaves the matrix as it was.
+fn search(matrix: &mut LinkedMatrix, partial: &mut Vec<usize>, found: &mut impl FnMut(&[usize]) -> bool) -> bool {
+    if matrix.is_solved() { return found(partial); }
+    let col = matrix.columns().min_by_key(|&col| matrix.size(col)).unwrap();
+    matrix.cover(col);
+    let rows: Vec<(usize, Vec<ColumnRef>)> = matrix.rows_in(col).map(|row| (row.index(), row.cells().skip(1).collect())).collect();
+
+    let mut stop = false;
+    for (row, cells) in rows {
+        partial.push(row);
+        for &cell in &cells { matrix.cover(cell); }
+        stop = search(matrix, partial, found);
+        for &cell in cells.iter().rev() { matrix.uncover(cell); }
+        partial.pop();
+        if stop { break; }
+    }
+    matrix.uncover(col);
+    stop
+}
+
+#[test]
+fn reimplements_solve_once_and_solve_all() {
+    for seed in 0..200 {
+        let input = random(seed, 20, 8, 30);
+        let mut matrix = LinkedMatrix::build(&input);
+
+        let mut first = None;
+        search(&mut matrix, &mut Vec::new(), &mut |solution| {
+            first = Some(solution.to_vec());
+            true
+        });
+        assert_eq!(first, Node::solve_once(&input), "seed {seed}");
+
+        let mut all = Vec::new();
+        search(&mut matrix, &mut Vec::new(), &mut |solution| {
+            all.push(solution.to_vec());
+            false
+        });
+        assert_eq!(all, Node::solve_all(&input), "seed {seed}");
+        assert_eq!(matrix.columns().count(), 8, "seed {seed}");
+    }
+}
+
+#[test]
+fn rows_and_columns_are_those_of_the_input() {
+    let input = dominoes(3, 2);
+    let matrix = LinkedMatrix::build(&input);
+    let columns: Vec<usize> = matrix.columns().map(|col| col.index()).collect();
+    assert_eq!(columns, (0..6).collect::<Vec<_>>());
+    for col in matrix.columns() {
+        let rows: Vec<usize> = matrix.rows_in(col).map(|row| row.index()).collect();
+        assert_eq!(rows, (0..input.len()).filter(|&row| input[row][col.index()]).collect::<Vec<_>>());
+        assert_eq!(matrix.size(col), rows.len());
+        for row in matrix.rows_in(col) {
+            // the column a row is reached through comes first
+            let cells: Vec<usize> = row.cells().map(|cell| cell.index()).collect();
+            assert_eq!(cells[0], col.index());
+            assert!(cells.iter().all(|&cell| input[row.index()][cell]));
+        }
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn uncovering_out_of_order_is_caught() {
+    use std::panic;
+
+    let mut matrix = LinkedMatrix::build(&random(1, 5, 3, 50));
+    let columns: Vec<ColumnRef> = matrix.columns().collect();
+    matrix.cover(columns[0]);
+    matrix.cover(columns[1]);
+    assert!(panic::catch_unwind(panic::AssertUnwindSafe(|| matrix.uncover(columns[0]))).is_err());
+
+    let mut matrix = LinkedMatrix::build(&random(1, 5, 3, 50));
+    matrix.cover(columns[2]);
+    assert!(panic::catch_unwind(move || matrix.cover(columns[2])).is_err());
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use std::sync::Mutex;
+use dlx::{Backend, Node, SolveOptions};
+use common::permutations;
+
+/// The lines logged so far, with their levels.
+static LINES: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+/// A logger keeping every line in `LINES`.
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool { true }
+    fn log(&self, record: &log::Record) { LINES.lock().unwrap().push((record.level(), record.args().to_string())); }
+    fn flush(&self) {}
+}
+
+#[test]
+fn searches_log_their_progress() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    let input = permutations(8);
+
+    for backend in [Backend::DancingLinks, Backend::Bitset] {
+        LINES.lock().unwrap().clear();
+        let (solutions, stats) = Node::solve_all_with_options(&input, &SolveOptions { backend, ..SolveOptions::default() });
+        assert_eq!(solutions.len(), 40_320);
+
+        let lines = LINES.lock().unwrap().clone();
+        let count = |level| lines.iter().filter(|(logged, _)| *logged == level).count();
+        // a line every 10 000 solutions, one for every new depth, and columns now and then
+        assert_eq!(count(log::Level::Info), 4, "{backend:?}");
+        assert!(lines.iter().any(|(_, line)| line.starts_with("search found 40000 solutions after ")), "{backend:?}");
+        assert_eq!(count(log::Level::Debug), stats.max_depth, "{backend:?}");
+        assert!(count(log::Level::Trace) > 0, "{backend:?}");
+        assert!(count(log::Level::Trace) as u64 <= stats.nodes / 4096, "{backend:?}");
+    }
+}
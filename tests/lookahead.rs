@@ -0,0 +1,58 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions};
+use common::{dominoes, random, row_of, sorted};
+
+fn lookahead(candidates: usize) -> SolveOptions {
+    SolveOptions { column_strategy: ColumnStrategy::Lookahead { candidates }, ..SolveOptions::default() }
+}
+
+/// A 4x4 board of dominoes, and two columns of two rows each that all need the same column: every
+/// row of one empties the other, which lookahead sees before searching the board.
+fn poisoned_board() -> Vec<Vec<bool>> {
+    let cells = 16;
+    let width = cells + 3;
+    let mut input: Vec<Vec<bool>> = dominoes(4, 4).into_iter().map(|mut row| {
+        row.resize(width, false);
+        row
+    }).collect();
+    for column in [cells, cells + 1] {
+        input.push(row_of(width, &[column, cells + 2]));
+        input.push(row_of(width, &[column, cells + 2]));
+    }
+    input
+}
+
+#[test]
+fn lookahead_finds_the_same_solutions() {
+    for seed in 0..200 {
+        let input = random(seed, 14, 8, 30);
+        let (expected, _) = Node::solve_all_with_options(&input, &SolveOptions::default());
+        let (solutions, _) = Node::solve_all_with_options(&input, &lookahead(4));
+        assert_eq!(sorted(solutions), sorted(expected), "seed {seed}");
+    }
+
+    let input = dominoes(4, 5);
+    let (solutions, _) = Node::solve_all_with_options(&input, &lookahead(3));
+    assert_eq!(sorted(solutions), sorted(Node::solve_all(&input)));
+    assert!(Node::solve_once_with_options(&input, &lookahead(3)).0.is_some());
+}
+
+#[test]
+fn lookahead_prunes_a_poisoned_board() {
+    let input = poisoned_board();
+    let (solutions, plain) = Node::solve_all_with_options(&input, &SolveOptions::default());
+    let (pruned, stats) = Node::solve_all_with_options(&input, &lookahead(8));
+    assert!(solutions.is_empty() && pruned.is_empty());
+    assert!(stats.nodes * 10 < plain.nodes, "{} nodes with lookahead, {} without", stats.nodes, plain.nodes);
+}
+
+#[test]
+fn lookahead_cost_is_bounded_by_candidates() {
+    let input = dominoes(4, 4);
+    for candidates in 1..=4 {
+        let (_, stats) = Node::solve_all_with_options(&input, &lookahead(candidates));
+        // a column of the board has at most 4 rows
+        assert!(stats.lookahead_rows <= stats.nodes * 4 * candidates as u64, "{candidates} candidates");
+    }
+}
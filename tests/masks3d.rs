@@ -0,0 +1,44 @@
+use dlx::generation::blocks::ValidationError;
+use dlx::generation::blocks3d::{Block3D, Game3D};
+
+/// `n` rods of three cubes.
+fn rods(n: usize) -> Vec<Block3D> {
+    (0..n).map(|_| Block3D::from_layers(&["###"]).unwrap()).collect()
+}
+
+const FULL: &str = "###\n###\n###";
+const HOLLOW: &str = "###\n#.#\n###";
+
+#[test]
+fn rods_pack_the_full_cube() {
+    let game = Game3D::from_layer_strings(vec![FULL; 3], rods(9));
+    assert_eq!((game.w, game.h, game.d, game.area()), (3, 3, 3, 27));
+    assert!(game.validate().is_ok());
+    assert_eq!(game.get_matrix()[0].len(), 27 + 9);
+    let rows = game.solve_once().unwrap();
+    assert!(game.solution_grid_3d(&rows).iter().flatten().flatten().all(Option::is_some));
+}
+
+#[test]
+fn rods_dont_pack_the_hollow_cube() {
+    let game = Game3D::from_layer_strings(vec![FULL, HOLLOW, FULL], rods(9));
+    assert_eq!(game.area(), 26);
+    assert_eq!(game.get_matrix()[0].len(), 26 + 9);
+    // no rod runs through the middle of the cube
+    assert!(game.get_placements().iter().all(|placement| !placement.cells().contains(&(1, 1, 1))));
+    assert!(game.validate().errors.contains(&ValidationError::AreaMismatch { board: 26, blocks: 27 }));
+    assert_eq!(game.solve_once(), None);
+}
+
+#[test]
+fn irregular_shapes_leave_their_voids_blank() {
+    let blocks = vec![Block3D::from_layers(&["##\n#."]).unwrap(), Block3D::from_layers(&["##"]).unwrap()];
+    let game = Game3D::from_layer_strings(vec!["##\n##", "#."], blocks);
+    assert_eq!((game.w, game.h, game.d, game.area()), (2, 2, 2, 5));
+    let rows = game.solve_once().unwrap();
+    let grid = game.solution_grid_3d(&rows);
+    assert!(grid[1][0][0].is_some());
+    assert_eq!(grid[1][0][1..], [None]);
+    assert_eq!(grid[1][1], [None, None]);
+    assert!(grid[0].iter().flatten().all(Option::is_some));
+}
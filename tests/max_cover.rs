@@ -0,0 +1,42 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, random};
+
+/// Count the columns covered by rows sharing none, or `None` if two of them share one.
+fn covered(input: &[Vec<bool>], rows: &[usize]) -> Option<usize> {
+    let counts: Vec<usize> = (0..input[0].len()).map(|x| rows.iter().filter(|&&row| input[row][x]).count()).collect();
+    counts.iter().all(|&count| count <= 1).then(|| counts.iter().sum())
+}
+
+/// Find the most columns rows sharing none can cover, trying every set of rows.
+fn brute_force(input: &[Vec<bool>]) -> usize {
+    (0u32..1 << input.len()).filter_map(|set| {
+        let rows: Vec<usize> = (0..input.len()).filter(|row| set >> row & 1 == 1).collect();
+        covered(input, &rows)
+    }).max().unwrap()
+}
+
+#[test]
+fn known_optimums() {
+    // the rows overlap in the middle column, so one of them covers two columns
+    assert_eq!(Node::solve_max_cover(&matrix(&["110", "011"])).1, 2);
+    // the outer rows leave out nothing
+    assert_eq!(Node::solve_max_cover(&matrix(&["1100", "0110", "0011"])), (vec![0, 2], 4));
+    // dominoes leave one cell of an odd board
+    let board = dominoes(3, 3);
+    let (rows, count) = Node::solve_max_cover(&board);
+    assert_eq!(count, 8);
+    assert_eq!(covered(&board, &rows), Some(8));
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    for seed in 0..300 {
+        let input = random(seed, 10, 8, 25);
+        let (rows, count) = Node::solve_max_cover(&input);
+        assert_eq!(covered(&input, &rows), Some(count), "seed {seed}");
+        assert_eq!(count, brute_force(&input), "seed {seed}");
+        if !Node::solve_all(&input).is_empty() { assert_eq!(count, 8, "seed {seed}"); }
+    }
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use dlx::{Node, SolveOptions};
+use common::{dominoes, random, sorted};
+
+fn memo(capacity: usize) -> SolveOptions {
+    SolveOptions { memo_capacity: Some(capacity), ..SolveOptions::default() }
+}
+
+#[test]
+fn memo_counts_tilings_with_fewer_nodes() {
+    for height in 1..=8 {
+        let input = dominoes(4, height);
+        let (count, plain) = Node::count_solutions_with_options(&input, &SolveOptions::default());
+        let (memoized, stats) = Node::count_solutions_with_options(&input, &memo(100_000));
+        assert_eq!(memoized, count, "4x{height}");
+        assert_eq!(Node::count_solutions_with_options(&input, &memo(3)).0, count, "4x{height}");
+        if height >= 6 {
+            assert!(stats.nodes * 4 < plain.nodes, "4x{height}: {} nodes memoized, {} without", stats.nodes, plain.nodes);
+            assert!(stats.memo_hits > 0);
+        }
+    }
+}
+
+#[test]
+fn memo_keeps_the_solutions() {
+    for seed in 0..100 {
+        let input = random(seed, 16, 9, 25);
+        let (expected, _) = Node::solve_all_with_options(&input, &SolveOptions::default());
+        for options in [memo(100_000), memo(3)] {
+            let (solutions, _) = Node::solve_all_with_options(&input, &options);
+            assert_eq!(sorted(solutions), sorted(expected.clone()), "seed {seed}");
+        }
+        assert_eq!(Node::count_solutions_with_options(&input, &memo(100_000)).0, expected.len() as u64);
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use dlx::{Backend, Node, SolveOptions, Solver};
+use common::{dominoes, random};
+
+/// Append copies of some columns to every row.
+fn with_copies(input: &[Vec<bool>], copies: &[usize]) -> Vec<Vec<bool>> {
+    input.iter().map(|row| {
+        let mut row = row.clone();
+        for &col in copies { row.push(row[col]); }
+        row
+    }).collect()
+}
+
+#[test]
+fn copies_are_merged() {
+    let input = with_copies(&dominoes(4, 4), &[0, 3, 0, 15]);
+    let merged = SolveOptions { merge_duplicate_columns: true, backend: Backend::DancingLinks, ..SolveOptions::default() };
+    let plain = SolveOptions { backend: Backend::DancingLinks, ..SolveOptions::default() };
+    let (solutions, stats) = Node::solve_all_with_options(&input, &merged);
+    let (expected, plain_stats) = Node::solve_all_with_options(&input, &plain);
+    assert_eq!(solutions, expected);
+    assert_eq!(stats.merged_columns, vec![(16, 0), (17, 3), (18, 0), (19, 15)]);
+    assert!(plain_stats.merged_columns.is_empty());
+    assert!(stats.covers < plain_stats.covers, "{} covers against {}", stats.covers, plain_stats.covers);
+
+    assert_eq!(Node::count_solutions_with_options(&input, &merged).1.merged_columns.len(), 4);
+    let mut solver = Solver::with_options(&input, merged);
+    assert_eq!(solver.solve_all(), expected);
+    assert_eq!(solver.stats().merged_columns.len(), 4);
+    let automatic = SolveOptions { merge_duplicate_columns: true, ..SolveOptions::default() };
+    assert_eq!(Node::solve_all_with_options(&input, &automatic).0, expected);
+}
+
+#[test]
+fn merging_keeps_the_solutions() {
+    for seed in 0..300 {
+        let copies: Vec<usize> = (0..seed as usize % 4).map(|i| (seed as usize + i * 3) % 5).collect();
+        let input = with_copies(&random(seed, 12, 5, 35), &copies);
+        let width = input[0].len();
+        let primary_columns = seed.is_multiple_of(2).then_some(seed as usize / 2 % (width + 1));
+        let forbid_rows = vec![seed as usize % 12];
+        let plain = SolveOptions { primary_columns, forbid_rows, backend: Backend::DancingLinks, ..SolveOptions::default() };
+        let expected = Node::solve_all_with_options(&input, &plain).0;
+
+        let merged = SolveOptions { merge_duplicate_columns: true, ..plain };
+        let (solutions, stats) = Node::solve_all_with_options(&input, &merged);
+        assert_eq!(solutions, expected, "seed {seed}");
+        for &(col, kept) in &stats.merged_columns {
+            assert!(kept < col && input.iter().all(|row| row[col] == row[kept]), "seed {seed}");
+        }
+        assert_eq!(Node::count_solutions_with_options(&input, &merged).0, expected.len() as u64, "seed {seed}");
+        assert_eq!(Solver::with_options(&input, merged).solve_all(), expected, "seed {seed}");
+    }
+}
@@ -0,0 +1,22 @@
+//! Builds `examples/no_std`, a library without `std` depending on this crate without its default
+//! features, as crates depending on it do.
+
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn consumers_build_without_std() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/no_std/Cargo.toml");
+    let target = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std");
+    for features in ["", "dlx/log"] {
+        // offline, as the packages it needs are the ones these tests were built with
+        let status = Command::new(env!("CARGO"))
+            .args(["build", "--offline", "--quiet", "--features", features, "--manifest-path"])
+            .arg(&manifest)
+            .arg("--target-dir")
+            .arg(&target)
+            .status()
+            .unwrap();
+        assert!(status.success(), "examples/no_std doesn't build with features `{features}`");
+    }
+}
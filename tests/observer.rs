@@ -0,0 +1,50 @@
+mod common;
+
+use dlx::{Node, SearchObserver};
+use common::{random, sorted};
+
+/// Every event of a search, written out.
+#[derive(Default)]
+struct Trace(Vec<String>);
+
+impl SearchObserver for Trace {
+    fn on_choose_column(&mut self, col: usize, size: usize) { self.0.push(format!("choose {col} {size}")); }
+    fn on_try_row(&mut self, row: usize, depth: usize) { self.0.push(format!("try {row} {depth}")); }
+    fn on_cover(&mut self, col: usize) { self.0.push(format!("cover {col}")); }
+    fn on_uncover(&mut self, col: usize) { self.0.push(format!("uncover {col}")); }
+    fn on_solution(&mut self, rows: &[usize]) { self.0.push(format!("solution {rows:?}")); }
+}
+
+/// Counts solutions, leaving the other events to the defaults.
+struct Solutions(usize);
+
+impl SearchObserver for Solutions {
+    fn on_solution(&mut self, _: &[usize]) { self.0 += 1; }
+}
+
+#[test]
+fn events_follow_the_search() {
+    let mut trace = Trace::default();
+    assert_eq!(Node::solve_all_observed(&[vec![true, false], vec![false, true]], &mut trace), vec![vec![0, 1]]);
+    assert_eq!(trace.0, ["choose 0 1", "cover 0", "try 0 0", "choose 1 1", "cover 1", "try 1 1", "solution [0, 1]", "uncover 1", "uncover 0"]);
+
+    let mut trace = Trace::default();
+    let solutions = Node::solve_all_observed(&[vec![true, true], vec![true, false], vec![false, true]], &mut trace);
+    assert_eq!(solutions, vec![vec![0], vec![1, 2]]);
+    assert_eq!(trace.0, [
+        "choose 0 2", "cover 0", "try 0 0", "cover 1", "solution [0]", "uncover 1",
+        "try 1 0", "choose 1 1", "cover 1", "try 2 1", "solution [1, 2]", "uncover 1", "uncover 0",
+    ]);
+}
+
+#[test]
+fn observed_solutions_match_solve_all() {
+    for seed in 0..30 {
+        let input = random(seed, 14, 7, 30);
+        let mut counted = Solutions(0);
+        let solutions = Node::solve_all_observed(&input, &mut counted);
+        assert_eq!(sorted(solutions.clone()), sorted(Node::solve_all(&input)), "seed {seed}");
+        assert_eq!(counted.0, solutions.len(), "seed {seed}");
+        assert_eq!(Node::solve_all_observed(&input, &mut ()), solutions, "seed {seed}");
+    }
+}
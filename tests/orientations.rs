@@ -0,0 +1,51 @@
+use dlx::generation::blocks3d::{Block3D, Game3D, OrientationSet, Rotation3};
+use std::collections::HashSet;
+
+#[test]
+fn rotation_sets() {
+    let mirrors = Rotation3::all_with_mirrors();
+    assert_eq!(mirrors.len(), 48);
+    assert_eq!(mirrors.iter().collect::<HashSet<_>>().len(), 48);
+    assert!(mirrors[..24].iter().all(|rotation| !rotation.is_mirror()) && mirrors[24..].iter().all(Rotation3::is_mirror));
+    assert_eq!(Rotation3::all(), mirrors[..24]);
+    assert_eq!(Rotation3::flat().len(), 4);
+    assert_eq!(Rotation3::all()[0], Rotation3::IDENTITY);
+}
+
+#[test]
+fn flat_tricubes_stay_in_their_layer() {
+    let l = Block3D::from_layers(&["#.\n##"]).unwrap();
+    assert_eq!(l.get_transformations().len(), 12);
+
+    let l = l.orientations(OrientationSet::FlatXYOnly);
+    assert_eq!(l.get_transformations().len(), 4);
+    let game = Game3D::new(3, 3, 2, vec![l]);
+    let placements = game.get_placements();
+    // four turns at each of the four offsets in the layer, on both layers
+    assert_eq!(placements.len(), 4 * 4 * 2);
+    for placement in &placements {
+        assert!(placement.cells().iter().all(|&(_, _, z)| z == placement.z), "{placement:?}");
+    }
+}
+
+#[test]
+fn chiral_pieces_keep_their_hand() {
+    let piece = Block3D::from_layers(&["##\n.#", "..\n.#"]).unwrap();
+    let mirror = piece.transformed(Rotation3::all_with_mirrors()[24]);
+    let turns = piece.get_transformations();
+    assert_eq!(turns.len(), 12);
+    assert!(turns.iter().all(|(_, turned)| turned.data != mirror.data));
+
+    let either = piece.clone().orientations(OrientationSet::All48WithMirror).get_transformations();
+    assert_eq!(either.len(), 24);
+    assert!(either.iter().any(|(_, turned)| turned.data == mirror.data));
+}
+
+#[test]
+fn custom_orientations_are_the_only_ones() {
+    let piece = Block3D::from_layers(&["##\n.#"]).unwrap().orientations(OrientationSet::Custom(vec![Rotation3::IDENTITY]));
+    let turns = piece.get_transformations();
+    assert_eq!(turns.len(), 1);
+    assert_eq!(turns[0].1.data, piece.data);
+    assert_eq!(Game3D::new(2, 2, 1, vec![piece]).get_placements().len(), 1);
+}
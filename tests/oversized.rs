@@ -0,0 +1,30 @@
+use dlx::generation::blocks::{CountMode, Game2D};
+
+#[test]
+fn pieces_larger_than_the_board_have_no_placements() {
+    for piece in ["###", "#\n#\n#"] {
+        let mut game = Game2D::from_strings(2, 2, vec![piece]).unwrap();
+        assert!(game.get_matrix().is_empty());
+        assert!(game.get_placements().is_empty());
+        assert_eq!(game.placement_counts(), vec![0]);
+        assert_eq!(game.solve_once(), None);
+        assert_eq!(game.count_solutions(CountMode::Raw), 0);
+    }
+}
+
+#[test]
+fn oversized_pieces_among_others() {
+    let mut game = Game2D::from_strings(2, 2, vec!["###", "##", "##"]).unwrap();
+    assert_eq!(game.placement_counts(), vec![0, 4, 4]);
+    let matrix = game.get_matrix();
+    assert_eq!(matrix.len(), 8);
+    assert!(matrix.iter().all(|row| !row[0]));
+    assert_eq!(game.solve_once(), None);
+
+    let mut game = Game2D::from_strings(3, 1, vec!["#\n#\n#\n#", "#", "#"]).unwrap();
+    assert_eq!(game.placement_counts(), vec![0, 3, 3]);
+    assert_eq!(game.solve_once(), None);
+
+    let mut game = Game2D::from_strings(3, 1, vec!["##", "#"]).unwrap();
+    assert!(game.solve_once().is_some());
+}
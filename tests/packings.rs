@@ -0,0 +1,43 @@
+use dlx::generation::blocks::Game2D;
+
+#[test]
+fn dominoes_on_a_square_pack_as_tilings() {
+    let mut game = Game2D::from_strings(2, 2, vec!["##", "##"]).unwrap();
+    let packings = game.maximal_packings(100);
+    assert_eq!(packings.len(), 2);
+    for packing in &packings {
+        assert!(packing.uncovered.is_empty());
+        assert_eq!(packing.covered.len(), 4);
+        assert_eq!(packing.placements.len(), 2);
+    }
+    assert_ne!(packings[0].placements[0].shape, packings[1].placements[0].shape);
+}
+
+#[test]
+fn a_square_packs_alone() {
+    let mut game = Game2D::from_strings(3, 3, vec!["##\n##"]).unwrap();
+    let packings = game.maximal_packings(100);
+    assert_eq!(packings.len(), 4);
+    let mut corners: Vec<(usize, usize)> = packings.iter().map(|packing| {
+        assert_eq!(packing.placements.len(), 1);
+        assert_eq!((packing.covered.len(), packing.uncovered.len()), (4, 5));
+        (packing.placements[0].x, packing.placements[0].y)
+    }).collect();
+    corners.sort_unstable();
+    assert_eq!(corners, vec![(0, 0), (0, 1), (1, 0), (1, 1)]);
+}
+
+#[test]
+fn packings_leave_no_room() {
+    let mut game = Game2D::from_strings(3, 2, vec!["##", "##", "##"]).unwrap();
+    let packings = game.maximal_packings(100);
+    for packing in &packings {
+        // two empty cells side by side would take the unused domino
+        let empty = |x: usize, y: usize| packing.uncovered.contains(&(x, y));
+        let room = packing.uncovered.iter().any(|&(x, y)| empty(x + 1, y) || empty(x, y + 1));
+        assert!(packing.placements.len() == 3 || !room, "{packing:?}");
+    }
+    assert!(packings.iter().any(|packing| !packing.uncovered.is_empty()));
+    assert_eq!(game.maximal_packings(1).len(), 1);
+    assert!(game.maximal_packings(0).is_empty());
+}
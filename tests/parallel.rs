@@ -0,0 +1,22 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, random};
+
+#[test]
+fn same_solutions_in_the_same_order() {
+    for seed in 0..200 {
+        let input = random(seed, 14, 9, 30);
+        assert_eq!(Node::par_solve_all(&input), Node::solve_all(&input), "seed {seed}");
+    }
+    let input = dominoes(6, 6);
+    assert_eq!(Node::par_solve_all(&input), Node::solve_all(&input));
+}
+
+#[test]
+fn single_row_columns_leave_one_branch() {
+    // nothing to share out: the first column has one row
+    let input = common::matrix(&["110", "001"]);
+    assert_eq!(Node::par_solve_all(&input), [[0, 1]]);
+    assert!(Node::par_solve_all(&common::matrix(&["10"])).is_empty());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::blocks::Game2D;
+use common::{dominoes, matrix, random};
+
+#[test]
+fn unique_solution_rows_count_once() {
+    let input = matrix(&["1100", "0011", "1010", "0100"]);
+    let solution = Node::solve_all(&input);
+    assert_eq!(solution.len(), 1);
+    let participation = Node::row_participation(&input);
+    for (row, &count) in participation.iter().enumerate() {
+        assert_eq!(count, u64::from(solution[0].contains(&row)));
+    }
+}
+
+#[test]
+fn domino_counts_sum_to_solution_rows() {
+    let input = dominoes(3, 2);
+    let solutions = Node::solve_all(&input);
+    assert_eq!(solutions.len(), 3);
+    assert_eq!(Node::row_participation(&input).iter().sum::<u64>(), 3 * 3);
+}
+
+#[test]
+fn counts_match_enumeration() {
+    for seed in 0..60 {
+        let input = random(seed, 16, 8, 25);
+        let solutions = Node::solve_all(&input);
+        for (row, count) in Node::row_participation(&input).into_iter().enumerate() {
+            assert_eq!(count as usize, solutions.iter().filter(|solution| solution.contains(&row)).count());
+        }
+    }
+}
+
+#[test]
+fn placement_heat() {
+    let mut game = Game2D::from_strings(3, 2, vec!["##", "##", "##"]).unwrap();
+    let heat = game.placement_heat();
+    // 3 tilings by shape, each with the 3! ways to pick which block goes where
+    assert_eq!(heat.iter().map(|(_, heat)| heat).sum::<u64>(), 18 * 3);
+    assert!(!game.render_placement_heat().is_empty());
+}
+
+#[test]
+fn placement_heat_without_tilings() {
+    let mut game = Game2D::from_strings(2, 2, vec!["###"]).unwrap();
+    assert!(game.placement_heat().is_empty());
+
+    let mut game = Game2D::from_strings(2, 2, vec!["##", "###"]).unwrap();
+    let heat = game.placement_heat();
+    assert!(!heat.is_empty());
+    assert!(heat.iter().all(|&(_, heat)| heat == 0));
+}
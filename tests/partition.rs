@@ -0,0 +1,54 @@
+mod common;
+
+use dlx::{Node, PartitionError};
+use common::sorted;
+
+/// The partitions of `1..=n`, found by trying every choice of subsets.
+fn partitions(n: usize, subsets: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut found = Vec::new();
+    for mask in 0u32..1 << subsets.len() {
+        let chosen: Vec<usize> = (0..subsets.len()).filter(|i| mask >> i & 1 == 1).collect();
+        let mut times = vec![0; n + 1];
+        for &subset in &chosen {
+            for &element in &subsets[subset] { times[element] += 1; }
+        }
+        if times[1..].iter().all(|&count| count == 1) { found.push(chosen); }
+    }
+    found
+}
+
+#[test]
+fn partitions_of_seven() {
+    let universe: Vec<usize> = (1..=7).collect();
+    let knuth = vec![vec![3, 5, 6], vec![1, 4, 7], vec![2, 3, 6], vec![1, 4], vec![2, 7], vec![4, 5, 7]];
+    assert_eq!(Node::solve_partition(&universe, &knuth), Ok(vec![vec![3, 0, 4]]));
+
+    // the lines of the Fano plane, with every single element and two pairs
+    let mut family = vec![vec![1, 2, 3], vec![1, 4, 5], vec![1, 6, 7], vec![2, 4, 6], vec![2, 5, 7], vec![3, 4, 7], vec![3, 5, 6]];
+    family.extend((1..=7).map(|element| vec![element]));
+    family.extend([vec![2, 6], vec![5, 7]]);
+    assert_eq!(sorted(Node::solve_partition(&universe, &family).unwrap()), sorted(partitions(7, &family)));
+}
+
+#[test]
+fn elements_of_any_type() {
+    let names = ["a", "b", "c"];
+    let subsets = vec![vec!["a", "b"], vec!["c"], vec!["b", "c"], vec!["a"]];
+    assert_eq!(sorted(Node::solve_partition(&names, &subsets).unwrap()), vec![vec![0, 1], vec![2, 3]]);
+}
+
+#[test]
+fn bad_elements_are_errors() {
+    let names = ["a", "b", "c"];
+    let unknown = PartitionError::UnknownElement { subset: 1, position: 0 };
+    assert_eq!(Node::solve_partition(&names, &[vec!["a"], vec!["d"]]), Err(unknown));
+    assert_eq!(Node::solve_partition(&names, &[vec!["a", "b", "a"]]), Err(PartitionError::DuplicateElement { subset: 0, position: 2 }));
+    assert_eq!(Node::solve_partition(&["a", "a"], &[vec!["a"]]), Err(PartitionError::DuplicateInUniverse { index: 1 }));
+}
+
+#[test]
+fn empty_universes_and_subsets() {
+    assert_eq!(Node::solve_partition::<u8>(&[], &[]), Ok(vec![Vec::new()]));
+    assert_eq!(Node::solve_partition(&[1], &[vec![]]), Ok(Vec::new()));
+    assert_eq!(Node::solve_partition(&[1], &[]), Ok(Vec::new()));
+}
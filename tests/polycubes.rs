@@ -0,0 +1,53 @@
+use dlx::generation::blocks3d::{Block3D, Game3D, OrientationSet, Rotation3};
+use std::collections::HashSet;
+
+/// Check if the cells of a block are joined face to face.
+fn connected(block: &Block3D) -> bool {
+    let cells = block.cells();
+    let mut seen = vec![cells[0]];
+    let mut i = 0;
+    while i < seen.len() {
+        let [x, y, z] = seen[i];
+        for next in [[x + 1, y, z], [x - 1, y, z], [x, y + 1, z], [x, y - 1, z], [x, y, z + 1], [x, y, z - 1]] {
+            if cells.contains(&next) && !seen.contains(&next) { seen.push(next); }
+        }
+        i += 1;
+    }
+    seen.len() == cells.len()
+}
+
+#[test]
+fn counts_free_polycubes() {
+    let counts: Vec<usize> = (0..=6).map(|n| Block3D::free_polycubes(n).len()).collect();
+    assert_eq!(counts, [0, 1, 1, 2, 8, 29, 166]);
+
+    for n in 1..=5 {
+        let polycubes = Block3D::free_polycubes(n);
+        assert!(polycubes.iter().all(|block| block.cells().len() == n && connected(block)), "order {n}");
+        let canonical: HashSet<_> = polycubes.iter().map(Block3D::canonical_cells).collect();
+        assert_eq!(canonical.len(), polycubes.len(), "order {n} has rotations of each other");
+    }
+}
+
+#[test]
+fn mirrors_merge_and_fixed_polycubes_split() {
+    let merged: Vec<usize> = (1..=6).map(|n| Block3D::polycubes(n, OrientationSet::All48WithMirror).len()).collect();
+    assert_eq!(merged, [1, 1, 2, 7, 23, 112]);
+    let fixed: Vec<usize> = (1..=4).map(|n| Block3D::polycubes(n, OrientationSet::Custom(vec![Rotation3::IDENTITY])).len()).collect();
+    assert_eq!(fixed, [1, 3, 15, 86]);
+}
+
+#[test]
+fn soma_cube() {
+    // the seven pieces of the Soma cube are the bent tricube and the tetracubes that are neither straight nor square
+    let size = |block: &Block3D| {
+        let mut size = [block.w, block.h, block.d];
+        size.sort();
+        size
+    };
+    let mut pieces: Vec<Block3D> = Block3D::free_polycubes(3).into_iter().filter(|block| size(block) == [1, 2, 2]).collect();
+    pieces.extend(Block3D::free_polycubes(4).into_iter().filter(|block| ![[1, 1, 4], [1, 2, 2]].contains(&size(block))));
+    assert_eq!(pieces.len(), 7);
+    let game = Game3D::new(3, 3, 3, pieces);
+    assert!(game.solve_once().is_some());
+}
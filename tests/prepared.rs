@@ -0,0 +1,84 @@
+mod common;
+
+use std::panic;
+use dlx::{Dlx, DlxError, Node, SolveOptions};
+use common::{matrix, random};
+
+/// Get the 64 bit FNV-1a hash saved structures end with.
+fn fnv(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Change a byte of a saved structure, keeping its checksum right, so loading has to find it.
+fn forged(bytes: &[u8], at: usize) -> Vec<u8> {
+    let mut forged = bytes[..bytes.len() - 8].to_vec();
+    forged[at] ^= 1;
+    let checksum = fnv(&forged);
+    forged.extend_from_slice(&checksum.to_le_bytes());
+    forged
+}
+
+#[test]
+fn pentominoes_round_trip() {
+    let input = Node::parse_instance(include_str!("data/pentominoes-20x3.txt")).unwrap();
+    let dlx = Dlx::new(&input);
+    let loaded = Dlx::from_bytes(&dlx.to_bytes()).unwrap();
+    assert_eq!(loaded, dlx);
+    let solutions = loaded.solve_all();
+    assert_eq!(solutions, dlx.solve_all());
+    assert_eq!(solutions, Node::solve_all(&input));
+    assert_eq!(loaded.solve_once(), Node::solve_once(&input));
+}
+
+#[test]
+fn random_matrices_round_trip() {
+    for seed in 0..200 {
+        let input = random(seed, 12, 8, 30);
+        let loaded = Dlx::from_bytes(&Dlx::new(&input).to_bytes()).unwrap();
+        assert_eq!(loaded.solve_all(), Node::solve_all(&input), "seed {seed}");
+    }
+}
+
+#[test]
+fn corrupted_bytes_are_errors() {
+    let bytes = Dlx::new(&random(3, 10, 6, 40)).to_bytes();
+    for at in 0..bytes.len() {
+        let mut corrupted = bytes.clone();
+        corrupted[at] ^= 0x10;
+        assert!(Dlx::from_bytes(&corrupted).is_err(), "byte {at}");
+    }
+    assert_eq!(Dlx::from_bytes(&bytes[..bytes.len() - 3]), Err(DlxError::Truncated));
+    assert_eq!(Dlx::from_bytes(&[bytes.as_slice(), &[0]].concat()), Err(DlxError::Truncated));
+    assert_eq!(Dlx::from_bytes(b"not a structure"), Err(DlxError::NotDlx));
+    assert_eq!(Dlx::from_bytes(&forged(&bytes, 4)), Err(DlxError::UnsupportedVersion { version: 0 }));
+}
+
+#[test]
+fn broken_links_are_found_past_the_checksum() {
+    let bytes = Dlx::new(&random(3, 10, 6, 40)).to_bytes();
+    // the header is 32 bytes, and every node 28, its links coming first
+    for (node, link) in [(0, 3), (2, 0), (9, 1), (12, 4)] {
+        let result = Dlx::from_bytes(&forged(&bytes, 32 + node * 28 + link * 4));
+        assert!(matches!(result, Err(DlxError::BrokenLink { .. })), "link {link} of node {node}: {result:?}");
+    }
+}
+
+#[test]
+fn options_require_and_forbid_rows() {
+    let input = matrix(&["10", "01", "11"]);
+    let options = SolveOptions { forbid_rows: vec![2], ..SolveOptions::default() };
+    assert_eq!(Dlx::new(&input).solve_all_with_options(&options).0, vec![vec![0, 1]]);
+
+    for seed in 0..200 {
+        let input = random(seed, 12, 6, 35);
+        let dlx = Dlx::from_bytes(&Dlx::new(&input).to_bytes()).unwrap();
+        let row = seed as usize % 12;
+        let require_rows = if input[row].contains(&true) { vec![row] } else { Vec::new() };
+        let forbid_rows = vec![(row + 5) % 12, (row + 7) % 12];
+        let options = SolveOptions { require_rows, forbid_rows, ..SolveOptions::default() };
+        assert_eq!(dlx.solve_all_with_options(&options).0, Node::solve_all_with_options(&input, &options).0, "seed {seed}");
+    }
+
+    let both = SolveOptions { require_rows: vec![2], forbid_rows: vec![2], ..SolveOptions::default() };
+    assert!(panic::catch_unwind(|| Dlx::new(&input).solve_all_with_options(&both)).is_err());
+}
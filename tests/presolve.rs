@@ -0,0 +1,48 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::sudoku;
+use common::{matrix, random, sorted};
+
+/// The sudoku matrix restricted to the clue rows and the rows compatible with them, taking as clues
+/// the cells of a solved grid picked by `is_clue`.
+fn puzzle(is_clue: impl Fn(usize) -> bool) -> Vec<Vec<bool>> {
+    let full = sudoku::get_matrix();
+    let grid = Node::solve_once(&full).unwrap();
+    let clues: Vec<usize> = grid.iter().enumerate().filter(|&(cell, _)| is_clue(cell)).map(|(_, &row)| row).collect();
+    let covered: Vec<usize> = (0..full[0].len()).filter(|&col| clues.iter().any(|&row| full[row][col])).collect();
+    full.iter().enumerate()
+        .filter(|&(i, row)| clues.contains(&i) || !covered.iter().any(|&col| row[col]))
+        .map(|(_, row)| row.clone())
+        .collect()
+}
+
+#[test]
+fn naked_singles_are_forced() {
+    let input = puzzle(|cell| cell % 3 != 0);
+    let presolved = Node::presolve(&input);
+    assert!(!presolved.infeasible);
+    // the 54 clues are forced, and so is every cell left with a single digit
+    assert!(presolved.forced_rows.len() > 54, "{} forced", presolved.forced_rows.len());
+    assert_eq!(sorted(Node::solve_all_presolved(&input)), sorted(Node::solve_all(&input)));
+}
+
+#[test]
+fn presolved_matches_solve_all() {
+    for seed in 0..400 {
+        let input = random(seed, 10, 6, 30);
+        let presolved = Node::presolve(&input);
+        let expected = sorted(Node::solve_all(&input));
+        assert_eq!(sorted(Node::solve_all_presolved(&input)), expected, "seed {seed}");
+        assert!(!presolved.infeasible || expected.is_empty(), "seed {seed}");
+        for solution in &expected {
+            assert!(presolved.forced_rows.iter().all(|row| solution.contains(row)), "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn empty_column_is_infeasible() {
+    assert!(Node::presolve(&matrix(&["10", "10"])).infeasible);
+    assert_eq!(Node::solve_all_presolved(&matrix(&["10", "01"])), vec![vec![0, 1]]);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::panic;
+use dlx::{ExactCoverProblem, Node};
+use dlx::generation::blocks::Game2D;
+use common::{PENTOMINOES, permutations};
+
+/// The permutations of `n` elements, rows generated in the order of `common::permutations`.
+struct Permutations(usize);
+
+impl ExactCoverProblem for Permutations {
+    fn num_columns(&self) -> usize { 2 * self.0 }
+    fn rows(&self) -> Box<dyn Iterator<Item = Vec<usize>> + '_> {
+        let n = self.0;
+        Box::new((0..n * n).map(move |i| vec![n + i % n, i / n]))
+    }
+}
+
+/// A problem of rows given as they are.
+struct Rows(Vec<Vec<usize>>, usize);
+
+impl ExactCoverProblem for Rows {
+    fn num_columns(&self) -> usize { self.1 }
+    fn rows(&self) -> Box<dyn Iterator<Item = Vec<usize>> + '_> { Box::new(self.0.iter().cloned()) }
+}
+
+#[test]
+fn streamed_rows_match_the_matrix() {
+    assert_eq!(Node::solve_problem(&Permutations(5)), Node::solve_all(&permutations(5)));
+
+    let mut game = Game2D::from_strings(20, 3, PENTOMINOES.to_vec()).unwrap();
+    let input = game.get_matrix();
+    assert_eq!(game.num_columns(), input[0].len());
+    let solutions = Node::solve_problem(&game);
+    assert_eq!(solutions.len(), 8);
+    assert_eq!(solutions, Node::solve_all(&input));
+
+    let mut game = Game2D::from_strings(3, 3, vec!["##\n#.", ".#\n##", "###"]).unwrap();
+    assert_eq!(Node::solve_problem(&game), Node::solve_all(&game.get_matrix()));
+}
+
+#[test]
+fn small_problems() {
+    assert_eq!(Node::solve_problem(&Rows(vec![], 0)), vec![Vec::<usize>::new()]);
+    assert!(Node::solve_problem(&Rows(vec![vec![0]], 2)).is_empty());
+    assert_eq!(Node::solve_problem(&Rows(vec![vec![1, 0], vec![0], vec![1]], 2)), vec![vec![0], vec![1, 2]]);
+}
+
+#[test]
+fn bad_rows_panic() {
+    assert!(panic::catch_unwind(|| Node::solve_problem(&Rows(vec![vec![2]], 2))).is_err());
+    assert!(panic::catch_unwind(|| Node::solve_problem(&Rows(vec![vec![1, 1]], 2))).is_err());
+}
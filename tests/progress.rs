@@ -0,0 +1,37 @@
+mod common;
+
+use dlx::{Node, Progress, ProgressCallback, SolveOptions};
+use std::sync::{Arc, Mutex};
+use common::dominoes;
+
+/// Solve with progress reported every `every` nodes, giving the reports and the node count.
+fn reports(input: &[Vec<bool>], every: u64) -> (Vec<Progress>, u64) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let progress = ProgressCallback::new(every, move |progress| sink.lock().unwrap().push(progress.clone()));
+    let (_, stats) = Node::solve_all_with_options(input, &SolveOptions { progress: Some(progress), ..SolveOptions::default() });
+    let seen = seen.lock().unwrap().clone();
+    (seen, stats.nodes)
+}
+
+#[test]
+fn fraction_never_goes_down() {
+    let (seen, nodes) = reports(&dominoes(4, 5), 3);
+    assert_eq!(seen.len() as u64, nodes / 3);
+    for pair in seen.windows(2) {
+        assert!(pair[0].fraction <= pair[1].fraction, "{:?} then {:?}", pair[0], pair[1]);
+        assert!(pair[0].nodes < pair[1].nodes);
+    }
+}
+
+#[test]
+fn reports_describe_the_current_path() {
+    let (seen, _) = reports(&dominoes(4, 4), 1);
+    for progress in &seen {
+        assert_eq!(progress.depth, progress.branches.len());
+        assert!(progress.branches.iter().all(|&(index, count)| index < count), "{progress:?}");
+        assert!((0.0..1.0).contains(&progress.fraction), "{progress:?}");
+    }
+    // the root comes first, before any branch is taken
+    assert_eq!((seen[0].depth, seen[0].fraction, seen[0].nodes), (0, 0.0, 1));
+}
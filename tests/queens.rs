@@ -0,0 +1,36 @@
+use dlx::Node;
+use dlx::generation::presets::{QueensError, n_queens, n_queens_complete};
+
+/// Check if `n` queens are on different ranks, files and diagonals of the board.
+fn non_attacking(n: usize, queens: &[(usize, usize)]) -> bool {
+    queens.len() == n && queens.iter().enumerate().all(|(i, &(r1, c1))| {
+        r1 < n && c1 < n && queens[..i].iter().all(|&(r2, c2)| r1 != r2 && c1 != c2 && r1 + c2 != r2 + c1 && r1 + c1 != r2 + c2)
+    })
+}
+
+#[test]
+fn counts_fresh_boards() {
+    let counts: Vec<usize> = (1..=8).map(|n| Node::solve_all(&n_queens(n)).len()).collect();
+    assert_eq!(counts, [1, 0, 0, 2, 10, 4, 40, 92]);
+}
+
+#[test]
+fn completes_with_the_givens() {
+    let placed = [(0, 0), (1, 4)];
+    let queens = n_queens_complete(8, &placed).unwrap().unwrap();
+    assert!(non_attacking(8, &queens), "{queens:?}");
+    assert!(placed.iter().all(|queen| queens.contains(queen)));
+
+    assert_eq!(n_queens_complete(1, &[(0, 0)]), Ok(Some(vec![(0, 0)])));
+    // a queen in the corner of the 4x4 board leaves no room for the others
+    assert_eq!(n_queens_complete(4, &[(0, 0)]), Ok(None));
+}
+
+#[test]
+fn rejects_givens_before_searching() {
+    // the board is far too big to build, so this returns before any search
+    let error = n_queens_complete(10_000, &[(0, 0), (3, 3)]).unwrap_err();
+    assert_eq!(error, QueensError::Attack { queens: ((0, 0), (3, 3)) });
+    assert_eq!(error.to_string(), "queens at (0, 0) and (3, 3) attack each other");
+    assert_eq!(n_queens_complete(8, &[(9, 0)]), Err(QueensError::OffBoard { queen: (9, 0) }));
+}
@@ -0,0 +1,48 @@
+use dlx::generation::courses::{Compactness, Schedule};
+
+fn schedule() -> Schedule {
+    let availability = vec![vec![true, true, true, true], vec![true, true, true, true], vec![true, false, true, true]];
+    let costs = vec![vec![3, 1, 4, 1], vec![5, 9, 2, 6], vec![5, 0, 5, 8]];
+    Schedule::from_availability(&availability, Some(&costs))
+}
+
+#[test]
+fn top_one_is_the_best() {
+    let schedule = schedule();
+    let (cost, assignments) = schedule.solve_best().unwrap();
+    let ranked = schedule.enumerate_ranked(|assignments| schedule.cost(assignments) as i64, 1);
+    assert_eq!(ranked.len(), 1);
+    assert_eq!(ranked[0].score as u64, cost);
+    assert_eq!(ranked[0].assignments, assignments);
+}
+
+#[test]
+fn sorted_and_capped() {
+    let schedule = schedule();
+    let ranked = schedule.enumerate_ranked(|assignments| schedule.cost(assignments) as i64, 5);
+    assert_eq!(ranked.len(), 5);
+    assert!(ranked.windows(2).all(|pair| pair[0].score <= pair[1].score));
+
+    // all of them, with the same costs as the cheapest-first solve
+    let all = schedule.enumerate_ranked(|assignments| schedule.cost(assignments) as i64, 1000);
+    let best = schedule.solve_best_k(1000);
+    assert!(all.len() < 1000);
+    assert_eq!(all.iter().map(|ranked| ranked.score as u64).collect::<Vec<_>>(), best.iter().map(|(cost, _)| *cost).collect::<Vec<_>>());
+    assert!(schedule.enumerate_ranked(|_| 0, 0).is_empty());
+}
+
+#[test]
+fn compactness() {
+    let schedule = schedule();
+    let compactness = Compactness(&schedule);
+    let ranked = schedule.enumerate_ranked(|assignments| compactness.score(assignments), 3);
+    assert_eq!(ranked.len(), 3);
+    assert!(ranked.iter().all(|ranked| ranked.score == 0));
+    for ranked in &ranked {
+        let mut slots: Vec<usize> = ranked.assignments.iter()
+            .map(|assignment| schedule.courses[assignment.course][assignment.pattern].slots[0])
+            .collect();
+        slots.sort_unstable();
+        assert!(slots.windows(2).all(|pair| pair[1] == pair[0] + 1));
+    }
+}
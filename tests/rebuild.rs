@@ -0,0 +1,25 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, matrix, sorted};
+
+#[test]
+fn matrices_back_to_back() {
+    let board = dominoes(4, 4);
+    let small = matrix(&["1100", "0011", "1010", "0101"]);
+    assert_eq!(Node::solve_all(&board).len(), 36);
+    assert_eq!(sorted(Node::solve_all(&small)), [vec![0, 1], vec![2, 3]]);
+    assert_eq!(Node::solve_all(&board).len(), 36);
+}
+
+#[test]
+fn builds_are_all_alike() {
+    // nothing carries over from one build to the next, however many there are
+    let input = matrix(&["1100", "0011", "1010", "0101"]);
+    let first = Node::build(&input);
+    for _ in 0..100_000 {
+        assert_eq!(Node::build(&input), first);
+    }
+    assert_eq!(first.check_invariants(), Ok(()));
+    assert_eq!(sorted(Node::solve_all(&input)), [vec![0, 1], vec![2, 3]]);
+}
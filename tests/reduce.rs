@@ -0,0 +1,48 @@
+mod common;
+
+use dlx::{ConflictError, Node};
+use common::{dominoes, matrix, permutations, sorted};
+
+/// Check that committing to rows and solving the rest finds the solutions with those rows.
+fn check_reduce(input: &[Vec<bool>], chosen: &[usize]) {
+    let reduced = Node::reduce(input, chosen).unwrap();
+    assert_eq!(reduced.matrix.len(), reduced.rows.len());
+    let from_reduced = Node::solve_all(&reduced.matrix).iter().map(|solution| {
+        let mut rows = chosen.to_vec();
+        rows.extend(reduced.original_rows(solution));
+        rows
+    }).collect();
+
+    let with_givens = Node::solve_all_with_givens(input, chosen).unwrap();
+    assert_eq!(sorted(from_reduced), sorted(with_givens), "{chosen:?}");
+}
+
+#[test]
+fn reduced_solutions_extend_the_chosen_rows() {
+    let board = dominoes(4, 3);
+    for chosen in [vec![0], vec![1], vec![0, 5], vec![2, 9]] {
+        check_reduce(&board, &chosen);
+    }
+    let permutations = permutations(4);
+    for chosen in [vec![0], vec![1, 4], vec![3, 6, 9]] {
+        check_reduce(&permutations, &chosen);
+    }
+}
+
+#[test]
+fn reduced_maps_back() {
+    let input = matrix(&["1100", "0011", "1000", "0110", "0001"]);
+    let reduced = Node::reduce(&input, &[0]).unwrap();
+    assert_eq!(reduced.columns, vec![2, 3]);
+    assert_eq!(reduced.rows, vec![1, 4]);
+    assert_eq!(reduced.matrix, matrix(&["11", "01"]));
+}
+
+#[test]
+fn overlapping_rows_conflict() {
+    let input = dominoes(4, 3);
+    // rows 0 and 1 both cover the first cell
+    let error = Node::reduce(&input, &[0, 1]).unwrap_err();
+    assert_eq!(error, ConflictError { column: 0, rows: (0, 1) });
+    assert_eq!(error.to_string(), "rows 0 and 1 both cover column 0");
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::blocks::{Game2D, TransformOp};
+use common::{matrix, permutations};
+
+#[test]
+fn board_one_cell_too_large() {
+    let mut game = Game2D::from_strings(3, 3, vec!["###", "###", "##"]).unwrap();
+    let cells = game.suggest_empty_cells(2, None).unwrap();
+    assert_eq!(cells.len(), 1);
+
+    // the blocks tile the rest of the board
+    let (x, y) = cells[0];
+    let mut filled = Game2D::from_strings(3, 3, vec!["###", "###", "##", "#"]).unwrap();
+    filled.fix(3, TransformOp::default(), x, y);
+    let matrix = filled.get_matrix();
+    assert!(Node::solve_once(&matrix).is_some());
+}
+
+#[test]
+fn feasible_problems_need_nothing_dropped() {
+    let mut game = Game2D::from_strings(2, 2, vec!["##", "##"]).unwrap();
+    assert_eq!(game.suggest_empty_cells(2, None), Some(Vec::new()));
+    assert_eq!(Node::suggest_relaxations(&permutations(3), 2, None), Some(Vec::new()));
+}
+
+#[test]
+fn relaxations_are_bounded() {
+    let input = matrix(&["110", "011"]);
+    assert_eq!(Node::suggest_relaxations(&input, 2, None), Some(vec![0]));
+    assert_eq!(Node::suggest_relaxations(&input, 0, None), None);
+
+    let mut game = Game2D::from_strings(3, 2, vec!["##", "##"]).unwrap();
+    assert_eq!(game.suggest_empty_cells(1, None), None);
+    assert_eq!(game.suggest_empty_cells(2, None).unwrap().len(), 2);
+}
@@ -0,0 +1,54 @@
+use dlx::generation::blocks3d::{Block3D, Game3D};
+
+/// Split a rendering into its layers, checking the headings.
+fn layers(rendering: &str) -> Vec<Vec<&str>> {
+    let mut layers: Vec<Vec<&str>> = Vec::new();
+    for line in rendering.lines() {
+        if let Some(z) = line.strip_prefix("layer ") {
+            assert_eq!(z, layers.len().to_string());
+            layers.push(Vec::new());
+        } else {
+            layers.last_mut().expect("a grid before the first heading").push(line);
+        }
+    }
+    layers
+}
+
+#[test]
+fn slabs_fill_a_cube() {
+    let game = Game3D::from_layers(2, 2, 2, vec![vec!["##\n##"], vec!["##\n##"]]).unwrap();
+    let rows = game.solve_once().unwrap();
+    let grid = game.solution_grid_3d(&rows);
+    let rendering = game.render_solution(&rows);
+    let layers = layers(&rendering);
+    assert_eq!(layers.len(), 2);
+
+    for (z, layer) in layers.iter().enumerate() {
+        assert_eq!(layer.len(), 2, "layer {z}");
+        for (y, line) in layer.iter().enumerate() {
+            let drawn: Vec<char> = grid[z][y].iter().map(|cell| game.block_char(cell.unwrap())).collect();
+            assert_eq!(line.chars().collect::<Vec<_>>(), drawn, "layer {z}, line {y}");
+        }
+    }
+    for block in 0..2 {
+        let c = game.block_char(block);
+        assert_eq!(layers.iter().flatten().flat_map(|line| line.chars()).filter(|&drawn| drawn == c).count(), 4, "{c}");
+    }
+    assert_ne!(game.block_char(0), game.block_char(1));
+}
+
+#[test]
+fn missing_cells_are_blank() {
+    let blocks = vec![Block3D::from_layers(&["##\n#."]).unwrap().named("L"), Block3D::from_layers(&["##"]).unwrap().named("I")];
+    let game = Game3D::from_layer_strings(vec!["##\n##", "#.\n.."], blocks);
+    let rows = game.solve_once().unwrap();
+    let grid = game.solution_grid_3d(&rows);
+    assert_eq!(grid[1][0][1], None);
+    // the pieces are told apart by their names on every layer
+    let rendering = game.render_solution(&rows);
+    assert_eq!(rendering.chars().filter(|&c| c == 'L').count(), 3);
+    assert_eq!(rendering.chars().filter(|&c| c == 'I').count(), 2);
+    let top = &layers(&rendering)[1];
+    assert!(matches!(top[0], "L " | "I "), "{rendering}");
+    assert_eq!(top[1], "  ");
+}
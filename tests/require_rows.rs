@@ -0,0 +1,51 @@
+mod common;
+
+use dlx::{Backend, DlxError, Node, SolveOptions, Solver};
+use common::{matrix, random, sorted};
+
+#[test]
+fn required_and_forbidden_rows_filter_the_solutions() {
+    for seed in 0..200 {
+        let input = random(seed, 14, 6, 35);
+        let all = Node::solve_all(&input);
+        let seed = seed as usize;
+        let require: Vec<usize> = [seed % 14].into_iter().filter(|&row| seed.is_multiple_of(3) && input[row].contains(&true)).collect();
+        let forbid: Vec<usize> = [seed * 7 % 14, seed * 11 % 14].into_iter().filter(|row| !require.contains(row)).take(seed % 3).collect();
+        let expected = sorted(all.into_iter().filter(|solution| {
+            require.iter().all(|row| solution.contains(row)) && forbid.iter().all(|row| !solution.contains(row))
+        }).collect());
+
+        for backend in [Backend::Bitset, Backend::DancingLinks] {
+            let options = SolveOptions { require_rows: require.clone(), forbid_rows: forbid.clone(), backend, ..SolveOptions::default() };
+            let solutions = Node::solve_all_with_options(&input, &options).0;
+            // required rows come first, as givens
+            assert!(solutions.iter().all(|solution| solution.starts_with(&require)), "seed {seed}");
+            assert_eq!(sorted(solutions), expected, "seed {seed}");
+            assert_eq!(Node::count_solutions_with_options(&input, &options).0, expected.len() as u64, "seed {seed}");
+
+            let mut solver = Solver::with_options(&input, options);
+            assert_eq!(sorted(solver.solve_all()), expected, "seed {seed}");
+            assert_eq!(solver.count_solutions(), expected.len() as u64, "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn forbidding_a_whole_column_is_unsatisfiable() {
+    let input = matrix(&["110", "011", "001", "100"]);
+    let options = SolveOptions { forbid_rows: vec![2, 1], ..SolveOptions::default() };
+    assert!(Node::solve_all_with_options(&input, &options).0.is_empty());
+    assert_eq!(Node::count_solutions_with_options(&input, &options).0, 0);
+}
+
+#[test]
+fn contradictory_rows_are_errors() {
+    let input = matrix(&["110", "011", "001", "100"]);
+    let error = |require_rows: Vec<usize>, forbid_rows: Vec<usize>| {
+        Node::try_solve_all_with_options(&input, &SolveOptions { require_rows, forbid_rows, ..SolveOptions::default() }).err()
+    };
+    assert_eq!(error(vec![0, 1], vec![]), Some(DlxError::RequiredConflict { column: 1, rows: (0, 1) }));
+    assert_eq!(error(vec![2], vec![2]), Some(DlxError::RequiredAndForbidden { row: 2 }));
+    assert_eq!(error(vec![], vec![7]), Some(DlxError::RowOutOfRange { row: 7 }));
+    assert_eq!(error(vec![0, 2], vec![]), None);
+}
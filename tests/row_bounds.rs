@@ -0,0 +1,40 @@
+mod common;
+
+use dlx::{Backend, Node, SolveOptions, Solver};
+use common::{dominoes, random, sorted};
+
+#[test]
+fn pruning_matches_filtering_afterwards() {
+    for seed in 0..200 {
+        let input = random(seed, 14, 6, 30);
+        let all = Node::solve_all(&input);
+        let seed = seed as usize;
+        let min_rows = (seed.is_multiple_of(3)).then_some(seed / 3 % 6);
+        let max_rows = (!seed.is_multiple_of(4)).then_some(seed / 4 % 6);
+        let expected = sorted(all.into_iter().filter(|solution| {
+            min_rows.is_none_or(|min| solution.len() >= min) && max_rows.is_none_or(|max| solution.len() <= max)
+        }).collect());
+
+        for backend in [Backend::Bitset, Backend::DancingLinks] {
+            for memo_capacity in [None, Some(64)] {
+                let options = SolveOptions { min_rows, max_rows, backend, memo_capacity, ..SolveOptions::default() };
+                assert_eq!(sorted(Node::solve_all_with_options(&input, &options).0), expected, "seed {seed}");
+                assert_eq!(Node::count_solutions_with_options(&input, &options).0, expected.len() as u64, "seed {seed}");
+
+                let mut solver = Solver::with_options(&input, options);
+                assert_eq!(sorted(solver.solve_all()), expected, "seed {seed}");
+                assert_eq!(solver.count_solutions(), expected.len() as u64, "seed {seed}");
+            }
+        }
+    }
+}
+
+#[test]
+fn max_rows_prunes_the_search() {
+    let input = dominoes(6, 6);
+    let unbounded = Node::solve_all_with_options(&input, &SolveOptions::default()).1.nodes;
+    // every tiling takes 18 dominoes
+    let (solutions, stats) = Node::solve_all_with_options(&input, &SolveOptions { max_rows: Some(5), ..SolveOptions::default() });
+    assert!(solutions.is_empty());
+    assert!(stats.nodes < unbounded / 10, "{} nodes against {unbounded}", stats.nodes);
+}
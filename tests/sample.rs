@@ -0,0 +1,22 @@
+use dlx::generation::blocks::Game2D;
+use std::collections::HashSet;
+
+#[test]
+fn seeds_spread_over_the_tilings() {
+    // five ways to tile the board, times the orders of the dominoes
+    let mut game = Game2D::from_strings(4, 2, vec!["##", "##", "##", "##"]).unwrap();
+    let mut grids = HashSet::new();
+    for seed in 0..50 {
+        let solution = game.sample_solution(seed).unwrap();
+        assert!(game.validate_layout(&solution), "seed {seed}");
+        assert_eq!(game.sample_solution(seed).as_ref(), Some(&solution), "seed {seed} isn't reproducible");
+        grids.insert(solution.grid);
+    }
+    assert!(grids.len() >= 3, "only {} tilings", grids.len());
+}
+
+#[test]
+fn nothing_to_sample_without_a_tiling() {
+    let mut game = Game2D::from_strings(2, 2, vec!["###"]).unwrap();
+    assert_eq!(game.sample_solution(1), None);
+}
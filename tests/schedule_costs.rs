@@ -0,0 +1,49 @@
+use dlx::generation::courses::{Assignment, Pattern, Schedule};
+
+#[test]
+fn conflicting_favourites_force_a_dearer_schedule() {
+    // every course is cheapest in slot 0, so at most one of them gets it
+    let availability = vec![vec![true; 3]; 3];
+    let costs = vec![vec![1, 5, 9], vec![1, 4, 9], vec![1, 9, 2]];
+    let schedule = Schedule::from_availability(&availability, Some(&costs));
+
+    let (cost, mut assignments) = schedule.solve_best().unwrap();
+    assignments.sort_unstable();
+    assert_eq!(cost, 1 + 4 + 2);
+    assert_eq!(assignments, vec![
+        Assignment { course: 0, pattern: 0 },
+        Assignment { course: 1, pattern: 1 },
+        Assignment { course: 2, pattern: 2 },
+    ]);
+    assert_eq!(schedule.cost(&assignments), cost);
+}
+
+#[test]
+fn best_k_come_by_cost() {
+    let availability = vec![vec![true; 3]; 3];
+    let costs = vec![vec![1, 5, 9], vec![1, 4, 9], vec![1, 9, 2]];
+    let schedule = Schedule::from_availability(&availability, Some(&costs));
+    let best: Vec<u64> = schedule.solve_best_k(3).into_iter().map(|(cost, _)| cost).collect();
+    assert_eq!(best, vec![7, 8, 14]);
+    assert_eq!(schedule.solve_best_k(100).len(), 6);
+}
+
+#[test]
+fn patterns_can_take_several_slots() {
+    let schedule = Schedule {
+        slots: 3,
+        courses: vec![
+            vec![Pattern { slots: vec![0, 1], cost: 2 }, Pattern { slots: vec![2], cost: 7 }],
+            vec![Pattern { slots: vec![1], cost: 0 }],
+        ],
+        ..Schedule::default()
+    };
+    assert_eq!(schedule.solve_best().unwrap().0, 7);
+}
+
+#[test]
+fn schedules_without_patterns() {
+    assert_eq!(Schedule::default().solve_best(), Some((0, Vec::new())));
+    let schedule = Schedule { courses: vec![Vec::new()], ..Schedule::default() };
+    assert_eq!(schedule.solve_best(), None);
+}
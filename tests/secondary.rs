@@ -0,0 +1,54 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions};
+use common::{matrix, random, sorted};
+
+/// The N-Queens puzzle with no rows for empty diagonals: the ranks and files first, then the
+/// diagonals and anti-diagonals, which may stay empty.
+fn queens(n: usize) -> Vec<Vec<bool>> {
+    let width = 2 * n + 2 * (2 * n - 1);
+    (0..n * n).map(|square| {
+        let (rank, file) = (square / n, square % n);
+        let mut row = vec![false; width];
+        row[rank] = true;
+        row[n + file] = true;
+        row[2 * n + rank + file] = true;
+        row[4 * n - 1 + rank + n - 1 - file] = true;
+        row
+    }).collect()
+}
+
+#[test]
+fn queens_need_secondary_diagonals() {
+    for (n, count) in [(1, 1), (4, 2), (5, 10), (6, 4), (8, 92)] {
+        let input = queens(n);
+        // some diagonal is always left empty
+        assert!(Node::solve_all(&input).is_empty() || n == 1, "{n} queens");
+        assert_eq!(Node::solve_all_with_secondary(&input, 2 * n).len(), count, "{n} queens");
+
+        let options = SolveOptions { primary_columns: Some(2 * n), ..SolveOptions::default() };
+        assert_eq!(Node::count_solutions_with_options(&input, &options).0, count as u64, "{n} queens");
+        for column_strategy in [ColumnStrategy::Bucketed, ColumnStrategy::Lookahead { candidates: 4 }] {
+            let options = SolveOptions { column_strategy, early_contradiction: true, ..options.clone() };
+            assert_eq!(Node::solve_all_with_options(&input, &options).0.len(), count, "{n} queens, {column_strategy:?}");
+        }
+    }
+}
+
+#[test]
+fn secondary_columns_are_covered_at_most_once() {
+    // the last column may stay empty, but rows 0 and 2 can't both cover it
+    let input = matrix(&["101", "010", "011", "100"]);
+    assert_eq!(sorted(Node::solve_all(&input)), [vec![0, 1], vec![2, 3]]);
+    assert_eq!(sorted(Node::solve_all_with_secondary(&input, 2)), [vec![0, 1], vec![1, 3], vec![2, 3]]);
+    // with every column secondary, choosing nothing solves it
+    assert_eq!(Node::solve_all_with_secondary(&input, 0), [Vec::<usize>::new()]);
+}
+
+#[test]
+fn all_primary_is_the_plain_solve() {
+    for seed in 0..100 {
+        let input = random(seed, 10, 8, 30);
+        assert_eq!(Node::solve_all_with_secondary(&input, 8), Node::solve_all(&input), "seed {seed}");
+    }
+}
@@ -0,0 +1,35 @@
+use dlx::{DiversityMetric, Node, SolutionSet, SolutionStorage};
+use dlx::generation::blocks::Game2D;
+
+#[test]
+fn selection_picks_the_obvious_pair() {
+    // three solutions close together, and one sharing no row with them
+    let set = SolutionSet::from_indices(vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 1, 2, 3], vec![4, 5, 6, 7, 8, 9]], 10);
+    assert_eq!(set.select_diverse(2, DiversityMetric::SymmetricDifference), vec![3, 2]);
+    assert_eq!(set.select_diverse(10, DiversityMetric::SymmetricDifference).len(), 4);
+    assert!(set.select_diverse(0, DiversityMetric::SymmetricDifference).is_empty());
+
+    let sizes = |a: &[usize], b: &[usize]| a.len().abs_diff(b.len());
+    assert_eq!(set.select_diverse(2, DiversityMetric::Custom(&sizes)), vec![3, 0]);
+}
+
+#[test]
+fn selection_breaks_ties_by_index() {
+    let set = SolutionSet::from_indices(vec![vec![0], vec![1], vec![1], vec![0]], 2);
+    assert_eq!(set.select_diverse(2, DiversityMetric::SymmetricDifference), vec![1, 0]);
+    let set = set.convert(SolutionStorage::Bitsets);
+    assert_eq!(set.select_diverse(2, DiversityMetric::SymmetricDifference), vec![1, 0]);
+}
+
+#[test]
+fn games_select_different_tilings() {
+    let mut game = Game2D::from_strings(4, 2, vec!["##", "##", "##", "##"]).unwrap();
+    let matrix = game.get_matrix();
+    let solutions = Node::solve_all_set(&matrix, SolutionStorage::Bitsets);
+    let picked = game.select_diverse(&solutions, 3);
+
+    assert_eq!(picked.len(), 3);
+    assert!(picked.iter().all(|&i| i < solutions.len()));
+    assert!(picked[0] != picked[1] && picked[1] != picked[2] && picked[0] != picked[2]);
+    assert_eq!(game.select_diverse(&solutions, 3), picked);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use dlx::Node;
+use common::{matrix, random, sorted};
+
+/// Whether some rows cover every column.
+fn covers(input: &[Vec<bool>], rows: &[usize]) -> bool {
+    (0..input[0].len()).all(|col| rows.iter().any(|&row| input[row][col]))
+}
+
+/// The minimal covers, found by trying every set of rows.
+fn minimal_covers(input: &[Vec<bool>]) -> Vec<Vec<usize>> {
+    let mut found = Vec::new();
+    for mask in 0u32..1 << input.len() {
+        let rows: Vec<usize> = (0..input.len()).filter(|i| mask >> i & 1 == 1).collect();
+        let minimal = (0..rows.len()).all(|skip| {
+            let fewer: Vec<usize> = rows.iter().enumerate().filter(|&(i, _)| i != skip).map(|(_, &row)| row).collect();
+            !covers(input, &fewer)
+        });
+        if covers(input, &rows) && minimal { found.push(rows); }
+    }
+    found
+}
+
+#[test]
+fn overlapping_rows_cover() {
+    let input = matrix(&["110", "011"]);
+    assert!(Node::solve_all(&input).is_empty());
+    assert_eq!(Node::solve_cover(&input, false), vec![vec![0, 1]]);
+    assert_eq!(Node::solve_cover(&input, true), vec![vec![0, 1]]);
+}
+
+#[test]
+fn column_without_rows_has_no_cover() {
+    let input = matrix(&["10", "10"]);
+    assert!(Node::solve_cover(&input, false).is_empty());
+    assert!(Node::solve_cover(&input, true).is_empty());
+}
+
+#[test]
+fn covers_match_brute_force() {
+    for seed in 0..400 {
+        let input = random(seed, 10, 6, 35);
+        let expected = sorted(minimal_covers(&input));
+        let all = Node::solve_cover(&input, false);
+        // each set of rows is found once
+        assert_eq!(all.len(), expected.len(), "seed {seed}");
+        assert_eq!(sorted(all), expected, "seed {seed}");
+
+        let minimum = Node::solve_cover(&input, true);
+        match expected.iter().map(Vec::len).min() {
+            None => assert!(minimum.is_empty(), "seed {seed}"),
+            Some(fewest) => {
+                assert_eq!(minimum.len(), 1, "seed {seed}");
+                assert_eq!(minimum[0].len(), fewest, "seed {seed}");
+                assert!(covers(&input, &minimum[0]), "seed {seed}");
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use dlx::debugging::{MAX_CHECKS, shrink_instance};
+use std::cell::Cell;
+
+/// Check if a matrix has a diagonal of two ones next to a diagonal of two zeros.
+fn has_pattern(matrix: &[Vec<bool>]) -> bool {
+    let width = matrix.first().map_or(0, Vec::len);
+    (1..matrix.len()).any(|y| (1..width).any(|x| {
+        matrix[y - 1][x - 1] && !matrix[y - 1][x] && !matrix[y][x - 1] && matrix[y][x]
+    }))
+}
+
+/// A random 20x20 matrix with the pattern somewhere in it.
+fn padded() -> Vec<Vec<bool>> {
+    let mut matrix = common::random(7, 20, 20, 30);
+    matrix[5][5] = true;
+    matrix[5][6] = false;
+    matrix[6][5] = false;
+    matrix[6][6] = true;
+    matrix
+}
+
+#[test]
+fn shrinks_to_the_pattern() {
+    let shrunk = shrink_instance(&padded(), |matrix| has_pattern(matrix));
+    // removing any row or column of the pattern loses it
+    assert_eq!(shrunk, common::matrix(&["10", "01"]));
+    assert_eq!(shrink_instance(&padded(), |matrix| has_pattern(matrix)), shrunk);
+}
+
+#[test]
+fn checks_are_capped() {
+    let checks = Cell::new(0);
+    let shrunk = shrink_instance(&padded(), |matrix| {
+        checks.set(checks.get() + 1);
+        has_pattern(matrix)
+    });
+    assert!(has_pattern(&shrunk));
+    assert!(checks.get() <= MAX_CHECKS);
+
+    // a predicate that only holds for the input can't shrink anything, however long it's tried
+    let input = padded();
+    let checks = Cell::new(0);
+    let shrunk = shrink_instance(&input, |matrix| {
+        checks.set(checks.get() + 1);
+        *matrix == input
+    });
+    assert_eq!(shrunk, input);
+    assert!(checks.get() <= MAX_CHECKS);
+}
+
+#[test]
+fn inputs_without_the_failure_stay() {
+    let matrix = vec![vec![false; 3]];
+    assert_eq!(shrink_instance(&matrix, |matrix| has_pattern(matrix)), matrix);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+use dlx::{DlxError, Node, ProgressCallback, SolveOptions, SolveOutcome};
+use common::dominoes;
+
+/// Wakes a thread waiting on a future by unparking it.
+struct Unpark(Thread);
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) { self.0.unpark(); }
+}
+
+/// Run a future to completion on the current thread.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(Unpark(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) { return output; }
+        thread::park();
+    }
+}
+
+#[test]
+fn awaiting_matches_solve_all() {
+    let input = dominoes(4, 4);
+    let outcome = block_on(Node::solve_all_async(input.clone(), SolveOptions::default()));
+    assert_eq!(outcome, SolveOutcome::Complete(Node::solve_all(&input)));
+}
+
+#[test]
+fn dropping_stops_the_worker() {
+    // an odd number of cells has no tiling, but the search takes far longer than the test to find out
+    let nodes = Arc::new(AtomicU64::new(0));
+    let reported = nodes.clone();
+    let progress = ProgressCallback::new(1000, move |progress| reported.store(progress.nodes, Ordering::Relaxed));
+    let options = SolveOptions { progress: Some(progress), ..SolveOptions::default() };
+    let future = Node::solve_all_async(dominoes(7, 7), options);
+
+    let start = Instant::now();
+    while nodes.load(Ordering::Relaxed) == 0 {
+        assert!(start.elapsed() < Duration::from_secs(10), "the worker never started");
+        thread::sleep(Duration::from_millis(1));
+    }
+    drop(future);
+
+    thread::sleep(Duration::from_millis(50));
+    let stopped = nodes.load(Ordering::Relaxed);
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(nodes.load(Ordering::Relaxed), stopped, "the worker kept searching");
+}
+
+#[test]
+fn bad_matrices_fail_before_starting() {
+    let ragged = vec![vec![true, false], vec![true]];
+    let error = Node::try_solve_all_async(ragged.clone(), SolveOptions::default()).err();
+    assert_eq!(error, Some(DlxError::RaggedRow { row: 1, expected: 2, got: 1 }));
+    assert!(panic::catch_unwind(|| Node::solve_all_async(ragged, SolveOptions::default())).is_err());
+}
+
+#[test]
+fn panics_of_the_worker_reach_the_future() {
+    let progress = ProgressCallback::new(1, |_| panic!("stopped by the progress callback"));
+    let options = SolveOptions { progress: Some(progress), ..SolveOptions::default() };
+    let future = Node::solve_all_async(dominoes(4, 4), options);
+    let message = *panic::catch_unwind(AssertUnwindSafe(|| block_on(future))).unwrap_err().downcast::<&str>().unwrap();
+    assert_eq!(message, "stopped by the progress callback");
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use dlx::Node;
+use common::{dominoes, random};
+
+#[test]
+fn buffers_match_the_allocating_solves() {
+    let (mut all, mut once) = (vec![vec![9; 40]; 100], vec![7; 3]);
+    for seed in 0..300 {
+        let input = random(seed, 10, 6, 30);
+        Node::solve_all_into(&input, &mut all);
+        assert_eq!(all, Node::solve_all(&input), "seed {seed}");
+
+        let found = Node::solve_once_into(&input, &mut once);
+        match Node::solve_once(&input) {
+            Some(solution) => assert!(found && once == solution, "seed {seed}"),
+            None => assert!(!found && once.is_empty(), "seed {seed}"),
+        }
+    }
+}
+
+#[test]
+fn inner_buffers_are_reused() {
+    let input = dominoes(4, 4);
+    let mut out = Vec::new();
+    Node::solve_all_into(&input, &mut out);
+    assert_eq!(out, Node::solve_all(&input));
+    let first = out[0].as_ptr();
+    Node::solve_all_into(&input, &mut out);
+    assert_eq!(out[0].as_ptr(), first);
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::blocks::Game2D;
+
+#[test]
+fn first_solutions_of_a_game() {
+    let mut game = Game2D::from_strings(5, 4, vec!["#..\n###", "###\n#..", "##\n##", "####", "##", "##"]).unwrap();
+    let matrix = game.get_matrix();
+    let all = Node::solve_all(&matrix);
+    assert!(all.len() > 100);
+    for limit in [0, 1, 5, 100, all.len(), all.len() + 10] {
+        assert_eq!(Node::solve_n(&matrix, limit), all[..limit.min(all.len())], "limit {limit}");
+    }
+    assert_eq!(Node::solve_n(&matrix, 1).pop(), Node::solve_once(&matrix));
+}
+
+#[test]
+fn limits_past_the_count_find_everything() {
+    for seed in 0..100 {
+        let input = common::random(seed, 10, 6, 35);
+        let all = Node::solve_all(&input);
+        assert_eq!(Node::solve_n(&input, usize::MAX), all, "seed {seed}");
+        assert_eq!(Node::solve_n(&input, 1).pop(), Node::solve_once(&input), "seed {seed}");
+    }
+}
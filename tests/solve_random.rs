@@ -0,0 +1,32 @@
+mod common;
+
+use dlx::{Node, SolveOptions};
+use std::collections::HashSet;
+use common::{dominoes, random, sorted};
+
+#[test]
+fn seeds_give_different_solutions() {
+    let input = dominoes(4, 4);
+    let all = sorted(Node::solve_all(&input));
+    let mut seen = HashSet::new();
+    for seed in 0..50 {
+        let mut solution = Node::solve_random(&input, seed).unwrap();
+        assert_eq!(Node::solve_random(&input, seed).unwrap(), solution, "seed {seed} isn't reproducible");
+        solution.sort();
+        assert!(all.contains(&solution), "seed {seed}");
+        seen.insert(solution);
+    }
+    assert!(seen.len() > 10, "only {} of 36 tilings", seen.len());
+}
+
+#[test]
+fn finds_a_solution_whenever_there_is_one() {
+    for seed in 0..200 {
+        let input = random(seed, 12, 8, 30);
+        let all = Node::solve_all(&input);
+        assert_eq!(Node::solve_random(&input, seed).is_some(), !all.is_empty(), "seed {seed}");
+        // shuffling the rows only changes the order the solutions come in
+        let options = SolveOptions { row_seed: Some(seed), ..SolveOptions::default() };
+        assert_eq!(sorted(Node::solve_all_with_options(&input, &options).0), sorted(all), "seed {seed}");
+    }
+}
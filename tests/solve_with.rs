@@ -0,0 +1,41 @@
+mod common;
+
+use dlx::{Node, SolveOptions};
+use std::ops::ControlFlow;
+use common::dominoes;
+
+#[test]
+fn breaking_stops_the_search() {
+    let input = dominoes(4, 4);
+    let mut calls = 0;
+    let flow = Node::solve_with(&input, |_| {
+        calls += 1;
+        ControlFlow::Break(())
+    });
+    assert!(flow.is_break());
+    assert_eq!(calls, 1);
+
+    // breaking on the third solution, after seeing the first three
+    let mut seen = Vec::new();
+    let (flow, stats) = Node::solve_with_options(&input, &SolveOptions::default(), |solution| {
+        seen.push(solution.to_vec());
+        if seen.len() == 3 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+    });
+    assert!(flow.is_break());
+    assert_eq!(seen, Node::solve_n(&input, 3));
+    assert!(stats.nodes < Node::solve_all_with_options(&input, &SolveOptions::default()).1.nodes);
+}
+
+#[test]
+fn continuing_sees_every_solution() {
+    let input = dominoes(4, 4);
+    let mut seen = Vec::new();
+    let flow = Node::solve_with(&input, |solution| {
+        seen.push(solution.to_vec());
+        ControlFlow::Continue(())
+    });
+    assert!(flow.is_continue());
+    assert_eq!(seen.len(), 36);
+    assert_eq!(seen, Node::solve_all(&input));
+    assert_eq!(Node::solve_once(&input).as_ref(), seen.first());
+}
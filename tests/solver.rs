@@ -0,0 +1,51 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions, Solver};
+use common::{dominoes, random, sorted};
+
+#[test]
+fn solving_twice_gives_the_same_results() {
+    let input = dominoes(4, 4);
+    let mut solver = Solver::new(&input);
+    let first = solver.solve_all();
+    assert_eq!(first, Node::solve_all(&input));
+    assert_eq!(solver.solve_all(), first);
+    assert_eq!(solver.solve_once(), Node::solve_once(&input));
+    assert_eq!(solver.solve_once(), Node::solve_once(&input));
+    assert_eq!(solver.count_solutions(), 36);
+    assert_eq!(solver.solve_all(), first);
+}
+
+#[test]
+fn givens_leave_the_structure_as_it_was() {
+    for seed in 0..100 {
+        let input = random(seed, 12, 8, 30);
+        let all = Node::solve_all(&input);
+        for column_strategy in [ColumnStrategy::Smallest, ColumnStrategy::Bucketed] {
+            let options = SolveOptions { column_strategy, early_contradiction: true, ..SolveOptions::default() };
+            let mut solver = Solver::with_options(&input, options);
+            let first = solver.solve_all();
+            assert_eq!(sorted(first.clone()), sorted(all.clone()), "seed {seed}");
+
+            for given in 0..input.len() {
+                assert_eq!(solver.solve_with_givens(&[given]), Node::solve_all_with_givens(&input, &[given]), "seed {seed}");
+                // whether or not these two conflict, the structure is put back as it was
+                let _ = solver.solve_with_givens(&[given, (given + 1) % input.len()]);
+            }
+            assert_eq!(solver.solve_all(), first, "seed {seed}");
+            assert_eq!(solver.count_solutions(), all.len() as u64, "seed {seed}");
+            assert_eq!(solver.solve_once().is_some(), !all.is_empty(), "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn stats_are_of_the_last_solve() {
+    let input = dominoes(4, 4);
+    let mut solver = Solver::new(&input);
+    solver.solve_all();
+    assert_eq!(solver.stats().solutions, 36);
+    let nodes = solver.stats().nodes;
+    solver.solve_all();
+    assert_eq!((solver.stats().solutions, solver.stats().nodes), (36, nodes));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use dlx::{Node, SolveStats};
+use common::{matrix, random};
+
+/// The counts of the stats, in the order nodes, covers, backtracks, max depth and solutions.
+fn counts(stats: &SolveStats) -> (u64, u64, u64, usize, u64) {
+    (stats.nodes, stats.covers, stats.backtracks, stats.max_depth, stats.solutions)
+}
+
+#[test]
+fn counts_on_tiny_matrices() {
+    // the root branches on column 0, then the only row of column 1 solves it
+    let (solutions, stats) = Node::solve_all_with_stats(&matrix(&["10", "01"]));
+    assert_eq!(solutions, [[0, 1]]);
+    assert_eq!(counts(&stats), (3, 2, 0, 2, 1));
+
+    // column 1 is the smallest, and its row covers column 0 too
+    let (solutions, stats) = Node::solve_all_with_stats(&matrix(&["11", "10"]));
+    assert_eq!(solutions, [[0]]);
+    assert_eq!(counts(&stats), (2, 2, 0, 1, 1));
+
+    // column 1 has no rows, so the root covers it and finds nothing to try
+    let (solutions, stats) = Node::solve_all_with_stats(&matrix(&["10", "10"]));
+    assert!(solutions.is_empty());
+    assert_eq!(counts(&stats), (1, 1, 1, 0, 0));
+}
+
+#[test]
+fn counting_searches_the_same_tree() {
+    for seed in 0..50 {
+        let input = random(seed, 12, 8, 30);
+        let (solutions, listed) = Node::solve_all_with_stats(&input);
+        assert_eq!(listed.solutions, solutions.len() as u64, "seed {seed}");
+        assert!(listed.max_depth <= 8, "seed {seed}");
+        let (_, counted) = Node::count_solutions_with_options(&input, &Default::default());
+        assert_eq!(counts(&counted), counts(&listed), "seed {seed}");
+    }
+}
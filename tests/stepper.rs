@@ -0,0 +1,61 @@
+mod common;
+
+use dlx::{Node, SearchObserver, StepEvent, Stepper};
+use common::{permutations, random};
+
+/// The events of an observed search, as a stepper gives them.
+#[derive(Default)]
+struct Events(Vec<StepEvent>);
+
+impl SearchObserver for Events {
+    fn on_choose_column(&mut self, col: usize, size: usize) { self.0.push(StepEvent::ChoseColumn { col, size }); }
+    fn on_try_row(&mut self, row: usize, depth: usize) { self.0.push(StepEvent::TriedRow { row, depth }); }
+    fn on_cover(&mut self, col: usize) { self.0.push(StepEvent::Covered { col }); }
+    fn on_uncover(&mut self, col: usize) { self.0.push(StepEvent::Uncovered { col }); }
+    fn on_solution(&mut self, rows: &[usize]) { self.0.push(StepEvent::FoundSolution(rows.to_vec())); }
+}
+
+/// Step a search to the end, checking the partial solution and column sizes after every step.
+fn step_through(input: &[Vec<bool>]) -> Vec<StepEvent> {
+    let mut stepper = Stepper::new(input);
+    let mut events = Vec::new();
+    loop {
+        let sizes = stepper.column_sizes();
+        assert_eq!(sizes.len(), input[0].len());
+        let event = stepper.step();
+        match event {
+            StepEvent::Done => break,
+            StepEvent::TriedRow { row, depth } => {
+                assert_eq!(stepper.partial_solution().len(), depth + 1);
+                assert_eq!(stepper.partial_solution().last(), Some(&row));
+            }
+            StepEvent::Covered { col } => assert!(sizes[col].is_some() && stepper.column_sizes()[col].is_none()),
+            StepEvent::Uncovered { col } => assert!(sizes[col].is_none() && stepper.column_sizes()[col].is_some()),
+            _ => {}
+        }
+        events.push(event);
+    }
+    assert_eq!(stepper.step(), StepEvent::Done);
+    assert!(stepper.column_sizes().iter().all(Option::is_some));
+    assert!(stepper.partial_solution().is_empty());
+    events
+}
+
+#[test]
+fn stepping_finds_the_solutions_in_order() {
+    for seed in 0..60 {
+        let input = random(seed, 14, 7, 30);
+        let events = step_through(&input);
+        let solutions: Vec<Vec<usize>> = events.iter().filter_map(|event| match event {
+            StepEvent::FoundSolution(solution) => Some(solution.clone()),
+            _ => None,
+        }).collect();
+        assert_eq!(solutions, Node::solve_all(&input), "seed {seed}");
+
+        let mut observed = Events::default();
+        Node::solve_all_observed(&input, &mut observed);
+        assert_eq!(events, observed.0, "seed {seed}");
+    }
+    let solutions = step_through(&permutations(4)).into_iter().filter(|event| matches!(event, StepEvent::FoundSolution(_))).count();
+    assert_eq!(solutions, 24);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use dlx::{ColumnStrategy, Node, SolveOptions};
+use common::{dominoes, matrix, random, sorted};
+
+fn with(column_strategy: ColumnStrategy) -> SolveOptions {
+    SolveOptions { column_strategy, ..SolveOptions::default() }
+}
+
+#[test]
+fn strategies_find_the_same_solutions() {
+    for seed in 0..100 {
+        let input = random(seed, 12, 8, 30);
+        let expected = sorted(Node::solve_all(&input));
+        for column_strategy in [ColumnStrategy::First, ColumnStrategy::Random { seed }, ColumnStrategy::Random { seed: 7 }] {
+            let (solutions, _) = Node::solve_all_with_options(&input, &with(column_strategy));
+            assert_eq!(sorted(solutions), expected, "seed {seed}, {column_strategy:?}");
+            assert_eq!(Node::count_solutions_with_options(&input, &with(column_strategy)).0, expected.len() as u64);
+        }
+    }
+}
+
+#[test]
+fn smallest_breaks_ties_leftmost() {
+    // both columns have two rows, so the search branches on column 0 first
+    let input = matrix(&["10", "01", "10", "01"]);
+    assert_eq!(Node::solve_all(&input), [[0, 1], [0, 3], [2, 1], [2, 3]]);
+    // and on the smaller column otherwise
+    assert_eq!(Node::solve_all(&matrix(&["10", "10", "10", "01"]))[0], [3, 0]);
+}
+
+#[test]
+fn first_takes_the_leftmost_column() {
+    let input = matrix(&["10", "10", "10", "01"]);
+    assert_eq!(Node::solve_all_with_options(&input, &with(ColumnStrategy::First)).0, [[0, 3], [1, 3], [2, 3]]);
+}
+
+#[test]
+fn random_choices_are_reproducible() {
+    let input = dominoes(4, 4);
+    let orders: Vec<Vec<Vec<usize>>> = (0..8).map(|seed| Node::solve_all_with_options(&input, &with(ColumnStrategy::Random { seed })).0).collect();
+    for (seed, order) in orders.iter().enumerate() {
+        let again = Node::solve_all_with_options(&input, &with(ColumnStrategy::Random { seed: seed as u64 })).0;
+        assert_eq!(&again, order, "seed {seed}");
+    }
+    assert!(orders.iter().any(|order| order != &orders[0]), "every seed searched the same way");
+}
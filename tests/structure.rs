@@ -0,0 +1,41 @@
+//! The structure owns every node, so nothing the caller drops can leave it dangling, and matrices
+//! the search can't start on are errors instead of panics deep in the search. The crate has no
+//! `unsafe` code of its own; these tests also run under Miri with
+//! `cargo +nightly miri test --test structure`.
+
+mod common;
+
+use dlx::{Dlx, DlxError, Node};
+use common::{dominoes, sorted};
+
+#[test]
+fn structures_outlive_their_input() {
+    let input = dominoes(3, 4);
+    let expected = Node::solve_all(&input);
+    let structure = Node::build(&input);
+    let copy = structure.clone();
+    let prepared = Dlx::new(&input);
+    drop(input);
+    drop(structure);
+
+    assert_eq!(copy.check_invariants(), Ok(()));
+    assert_eq!(sorted(prepared.solve_all()), sorted(expected));
+}
+
+#[test]
+fn matrices_without_columns_are_errors() {
+    assert_eq!(Node::try_build(&[]), Err(DlxError::EmptyMatrix));
+    assert_eq!(Node::try_solve_all(&[]), Err(DlxError::EmptyMatrix));
+    assert_eq!(Node::try_solve_all(&[vec![], vec![]]), Err(DlxError::NoColumns));
+    assert_eq!(Node::try_solve_once(&[vec![]]), Err(DlxError::NoColumns));
+    let ragged = [vec![true, false], vec![true]];
+    assert_eq!(Node::try_solve_once(&ragged), Err(DlxError::RaggedRow { row: 1, expected: 2, got: 1 }));
+}
+
+#[test]
+fn columns_without_rows_leave_nothing_to_solve() {
+    // the middle column has no rows, so the search runs out of rows instead of columns
+    let input = vec![vec![true, false, false], vec![false, false, true]];
+    assert_eq!(Node::try_solve_all(&input), Ok(Vec::new()));
+    assert_eq!(Node::try_solve_once(&input), Ok(None));
+}
@@ -0,0 +1,52 @@
+use dlx::generation::sudoku::{get_matrix, matrix_row};
+use dlx::{Dlx, Node, Solver};
+use std::time::Instant;
+
+const PUZZLE: &str = "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+const SOLUTION: &str = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+/// Get the rows of the matrix putting every digit of a grid in its cell.
+fn rows(digits: &str) -> Vec<usize> {
+    digits.chars().enumerate().filter(|&(_, digit)| digit != '0')
+        .map(|(i, digit)| matrix_row(i / 9, i % 9, digit.to_digit(10).unwrap() as u8))
+        .collect()
+}
+
+/// Write a solution of the matrix as a grid.
+fn digits(solution: &[usize]) -> String {
+    let mut grid = ['0'; 81];
+    for &row in solution {
+        grid[row / 9] = char::from_digit(row as u32 % 9 + 1, 10).unwrap();
+    }
+    grid.iter().collect()
+}
+
+#[test]
+fn solves_the_classic_puzzle() {
+    let matrix = get_matrix();
+    let clues = rows(PUZZLE);
+    let solutions = Node::solve_all_with_givens(&matrix, &clues).unwrap();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(digits(&solutions[0]), SOLUTION);
+
+    let mut solver = Solver::new(&matrix);
+    assert_eq!(solver.solve_with_givens(&clues).unwrap(), solutions);
+    assert_eq!(solver.solve_with_givens(&clues).unwrap(), solutions);
+    // a solved grid is a solution of the matrix
+    let mut solved = rows(SOLUTION);
+    solved.sort();
+    assert_eq!(Node::verify_solution(&matrix, &solved), Ok(()));
+    assert!(Dlx::new(&matrix).solve_once().is_some());
+}
+
+#[test]
+#[ignore = "benchmark, run with --release --ignored --nocapture"]
+fn sudoku_benchmark() {
+    let matrix = get_matrix();
+    let clues = rows(PUZZLE);
+    for _ in 0..3 {
+        let start = Instant::now();
+        let solutions = Node::solve_all_with_givens(&matrix, &clues).unwrap();
+        println!("{} solution in {:?}", solutions.len(), start.elapsed());
+    }
+}
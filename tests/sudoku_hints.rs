@@ -0,0 +1,87 @@
+use dlx::generation::sudoku::{Grid, HintKind, SudokuError, hints};
+
+const SOLUTION: &str = "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+fn grid(digits: &str) -> Grid {
+    let mut grid = [[0; 9]; 9];
+    for (i, digit) in digits.chars().enumerate() {
+        grid[i / 9][i % 9] = digit.to_digit(10).unwrap() as u8;
+    }
+    grid
+}
+
+/// Find four cells of two boxes holding `a b` on one line and `b a` on the other, which can be swapped
+/// to give another solution.
+fn deadly_rectangle(grid: &Grid) -> [(usize, usize); 4] {
+    for r1 in 0..9 {
+        for r2 in r1 + 1..9 {
+            for c1 in 0..9 {
+                for c2 in c1 + 1..9 {
+                    let two_boxes = (r1 / 3 == r2 / 3) != (c1 / 3 == c2 / 3);
+                    if two_boxes && grid[r1][c1] == grid[r2][c2] && grid[r1][c2] == grid[r2][c1] {
+                        return [(r1, c1), (r1, c2), (r2, c1), (r2, c2)];
+                    }
+                }
+            }
+        }
+    }
+    panic!("no deadly rectangle");
+}
+
+#[test]
+fn near_complete_hints_match_the_solution() {
+    let solution = grid(SOLUTION);
+    let mut puzzle = solution;
+    let emptied = [(0, 0), (1, 4), (4, 4), (8, 8), (3, 7), (6, 2)];
+    for (row, col) in emptied { puzzle[row][col] = 0; }
+
+    let found = hints(&puzzle, false).unwrap();
+    assert_eq!(found.len(), emptied.len());
+    for hint in found {
+        assert!(emptied.contains(&(hint.row, hint.col)));
+        assert_eq!(hint.digit, solution[hint.row][hint.col]);
+    }
+    assert_eq!(hints(&solution, false), Ok(Vec::new()));
+}
+
+#[test]
+fn cells_differing_between_solutions_have_no_hint() {
+    let solution = grid(SOLUTION);
+    let rectangle = deadly_rectangle(&solution);
+    let mut puzzle = solution;
+    for (row, col) in rectangle { puzzle[row][col] = 0; }
+    assert_eq!(hints(&puzzle, false), Ok(Vec::new()));
+
+    let emptied = [(0, 0), (4, 4), (8, 8)].into_iter().filter(|cell| !rectangle.contains(cell));
+    for (row, col) in emptied.clone() { puzzle[row][col] = 0; }
+    let found = hints(&puzzle, false).unwrap();
+    assert_eq!(found.len(), emptied.count());
+    for hint in found {
+        assert!(!rectangle.contains(&(hint.row, hint.col)));
+        assert_eq!(hint.digit, solution[hint.row][hint.col]);
+    }
+}
+
+#[test]
+fn easiest_hints_take_no_search() {
+    let puzzle = grid("800000000003600000070090200050007000000045700000100030001000068008500010090000400");
+    let easiest = hints(&puzzle, true).unwrap();
+    assert!(easiest.iter().all(|hint| hint.kind != HintKind::Forced));
+    let all = hints(&puzzle, false).unwrap();
+    assert!(easiest.iter().all(|hint| all.contains(hint)));
+}
+
+#[test]
+fn contradictions_are_errors() {
+    let mut puzzle = grid(SOLUTION);
+    puzzle[0][0] = 0;
+    puzzle[0][1] = 5;
+    assert_eq!(hints(&puzzle, false), Err(SudokuError::Contradiction));
+
+    let mut puzzle = [[0; 9]; 9];
+    puzzle[0][0] = 1;
+    puzzle[1][1] = 1;
+    assert_eq!(hints(&puzzle, false), Err(SudokuError::Contradiction));
+    puzzle[1][1] = 10;
+    assert_eq!(hints(&puzzle, false), Err(SudokuError::InvalidDigit { row: 1, col: 1, digit: 10 }));
+}
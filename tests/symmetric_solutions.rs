@@ -0,0 +1,28 @@
+mod common;
+
+use dlx::Node;
+use dlx::generation::blocks::Game2D;
+use common::{PENTOMINOES, dominoes};
+
+#[test]
+fn symmetric_tilings_count_once() {
+    // a rectangle has four symmetries, none of which leaves a pentomino tiling as it is
+    let some = vec![PENTOMINOES[1], PENTOMINOES[2], PENTOMINOES[6], PENTOMINOES[9]];
+    for (w, h, blocks, unique) in [(5, 4, some, 5), (20, 3, PENTOMINOES.to_vec(), 2)] {
+        let mut game = Game2D::from_strings(w, h, blocks).unwrap();
+        let input = game.get_matrix();
+        let all = Node::solve_all(&input);
+        let deduped = Node::solve_all_dedup(&input, game.symmetry_canonicalizer());
+        assert_eq!(deduped.len(), unique, "{w}x{h}");
+        assert_eq!(all.len(), 4 * unique, "{w}x{h}");
+        assert!(deduped.iter().all(|solution| all.contains(solution)), "{w}x{h}");
+    }
+}
+
+#[test]
+fn first_solution_of_every_key_is_kept() {
+    let input = dominoes(4, 4);
+    let all = Node::solve_all(&input);
+    assert_eq!(Node::solve_all_dedup(&input, |solution| solution.to_vec()), all);
+    assert_eq!(Node::solve_all_dedup(&input, |_| Vec::new()), all[..1]);
+}
@@ -0,0 +1,84 @@
+use std::panic;
+use dlx::{DlxError, Node, SolveOptions};
+use dlx::generation::presets::n_queens;
+
+/// The permutation of the columns of `n_queens(n)` moving the queen on every square by `move_square`,
+/// found by matching the squares of every column.
+fn moving(n: usize, move_square: impl Fn(usize, usize) -> (usize, usize)) -> Vec<usize> {
+    let input = n_queens(n);
+    let squares = |col: usize| -> Vec<usize> { (0..n * n).filter(|&row| input[row][col]).collect() };
+    (0..input[0].len()).map(|col| {
+        let mut moved: Vec<usize> = squares(col).into_iter().map(|square| {
+            let (row, col) = move_square(square / n, square % n);
+            row * n + col
+        }).collect();
+        moved.sort_unstable();
+        (0..input[0].len()).position(|other| squares(other) == moved).unwrap()
+    }).collect()
+}
+
+#[test]
+fn one_solution_per_orbit_of_the_board() {
+    for (n, all, distinct) in [(4, 2, 1), (5, 10, 2), (6, 4, 1), (8, 92, 12)] {
+        let input = n_queens(n);
+        let rotate = moving(n, |row, col| (col, n - 1 - row));
+        let mirror = moving(n, |row, col| (row, n - 1 - col));
+        let plain = SolveOptions::default();
+        let first = Node::solve_all_with_options(&input, &plain).0;
+        assert_eq!(first.len(), all);
+
+        let options = SolveOptions { symmetries: vec![rotate.clone(), mirror], ..SolveOptions::default() };
+        let (solutions, stats) = Node::solve_all_with_options(&input, &options);
+        assert_eq!(solutions.len(), distinct, "{n} queens");
+        assert_eq!(stats.solutions, all as u64, "{n} queens");
+        assert_eq!(solutions[0], first[0], "{n} queens");
+        // without the mirror, mirrored solutions stay apart
+        let rotations = SolveOptions { symmetries: vec![rotate], ..SolveOptions::default() };
+        assert!(Node::solve_all_with_options(&input, &rotations).0.len() >= distinct, "{n} queens");
+    }
+}
+
+#[test]
+fn symmetries_must_permute_rows() {
+    let input = n_queens(4);
+    let width = input[0].len();
+    let mut repeated: Vec<usize> = (0..width).collect();
+    repeated[0] = 1;
+    let options = SolveOptions { symmetries: vec![repeated], ..SolveOptions::default() };
+    assert_eq!(Node::try_solve_all_with_options(&input, &options).unwrap_err(), DlxError::NotAPermutation { symmetry: 0 });
+
+    // swapping the first rank with the first diagonal leaves row 1 without a match
+    let mut swapped: Vec<usize> = (0..width).collect();
+    swapped.swap(0, 8);
+    let options = SolveOptions { symmetries: vec![(0..width).collect(), swapped], ..SolveOptions::default() };
+    assert_eq!(Node::try_solve_all_with_options(&input, &options).unwrap_err(), DlxError::SymmetryBreaksRow { symmetry: 1, row: 1 });
+    assert!(panic::catch_unwind(|| Node::solve_all_with_options(&input, &options)).is_err());
+}
+
+/// The pairs of `n` points, every perfect matching of them being a solution.
+fn pairs(n: usize) -> Vec<Vec<bool>> {
+    (0..n).flat_map(|a| (a + 1..n).map(move |b| (0..n).map(|x| x == a || x == b).collect())).collect()
+}
+
+#[test]
+fn orbits_too_large_to_search_are_told() {
+    // swapping the first two points and shifting all of them generate every permutation of the points
+    let symmetries = |n: usize| {
+        let mut swap: Vec<usize> = (0..n).collect();
+        swap.swap(0, 1);
+        vec![swap, (0..n).map(|x| (x + 1) % n).collect()]
+    };
+
+    // the 105 matchings of 8 points are all symmetric
+    let options = SolveOptions { symmetries: symmetries(8), ..SolveOptions::default() };
+    let (solutions, stats) = Node::solve_all_with_options(&pairs(8), &options);
+    assert_eq!((solutions.len(), stats.solutions, stats.capped_orbits), (1, 105, 0));
+
+    // but the 10 395 of 12 points are more than are looked at
+    let options = SolveOptions { symmetries: symmetries(12), ..SolveOptions::default() };
+    let (solutions, stats) = Node::solve_n_outcome(&pairs(12), 3, &options);
+    assert_eq!(stats.capped_orbits, stats.solutions);
+    assert!(stats.capped_orbits > 0);
+    // so symmetric solutions are handed back apart
+    assert!(solutions.into_inner().len() > 1);
+}
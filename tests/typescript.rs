@@ -0,0 +1,93 @@
+//! Checks the TypeScript declarations `wasm-bindgen` writes to `pkg/dlx.d.ts`. The wasm toolchain
+//! isn't needed by the other tests, so these are ignored: build the package with
+//!
+//! ```sh
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --crate-type cdylib
+//! wasm-bindgen --out-dir pkg target/wasm32-unknown-unknown/release/dlx.wasm
+//! ```
+//!
+//! then run `cargo test --test typescript -- --ignored`.
+
+use std::fs;
+use std::path::Path;
+
+/// A function declared by the package, with the doc comment right before it.
+struct Function {
+    name: String,
+    params: String,
+    returns: String,
+    doc: String,
+}
+
+/// Get the declarations of the package, without comments, and the functions it exports.
+fn declarations() -> (String, Vec<Function>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("pkg/dlx.d.ts");
+    let source = fs::read_to_string(&path).unwrap_or_else(|error| panic!("{}: {error}, build the package first", path.display()));
+    let (mut code, mut functions, mut doc) = (String::new(), Vec::new(), String::new());
+    let mut in_comment = false;
+
+    for line in source.lines().map(str::trim) {
+        if in_comment || line.starts_with("/*") {
+            in_comment = !line.ends_with("*/");
+            doc.push_str(line);
+            doc.push('\n');
+            continue;
+        }
+        let line = line.split("//").next().unwrap();
+        code.push_str(line);
+        code.push('\n');
+
+        if let Some(function) = line.strip_prefix("export function ") {
+            let (name, rest) = function.split_once('(').unwrap();
+            let (params, returns) = rest.rsplit_once("):").unwrap();
+            functions.push(Function {
+                name: name.to_string(),
+                params: params.to_string(),
+                returns: returns.trim().trim_end_matches(';').to_string(),
+                doc: std::mem::take(&mut doc),
+            });
+        } else if !line.is_empty() {
+            doc.clear();
+        }
+    }
+    (code, functions)
+}
+
+#[test]
+#[ignore = "needs `wasm-bindgen` to write pkg/dlx.d.ts"]
+fn exports_are_typed() {
+    let (_, functions) = declarations();
+    let expected = [
+        ("js_solve_once", "input: Matrix", "Solution | null"),
+        ("js_solve_all", "input: Matrix", "Solution[]"),
+        ("js_matrix_from_blocks", "w: number, h: number, blocks: Blocks", "Matrix"),
+        ("js_matrix_from_courses", "input: Courses", "Matrix"),
+        (
+            "js_best_schedule",
+            "input: Courses, costs: CourseCosts, constraints: ScheduleConstraints | undefined",
+            "ScheduleResult",
+        ),
+    ];
+
+    for (name, params, returns) in expected {
+        let function = functions.iter().find(|function| function.name == name).unwrap_or_else(|| panic!("{name} isn't exported"));
+        assert_eq!(function.params, params, "{name}");
+        assert_eq!(function.returns, returns, "{name}");
+        assert!(function.doc.contains("@throws {DlxError}"), "{name} doesn't say what it throws");
+    }
+    for function in &functions {
+        let signature = format!("{} {}", function.params, function.returns);
+        let mut words = signature.split(|c: char| !c.is_alphanumeric() && c != '_');
+        assert!(words.all(|word| word != "any" && word != "unknown"), "{} takes or returns `any`", function.name);
+    }
+}
+
+#[test]
+#[ignore = "needs `wasm-bindgen` to write pkg/dlx.d.ts"]
+fn types_are_declared() {
+    let (code, _) = declarations();
+    assert!(code.contains("export interface DlxError extends Error {"));
+    for name in ["Matrix", "Solution", "Blocks", "Courses", "CourseCosts", "Assignment", "CourseGroup", "ScheduleConstraints", "BestSchedule", "ScheduleResult"] {
+        assert!(code.contains(&format!("export type {name} = ")), "{name} isn't declared");
+    }
+}
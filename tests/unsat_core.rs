@@ -0,0 +1,58 @@
+mod common;
+
+use dlx::Node;
+use common::{permutations, row_of};
+
+/// Keep only some columns of a matrix, and the rows covering any of them.
+fn restricted(input: &[Vec<bool>], columns: &[usize]) -> Vec<Vec<bool>> {
+    input.iter()
+        .map(|row| columns.iter().map(|&x| row[x]).collect::<Vec<_>>())
+        .filter(|row| row.contains(&true))
+        .collect()
+}
+
+/// The permutations of 5 elements, widened by `extra` columns.
+fn widened(extra: usize) -> Vec<Vec<bool>> {
+    permutations(5).into_iter().map(|mut row| {
+        row.resize(10 + extra, false);
+        row
+    }).collect()
+}
+
+#[test]
+fn core_is_the_clashing_columns() {
+    // with the other columns ignored, two columns always have a cover if each has a row, so the
+    // smallest clash is three columns each pair of which shares a row
+    let mut input = widened(3);
+    input.push(row_of(13, &[10, 11]));
+    input.push(row_of(13, &[11, 12]));
+    input.push(row_of(13, &[10, 12]));
+    assert!(Node::solve_once(&input).is_none());
+
+    let core = Node::unsat_core(&input, None).unwrap();
+    assert_eq!(core, vec![10, 11, 12]);
+    assert!(Node::solve_once(&restricted(&input, &core)).is_none());
+}
+
+#[test]
+fn core_of_a_column_without_rows() {
+    let mut input = widened(2);
+    input.push(row_of(12, &[0, 10]));
+    let core = Node::unsat_core(&input, None).unwrap();
+    assert_eq!(core, vec![11]);
+}
+
+#[test]
+fn feasible_problems_have_no_core() {
+    assert_eq!(Node::unsat_core(&permutations(4), None), None);
+}
+
+#[test]
+fn budget_bounds_every_check() {
+    let mut input = widened(3);
+    input.push(row_of(13, &[10, 11]));
+    input.push(row_of(13, &[11, 12]));
+    input.push(row_of(13, &[10, 12]));
+    // too small a budget to show even the whole problem infeasible
+    assert_eq!(Node::unsat_core(&input, Some(1)), None);
+}
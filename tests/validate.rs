@@ -0,0 +1,61 @@
+use dlx::generation::blocks::{Block2D, CountMode, Game2D, TransformOp, ValidationError, ValidationWarning};
+
+#[test]
+fn reports_every_problem() {
+    let mut game = Game2D::from_strings(3, 3, vec!["####", "##", "##", "#.#"]).unwrap();
+    game.fix(1, TransformOp::default(), 0, 0);
+    game.fix(2, TransformOp::default(), 1, 0);
+    let report = game.validate();
+    assert!(!report.is_ok());
+    assert!(report.errors.contains(&ValidationError::NoPlacement { block: 0 }));
+    assert!(report.errors.contains(&ValidationError::AreaMismatch { board: 9, blocks: 10 }));
+    assert!(report.errors.contains(&ValidationError::FixedOverlap { blocks: (1, 2), cell: (1, 0) }));
+    assert_eq!(report.warnings, vec![ValidationWarning::Disconnected { block: 3 }]);
+
+    let game = Game2D::from_strings(2, 2, vec!["##", "##"]).unwrap();
+    assert!(game.validate().is_ok());
+}
+
+#[test]
+fn reports_parity_and_uncoverable_cells() {
+    // the T covers three cells of one color and one of the other, the domino one of each
+    let game = Game2D::from_strings(3, 2, vec!["###\n.#.", "##"]).unwrap();
+    assert_eq!(game.validate().errors, vec![ValidationError::Parity]);
+
+    let game = Game2D::from_strings(3, 3, vec!["###\n#.#\n###"]).unwrap();
+    let report = game.validate();
+    assert_eq!(report.errors, vec![
+        ValidationError::AreaMismatch { board: 9, blocks: 8 },
+        ValidationError::Parity,
+        ValidationError::UncoverableCell { cell: (1, 1) },
+    ]);
+    assert_eq!(report.errors[2].to_string(), "no block can cover (1, 1)");
+}
+
+/// A board the dominoes tile, along with a block without squares, which validation rejects but the
+/// solver places nowhere.
+fn with_empty_block() -> Game2D {
+    let mut game = Game2D::from_strings(2, 2, vec!["##", "##"]).unwrap();
+    game.blocks.push(Block2D { w: 1, h: 1, data: vec![vec![false]], name: None });
+    game
+}
+
+#[test]
+fn every_solve_validates_first() {
+    let mut game = with_empty_block();
+    assert_eq!(game.validate().errors, vec![ValidationError::NoCells { block: 2 }]);
+    assert!(game.solve_once().is_some());
+    assert!(game.count_solutions(CountMode::Raw) > 0);
+
+    game.validate_first = true;
+    assert_eq!(game.solve_once(), None);
+    assert_eq!(game.sample_solution(7), None);
+    assert_eq!(game.solve_min_flips(), None);
+    assert!(game.solve_fault_free(10).is_empty());
+    for mode in [CountMode::Raw, CountMode::UpToBoardSymmetry, CountMode::UpToBoardSymmetryAndIdenticalPieces] {
+        assert_eq!(game.count_solutions(mode), 0);
+    }
+    assert!(game.max_distinct_pieces(2).is_empty());
+    assert!(game.placement_heat().iter().all(|&(_, heat)| heat == 0));
+    assert!(game.coverage_heatmap().iter().flatten().all(|counts| counts.is_empty()));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use dlx::{Node, VerifyError};
+use common::{dominoes, matrix};
+
+#[test]
+fn reports_how_a_solution_fails() {
+    let input = matrix(&["1100", "0011", "0110", "1000", "0001"]);
+    assert_eq!(Node::verify_solution(&input, &[0, 1]), Ok(()));
+    assert_eq!(Node::verify_solution(&input, &[0]), Err(VerifyError::Uncovered(2)));
+    assert_eq!(Node::verify_solution(&input, &[0, 2, 4]), Err(VerifyError::DoublyCovered { col: 1, rows: (0, 2) }));
+    assert_eq!(Node::verify_solution(&input, &[0, 9]), Err(VerifyError::RowOutOfRange(9)));
+    assert_eq!(VerifyError::Uncovered(2).to_string(), "column 2 isn't covered");
+}
+
+#[test]
+fn secondary_columns_may_stay_uncovered() {
+    let input = matrix(&["1100", "0011", "0110", "1000", "0001"]);
+    assert_eq!(Node::verify_solution_with_secondary(&input, &[0], 2), Ok(()));
+    assert_eq!(Node::verify_solution_with_secondary(&input, &[3, 2, 4], 2), Ok(()));
+    assert_eq!(Node::verify_solution_with_secondary(&input, &[2], 2), Err(VerifyError::Uncovered(0)));
+    // secondary columns still can't be covered twice
+    assert_eq!(Node::verify_solution_with_secondary(&input, &[1, 2], 2), Err(VerifyError::DoublyCovered { col: 2, rows: (1, 2) }));
+}
+
+#[test]
+fn solver_solutions_verify() {
+    let input = dominoes(4, 4);
+    let solutions = Node::solve_all(&input);
+    assert_eq!(solutions.len(), 36);
+    for solution in solutions { assert_eq!(Node::verify_solution(&input, &solution), Ok(())); }
+}
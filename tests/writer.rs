@@ -0,0 +1,57 @@
+mod common;
+
+use std::io::{self, Write};
+use dlx::{Node, SolutionFormat};
+use common::{dominoes, matrix};
+
+/// A writer that fails once it has taken a number of writes.
+struct Full {
+    writes_left: usize,
+    written: Vec<u8>,
+}
+
+impl Write for Full {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.writes_left == 0 { return Err(io::Error::other("full")); }
+        self.writes_left -= 1;
+        self.written.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Parse solutions written one to a line, with the numbers split by `separator`.
+fn parse(text: &[u8], separator: char) -> Vec<Vec<usize>> {
+    let text = String::from_utf8(text.to_vec()).unwrap();
+    text.lines().map(|line| line.trim_matches(['[', ']']).split(separator).map(|row| row.parse().unwrap()).collect()).collect()
+}
+
+#[test]
+fn written_solutions_parse_back() {
+    let input = dominoes(4, 4);
+    let all = Node::solve_all(&input);
+    let mut out = Vec::new();
+    assert_eq!(Node::solve_all_to_writer(&input, &mut out, SolutionFormat::Lines).unwrap(), all.len() as u64);
+    assert_eq!(parse(&out, ' '), all);
+
+    let mut out = Vec::new();
+    assert_eq!(Node::solve_all_to_writer(&input, &mut out, SolutionFormat::JsonLines).unwrap(), all.len() as u64);
+    assert!(out.starts_with(b"["));
+    assert_eq!(parse(&out, ','), all);
+
+    let mut out = Vec::new();
+    assert_eq!(Node::solve_all_to_writer(&matrix(&["10", "10"]), &mut out, SolutionFormat::JsonLines).unwrap(), 0);
+    assert!(out.is_empty());
+}
+
+#[test]
+fn write_errors_stop_the_search() {
+    let mut out = Full { writes_left: 3, written: Vec::new() };
+    let error = Node::solve_all_to_writer(&dominoes(4, 4), &mut out, SolutionFormat::Lines).unwrap_err();
+    assert_eq!(error.to_string(), "full");
+    // the solutions written before the error are whole lines
+    assert_eq!(parse(&out.written, ' '), Node::solve_all(&dominoes(4, 4))[..3]);
+}
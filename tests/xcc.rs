@@ -0,0 +1,75 @@
+mod common;
+
+use dlx::{Node, XccRow};
+use common::{random, sorted};
+
+/// Find the solutions of a problem with colors by trying every set of rows.
+fn brute_force(rows: &[XccRow], primary: usize, secondary: usize) -> Vec<Vec<usize>> {
+    (0u32..1 << rows.len()).filter_map(|set| {
+        let chosen: Vec<usize> = (0..rows.len()).filter(|row| set >> row & 1 == 1).collect();
+        let mut counts = vec![0; primary];
+        let mut colors: Vec<Option<u32>> = vec![None; secondary];
+        for &row in &chosen {
+            for &x in &rows[row].primary { counts[x] += 1; }
+            for &(x, color) in &rows[row].secondary {
+                // color 0 shares with nothing
+                match colors[x] {
+                    Some(other) if other != color || color == 0 => return None,
+                    _ => colors[x] = Some(color),
+                }
+            }
+        }
+        counts.iter().all(|&count| count == 1).then_some(chosen)
+    }).collect()
+}
+
+#[test]
+fn agrees_with_brute_force() {
+    for seed in 0..400 {
+        let (primary, secondary) = (1 + seed as usize % 5, seed as usize % 4);
+        let input = random(seed, 1 + seed as usize % 12, primary + secondary, 40);
+        let rows: Vec<XccRow> = input.iter().enumerate().map(|(i, entries)| {
+            let mut columns: Vec<usize> = (0..primary).filter(|&x| entries[x]).collect();
+            if columns.is_empty() { columns.push(i % primary); }
+            let colors = (0..secondary).filter(|&x| entries[primary + x]).map(|x| (x, ((i + x) % 3) as u32)).collect();
+            XccRow { primary: columns, secondary: colors }
+        }).collect();
+        assert_eq!(sorted(Node::solve_xcc(&rows, primary, secondary)), sorted(brute_force(&rows, primary, secondary)), "seed {seed}");
+    }
+}
+
+#[test]
+fn word_squares() {
+    let words = ["at", "to", "ta", "oo"];
+    // a slot for each line and column of the square, and a secondary column for each cell, colored by
+    // the letter the word puts there
+    let slots = [[0, 1], [2, 3], [0, 2], [1, 3]];
+    let mut rows = Vec::new();
+    for word in words {
+        for (slot, cells) in slots.iter().enumerate() {
+            let secondary = cells.iter().zip(word.bytes()).map(|(&cell, letter)| (cell, u32::from(letter))).collect();
+            rows.push(XccRow { primary: vec![slot], secondary });
+        }
+    }
+
+    let mut squares: Vec<String> = Node::solve_xcc(&rows, 4, 4).iter().map(|solution| {
+        let line = |slot| words[solution.iter().find(|&&row| row % 4 == slot).unwrap() / 4];
+        format!("{}/{}", line(0), line(1))
+    }).collect();
+    squares.sort();
+
+    // the squares whose columns are words too
+    let mut expected = Vec::new();
+    for top in words {
+        for bottom in words {
+            let (top, bottom) = (top.as_bytes(), bottom.as_bytes());
+            let columns = [[top[0], bottom[0]], [top[1], bottom[1]]];
+            if columns.iter().all(|column| words.iter().any(|word| word.as_bytes() == column)) {
+                expected.push(format!("{}/{}", str::from_utf8(top).unwrap(), str::from_utf8(bottom).unwrap()));
+            }
+        }
+    }
+    expected.sort();
+    assert!(!expected.is_empty());
+    assert_eq!(squares, expected);
+}
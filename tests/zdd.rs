@@ -0,0 +1,46 @@
+mod common;
+
+use std::collections::HashSet;
+use dlx::{Node, SolveOptions};
+use common::{dominoes, permutations, random};
+
+fn fixtures() -> Vec<Vec<Vec<bool>>> {
+    let mut fixtures: Vec<_> = (0..60).map(|seed| random(seed, 16, 8, 25)).collect();
+    fixtures.push(dominoes(4, 4));
+    fixtures.push(permutations(4));
+    fixtures
+}
+
+#[test]
+fn zdd_holds_the_solutions() {
+    for input in fixtures() {
+        let zdd = Node::solve_to_zdd(&input);
+        let (count, _) = Node::count_solutions_with_options(&input, &SolveOptions::default());
+        let all: HashSet<Vec<usize>> = Node::solve_all(&input).into_iter().collect();
+        assert_eq!(zdd.count(), count);
+        assert_eq!(zdd.iter().collect::<HashSet<_>>(), all);
+        if let Some(sample) = zdd.sample(7) { assert!(all.contains(&sample)); }
+    }
+}
+
+#[test]
+fn restricting_splits_the_solutions() {
+    for input in fixtures() {
+        let zdd = Node::solve_to_zdd(&input);
+        let all = Node::solve_all(&input);
+        for row in 0..input.len() {
+            let (with, without) = (zdd.restrict(row, true), zdd.restrict(row, false));
+            assert_eq!(with.count() + without.count(), zdd.count());
+            assert_eq!(with.count() as usize, all.iter().filter(|solution| solution.contains(&row)).count());
+            assert!(with.iter().all(|solution| solution.contains(&row)));
+            assert!(without.iter().all(|solution| !solution.contains(&row)));
+        }
+    }
+}
+
+#[test]
+fn zdd_is_smaller_than_the_solutions() {
+    let zdd = Node::solve_to_zdd(&dominoes(4, 10));
+    assert_eq!(zdd.count(), 18_061);
+    assert!(zdd.node_count() < 1000, "{} nodes", zdd.node_count());
+}